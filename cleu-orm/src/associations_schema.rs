@@ -0,0 +1,8 @@
+use crate::Schema;
+
+/// Pushes the tables reached by the associations of a table into a [Schema]
+pub trait AssociationsSchema {
+  /// `from_table` is the name of the table that owns these associations, which must already be
+  /// part of `schema`.
+  fn push_schema(&self, schema: &mut Schema, from_table: &'static str) -> crate::Result<()>;
+}
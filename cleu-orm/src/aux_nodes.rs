@@ -0,0 +1,138 @@
+use crate::{
+  write_generated_key, write_sql_value, BindValue, PrimaryKey, SqlDialect, SqlWriterMode,
+  TableSourceAssociation,
+};
+use smallvec::SmallVec;
+
+/// Auxiliary information about the nodes of a graph of tables that is being written.
+///
+/// Avoids writing the same node more than once and, for INSERT operations, makes it possible to
+/// only write a node after all its parents along with every foreign key contributed by them.
+#[derive(Debug, Default)]
+pub struct AuxNodes {
+  // (hash, foreign key field, foreign key value) of nodes that are waiting for other parents
+  foreign_keys: Vec<(u64, &'static str, ForeignKey)>,
+  // (hash, number of parents that weren't written yet)
+  parents: Vec<(u64, usize)>,
+  // (hash, table name)
+  visited: SmallVec<[(u64, &'static str); 64]>,
+}
+
+impl AuxNodes {
+  /// Creates an instance without nodes
+  #[inline]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Registers a parent of the node identified by `hash`. Returns `true` if this is the first
+  /// registered parent.
+  #[inline]
+  pub(crate) fn add_parent(&mut self, hash: u64) -> bool {
+    match self.parents.binary_search_by(|elem| elem.0.cmp(&hash)) {
+      Err(idx) => {
+        self.parents.insert(idx, (hash, 1));
+        true
+      }
+      Ok(idx) => {
+        if let Some(elem) = self.parents.get_mut(idx) {
+          elem.1 = elem.1.wrapping_add(1);
+        }
+        false
+      }
+    }
+  }
+
+  /// Keeps the foreign key contributed by a parent of a node that is still waiting for others
+  #[inline]
+  pub(crate) fn push_foreign_key(&mut self, hash: u64, field: &'static str, value: ForeignKey) {
+    self.foreign_keys.push((hash, field, value));
+  }
+
+  /// Signals that a parent of the node identified by `hash` was written. Returns `true` if all
+  /// parents were written, which also includes nodes without registered parents.
+  #[inline]
+  pub(crate) fn remove_parent(&mut self, hash: u64) -> bool {
+    let idx = self.parents.binary_search_by(|elem| elem.0.cmp(&hash)).ok();
+    if let Some(elem) = idx.and_then(|local_idx| self.parents.get_mut(local_idx)) {
+      elem.1 = elem.1.saturating_sub(1);
+      elem.1 == 0
+    } else {
+      true
+    }
+  }
+
+  /// Removes and returns all foreign keys previously contributed to the node identified by `hash`
+  #[inline]
+  pub(crate) fn take_foreign_keys(&mut self, hash: u64) -> Vec<(&'static str, ForeignKey)> {
+    self
+      .foreign_keys
+      .extract_if(.., |elem| elem.0 == hash)
+      .map(|(_, field, value)| (field, value))
+      .collect()
+  }
+
+  /// Marks the node identified by `hash` as visited. Returns `true` if it was already visited.
+  #[inline]
+  pub(crate) fn visit(&mut self, hash: u64, table: &'static str) -> crate::Result<bool> {
+    match self
+      .visited
+      .binary_search_by(|&(ref local_hash, _)| local_hash.cmp(&hash))
+      .and_then(|idx| self.visited.get(idx).map(|elem| elem.1).ok_or(idx))
+    {
+      Err(could_be_idx) => self.visited.insert(could_be_idx, (hash, table)),
+      Ok(existent_table) => {
+        if existent_table == table {
+          return Ok(true);
+        } else {
+          return Err(crate::Error::HashCollision(hash, existent_table, table));
+        }
+      }
+    }
+    Ok(false)
+  }
+}
+
+/// Value of a foreign key contributed by a parent node
+#[derive(Clone, Debug)]
+pub(crate) enum ForeignKey {
+  /// Primary key generated by the row at the second index of the statement at the first index
+  GeneratedKey(usize, usize),
+  /// Primary key known beforehand
+  Value(BindValue),
+}
+
+impl ForeignKey {
+  /// Value of the source key column named `from_id`
+  #[inline]
+  pub(crate) fn new<V>(from_id: &str, tsa: &TableSourceAssociation<'_, V>) -> Self
+  where
+    V: PrimaryKey,
+  {
+    if let Some(elem) = tsa.source_value() {
+      let idx = tsa.source_key_names().iter().position(|name| *name == from_id).unwrap_or(0);
+      Self::Value(elem.to_bind_value(idx))
+    } else {
+      Self::GeneratedKey(tsa.source_statement(), tsa.source_row())
+    }
+  }
+
+  #[inline]
+  pub(crate) fn write<B, D>(
+    &self,
+    buffer: &mut B,
+    dialect: D,
+    mode: &mut SqlWriterMode,
+  ) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+    D: SqlDialect,
+  {
+    match *self {
+      Self::GeneratedKey(statement, row) => {
+        write_generated_key(buffer, dialect, mode, statement, row)
+      }
+      Self::Value(ref elem) => write_sql_value(buffer, dialect, mode, elem),
+    }
+  }
+}
@@ -0,0 +1,161 @@
+use crate::{BindValue, ColumnType};
+use core::{iter, ops::Range};
+
+/// Ordered values referenced by the placeholders of a buffer written with
+/// [crate::SqlWriterMode::Parameterized].
+///
+/// A buffer can contain several statements and each statement has its own arguments as well as its
+/// own placeholder numbering.
+#[derive(Debug, Default, PartialEq)]
+pub struct BindArguments {
+  // (value index, statement index, row index) of values that are only known after the referenced
+  // statement returns its generated primary keys
+  generated_keys: Vec<(usize, usize, usize)>,
+  statements: Vec<Statement>,
+  values: Vec<BindValue>,
+}
+
+impl BindArguments {
+  /// Creates an instance without values
+  #[inline]
+  pub const fn new() -> Self {
+    Self { generated_keys: Vec::new(), statements: Vec::new(), values: Vec::new() }
+  }
+
+  /// Removes all values and statement boundaries
+  #[inline]
+  pub fn clear(&mut self) {
+    self.generated_keys.clear();
+    self.statements.clear();
+    self.values.clear();
+  }
+
+  /// Type of the generated primary keys returned by the statement at index `statement`, if such
+  /// statement ends with `RETURNING`. See [Self::values_with_generated_keys].
+  #[inline]
+  pub fn returned_key(&self, statement: usize) -> Option<ColumnType> {
+    self.statements.get(statement).and_then(|elem| elem.returned_key)
+  }
+
+  /// Pairs every statement of `buffer` with its own arguments. A trailing statement without
+  /// terminator, like a SELECT, is also yielded.
+  #[inline]
+  pub fn statements<'args, 'buffer>(
+    &'args self,
+    buffer: &'buffer str,
+  ) -> impl Iterator<Item = (&'buffer str, &'args [BindValue])> {
+    let mut bounds = self
+      .statements
+      .iter()
+      .map(|elem| (elem.buffer_end, elem.values_end))
+      .chain(iter::once((buffer.len(), self.values.len())));
+    let mut buffer_start = 0;
+    let mut values_start = 0;
+    iter::from_fn(move || loop {
+      let (buffer_end, values_end) = bounds.next()?;
+      let statement = buffer.get(buffer_start..buffer_end)?;
+      let values = self.values.get(values_start..values_end)?;
+      buffer_start = buffer_end;
+      values_start = values_end;
+      if !statement.trim().is_empty() {
+        return Some((statement, values));
+      }
+    })
+  }
+
+  /// Names of the tables modified by each terminated statement, in the same order of
+  /// [Self::statements].
+  #[inline]
+  pub fn tables(&self) -> impl Iterator<Item = &'static str> + '_ {
+    self.statements.iter().map(|elem| elem.table)
+  }
+
+  /// All values of all statements
+  #[inline]
+  pub fn values(&self) -> &[BindValue] {
+    &self.values
+  }
+
+  /// Arguments of the statement at index `statement` where values that reference generated
+  /// primary keys are replaced by the elements of `keys`, which are indexed by statement and then
+  /// by returned row.
+  ///
+  /// Referenced rows that didn't return a key, e.g., an upsert that did nothing, produce
+  /// [crate::Error::MissingGeneratedKey].
+  #[inline]
+  pub fn values_with_generated_keys(
+    &self,
+    statement: usize,
+    keys: &[Vec<BindValue>],
+  ) -> crate::Result<Vec<BindValue>> {
+    let range = self.values_range(statement);
+    let mut values = self.values.get(range.clone()).unwrap_or_default().to_vec();
+    for &(value_idx, key_statement, key_row) in &self.generated_keys {
+      let Some(value) = value_idx.checked_sub(range.start).and_then(|idx| values.get_mut(idx))
+      else {
+        continue;
+      };
+      if let Some(key) = keys.get(key_statement).and_then(|elem| elem.get(key_row)) {
+        *value = key.clone();
+      } else {
+        let table = self.statements.get(key_statement).map_or("", |elem| elem.table);
+        return Err(crate::Error::MissingGeneratedKey(table));
+      }
+    }
+    Ok(values)
+  }
+
+  #[inline]
+  pub(crate) fn finish_statement(
+    &mut self,
+    buffer_len: usize,
+    returned_key: Option<ColumnType>,
+    table: &'static str,
+  ) {
+    self.statements.push(Statement {
+      buffer_end: buffer_len,
+      returned_key,
+      table,
+      values_end: self.values.len(),
+    });
+  }
+
+  /// Index of the statement that is currently being written
+  #[inline]
+  pub(crate) fn next_statement(&self) -> usize {
+    self.statements.len()
+  }
+
+  /// Returns the 1-based placeholder number of `value` within the current statement
+  #[inline]
+  pub(crate) fn push(&mut self, value: BindValue) -> usize {
+    self.values.push(value);
+    let statement_start = self.statements.last().map_or(0, |elem| elem.values_end);
+    self.values.len().wrapping_sub(statement_start)
+  }
+
+  /// Pushes a value that will be replaced by the primary key generated by the row at index `row`
+  /// of the statement at index `statement`.
+  #[inline]
+  pub(crate) fn push_generated_key(&mut self, statement: usize, row: usize) -> usize {
+    self.generated_keys.push((self.values.len(), statement, row));
+    self.push(BindValue::Null)
+  }
+
+  fn values_range(&self, statement: usize) -> Range<usize> {
+    let start = statement
+      .checked_sub(1)
+      .and_then(|idx| self.statements.get(idx))
+      .map_or(0, |elem| elem.values_end);
+    let end = self.statements.get(statement).map_or(self.values.len(), |elem| elem.values_end);
+    start..end
+  }
+}
+
+#[derive(Debug, PartialEq)]
+struct Statement {
+  buffer_end: usize,
+  returned_key: Option<ColumnType>,
+  table: &'static str,
+  values_end: usize,
+}
@@ -0,0 +1,26 @@
+/// Owned representation of a [crate::SqlValue] that is sent to the database separately from the
+/// statement text.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BindValue {
+  /// Boolean
+  Bool(bool),
+  /// Raw bytes
+  Bytes(Vec<u8>),
+  /// Decimal number
+  #[cfg(feature = "rust_decimal")]
+  Decimal(rust_decimal::Decimal),
+  /// 32-bit signed integer
+  I32(i32),
+  /// 64-bit signed integer
+  I64(i64),
+  /// Absence of a value. Always written inline by [crate::write_sql_value] because databases
+  /// like Postgres infer the type of bound NULL values from their encoding.
+  Null,
+  /// Text
+  String(String),
+  /// 64-bit unsigned integer
+  U64(u64),
+  /// Universally unique identifier
+  #[cfg(feature = "uuid")]
+  Uuid(uuid::Uuid),
+}
@@ -0,0 +1,114 @@
+use crate::{Filter, OrderBy, SelectOrderBy, SqlValue, Suffix};
+use core::marker::PhantomData;
+
+/// A column of a specific [crate::Table] instance that resolves to the same suffixed alias used
+/// by SELECT commands. Created by [crate::Table::column] or [crate::Table::id_column].
+#[derive(Debug)]
+pub struct Column<T> {
+  field: &'static str,
+  phantom: PhantomData<fn() -> T>,
+  suffix: Suffix,
+  table: &'static str,
+}
+
+impl<T> Column<T>
+where
+  T: SqlValue,
+{
+  /// `ORDER BY column ASC`
+  #[inline]
+  pub fn asc(self) -> OrderBy {
+    OrderBy::new(self.erase(), SelectOrderBy::Ascending)
+  }
+
+  /// `ORDER BY column DESC`
+  #[inline]
+  pub fn desc(self) -> OrderBy {
+    OrderBy::new(self.erase(), SelectOrderBy::Descending)
+  }
+
+  /// `column = value`
+  #[inline]
+  pub fn eq(self, value: T) -> Filter {
+    Filter::compare(self.erase(), " = ", value.to_bind_value())
+  }
+
+  /// `column > value`
+  #[inline]
+  pub fn gt(self, value: T) -> Filter {
+    Filter::compare(self.erase(), " > ", value.to_bind_value())
+  }
+
+  /// `column IN (values)`. An empty iterator matches nothing.
+  #[inline]
+  pub fn is_in(self, values: impl IntoIterator<Item = T>) -> Filter {
+    Filter::is_in(self.erase(), values.into_iter().map(|elem| elem.to_bind_value()).collect())
+  }
+
+  /// `column IS NULL`
+  #[inline]
+  pub fn is_null(self) -> Filter {
+    Filter::is_null(self.erase())
+  }
+
+  /// `column LIKE pattern`
+  #[inline]
+  pub fn like(self, pattern: &str) -> Filter {
+    Filter::compare(self.erase(), " LIKE ", pattern.to_bind_value())
+  }
+
+  /// `column < value`
+  #[inline]
+  pub fn lt(self, value: T) -> Filter {
+    Filter::compare(self.erase(), " < ", value.to_bind_value())
+  }
+
+  /// `column <> value`
+  #[inline]
+  pub fn ne(self, value: T) -> Filter {
+    Filter::compare(self.erase(), " <> ", value.to_bind_value())
+  }
+}
+
+impl<T> Column<T> {
+  #[inline]
+  pub(crate) const fn new(table: &'static str, suffix: Suffix, field: &'static str) -> Self {
+    Self { field, phantom: PhantomData, suffix, table }
+  }
+
+  #[inline]
+  pub(crate) const fn field(&self) -> &'static str {
+    self.field
+  }
+
+  #[inline]
+  pub(crate) const fn suffix(&self) -> Suffix {
+    self.suffix
+  }
+
+  #[inline]
+  pub(crate) const fn table(&self) -> &'static str {
+    self.table
+  }
+
+  #[inline]
+  pub(crate) fn erase(self) -> Column<()> {
+    Column { field: self.field, phantom: PhantomData, suffix: self.suffix, table: self.table }
+  }
+}
+
+impl<T> Clone for Column<T> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T> Copy for Column<T> {}
+
+impl<T> PartialEq for Column<T> {
+  #[inline]
+  fn eq(&self, other: &Self) -> bool {
+    self.field == other.field && self.suffix == other.suffix && self.table == other.table
+  }
+}
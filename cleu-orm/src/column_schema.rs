@@ -0,0 +1,66 @@
+use crate::{ColumnType, SqlDialect};
+
+/// Column of a [crate::TableSchema]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColumnSchema {
+  column_type: ColumnType,
+  is_generated: bool,
+  is_nullable: bool,
+  name: &'static str,
+}
+
+impl ColumnSchema {
+  /// Creates a new instance from all parameters
+  #[inline]
+  pub const fn new(
+    column_type: ColumnType,
+    is_generated: bool,
+    is_nullable: bool,
+    name: &'static str,
+  ) -> Self {
+    Self { column_type, is_generated, is_nullable, name }
+  }
+
+  /// See [ColumnType].
+  #[inline]
+  pub const fn column_type(&self) -> ColumnType {
+    self.column_type
+  }
+
+  /// If values are generated by the database when omitted. See
+  /// [crate::SqlDialect::write_generated].
+  #[inline]
+  pub const fn is_generated(&self) -> bool {
+    self.is_generated
+  }
+
+  /// If NULL values are accepted
+  #[inline]
+  pub const fn is_nullable(&self) -> bool {
+    self.is_nullable
+  }
+
+  /// Column name
+  #[inline]
+  pub const fn name(&self) -> &'static str {
+    self.name
+  }
+  /// Writes the name, the type, the nullability and the generation used by `CREATE TABLE` and `ALTER TABLE`
+  /// statements
+  #[inline]
+  pub fn write<B, D>(&self, buffer: &mut B, dialect: D) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+    D: SqlDialect,
+  {
+    buffer.write_fmt(format_args!("{q}{}{q} ", self.name, q = D::IDENTIFIER_QUOTE))?;
+    dialect.write_column_type(buffer, self.column_type)?;
+    if !self.is_nullable {
+      buffer.push(" NOT NULL")?;
+    }
+    if self.is_generated {
+      dialect.write_generated(buffer, self.column_type)?;
+    }
+    Ok(())
+  }
+}
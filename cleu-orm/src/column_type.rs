@@ -0,0 +1,23 @@
+/// Vendor-independent type of a column, which is translated into SQL by
+/// [crate::SqlDialect::write_column_type]. See [crate::SqlType].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColumnType {
+  /// Boolean
+  Bool,
+  /// Raw bytes
+  Bytes,
+  /// Decimal number
+  #[cfg(feature = "rust_decimal")]
+  Decimal,
+  /// 32-bit signed integer
+  I32,
+  /// 64-bit signed integer
+  I64,
+  /// Text
+  String,
+  /// 64-bit unsigned integer
+  U64,
+  /// Universally unique identifier
+  #[cfg(feature = "uuid")]
+  Uuid,
+}
@@ -0,0 +1,47 @@
+use crate::{ColumnType, SqlType};
+
+/// Types of a group of columns, like the ones of a primary key or the ones of
+/// [crate::TableFields].
+///
+/// Single columns are represented by a [SqlType] while composite keys are represented by a tuple
+/// of [SqlType]s, in the same order of [crate::TableDefs::PRIMARY_KEY_NAMES].
+pub trait ColumnTypes {
+  /// Type of the column at index `idx` and if such column accepts NULL values. Out of bounds
+  /// indices return `None`.
+  fn column_type(idx: usize) -> Option<(ColumnType, bool)>;
+}
+
+impl<T> ColumnTypes for T
+where
+  T: SqlType + ?Sized,
+{
+  #[inline]
+  fn column_type(idx: usize) -> Option<(ColumnType, bool)> {
+    (idx == 0).then_some((T::COLUMN_TYPE, T::IS_NULLABLE))
+  }
+}
+
+macro_rules! tuple_impls {
+  ($( ($($idx:tt $T:ident)+) )+) => {
+    $(
+      impl<$($T),+> ColumnTypes for ($($T,)+)
+      where
+        $($T: SqlType,)+
+      {
+        #[inline]
+        fn column_type(idx: usize) -> Option<(ColumnType, bool)> {
+          match idx {
+            $($idx => Some(($T::COLUMN_TYPE, $T::IS_NULLABLE)),)+
+            _ => None,
+          }
+        }
+      }
+    )+
+  }
+}
+
+tuple_impls! {
+  (0 T0 1 T1)
+  (0 T0 1 T1 2 T2)
+  (0 T0 1 T1 2 T2 3 T3)
+}
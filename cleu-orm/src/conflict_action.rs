@@ -0,0 +1,8 @@
+/// What an INSERT does with rows that violate the conflict target of an [crate::OnConflict]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConflictAction {
+  /// Existing rows are kept as they are
+  DoNothing,
+  /// Non-key fields of existing rows are replaced by the inserted values
+  DoUpdate,
+}
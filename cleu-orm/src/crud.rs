@@ -1,14 +1,25 @@
 //! Operations for different types of databases
 
-#![cfg(any(feature = "sqlx-postgres", feature = "sqlx-runtime-tokio-native-tls"))]
+#![cfg(any(
+  feature = "sqlx-postgres",
+  feature = "sqlx-runtime-tokio-native-tls",
+  feature = "sqlx-sqlite"
+))]
 
+mod affected_rows;
+mod database;
 mod utils;
 
 use crate::{
-  FromRowsSuffix, InitialInsertValue, SelectLimit, SelectOrderBy, SqlWriter, Table, TableDefs,
+  AssociationsSchema, ColumnTypes, DecodePrimaryKey, EagerLoading, Filter, FromRowsSuffix,
+  InitialInsertValue, InsertOptions, Keyset, OnConflict, OrderBy, Schema, SelectLimit, SelectedRow,
+  SqlWriter, SqlWriterMode, Table, TableDefs,
 };
-use sqlx_core::{executor::Executor, postgres::PgPool};
-pub use utils::*;
+pub use affected_rows::*;
+pub use database::*;
+use futures_util::Stream;
+use sqlx_core::{acquire::Acquire, transaction::Transaction};
+use utils::*;
 
 pub(crate) type TdEntity<'entity, TD> = <TD as TableDefs<'entity>>::Entity;
 pub(crate) type TdError<'entity, TD> = <TD as TableDefs<'entity>>::Error;
@@ -17,71 +28,298 @@ impl<'entity, TD> Table<'entity, TD>
 where
   TD: TableDefs<'entity>,
 {
-  /// Creates a new table on the database
+  /// Creates a new table on the database. Entities without primary key values receive keys
+  /// generated by the database, which are available in [AffectedRows::generated_keys].
   #[inline]
-  pub async fn create<B>(
+  pub async fn create<'exec, A, B, DB>(
     &mut self,
     buffer: &mut B,
-    pool: &PgPool,
+    executor: A,
     table: &'entity TD::Entity,
-  ) -> Result<(), TdError<'entity, TD>>
+  ) -> Result<AffectedRows, TdError<'entity, TD>>
   where
+    A: Acquire<'exec, Database = DB> + Send,
     B: cl_traits::String,
-    TD::Entity: FromRowsSuffix<B, Error = TD::Error>,
+    DB: Database,
+    TD::Associations: SqlWriter<B, Error = TD::Error>,
+  {
+    self.create_with_options(buffer, executor, InsertOptions::new(), table).await
+  }
+
+  /// Creates the database tables of this table and of all its associations, which is useful to
+  /// bootstrap test databases. See [Schema].
+  #[inline]
+  pub async fn create_schema<'exec, A, B, DB>(
+    &self,
+    buffer: &mut B,
+    executor: A,
+  ) -> Result<AffectedRows, TdError<'entity, TD>>
+  where
+    A: Acquire<'exec, Database = DB> + Send,
+    B: cl_traits::String,
+    DB: Database,
+    TD::Associations: AssociationsSchema,
+    TD::Fields: ColumnTypes,
+    TD::PrimaryKeyValue: ColumnTypes,
+  {
+    let mut schema = Schema::new();
+    schema.push_table(self)?;
+    let mut mode = SqlWriterMode::parameterized();
+    schema.write_create_tables(buffer, DB::DIALECT, &mut mode)?;
+    Ok(execute(buffer, executor, &mode).await?)
+  }
+
+  /// Auxiliary method that creates a new table on the database using custom [InsertOptions].
+  #[inline]
+  pub async fn create_with_options<'exec, A, B, DB>(
+    &mut self,
+    buffer: &mut B,
+    executor: A,
+    options: InsertOptions<'_>,
+    table: &'entity TD::Entity,
+  ) -> Result<AffectedRows, TdError<'entity, TD>>
+  where
+    A: Acquire<'exec, Database = DB> + Send,
+    B: cl_traits::String,
+    DB: Database,
+    TD::Associations: SqlWriter<B, Error = TD::Error>,
+  {
+    self.update_all_table_fields(table);
+    let mut mode = SqlWriterMode::parameterized();
+    self.write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      buffer,
+      DB::DIALECT,
+      &mut mode,
+      options,
+      &mut None,
+    )?;
+    Ok(execute(buffer, executor, &mode).await?)
+  }
+
+  /// Deletes an entity and all its associations
+  #[inline]
+  pub async fn delete<'exec, A, B, DB>(
+    &mut self,
+    buffer: &mut B,
+    executor: A,
+    table: &'entity TD::Entity,
+  ) -> Result<AffectedRows, TdError<'entity, TD>>
+  where
+    A: Acquire<'exec, Database = DB> + Send,
+    B: cl_traits::String,
+    DB: Database,
     TD::Associations: SqlWriter<B, Error = TD::Error>,
   {
     self.update_all_table_fields(table);
-    self.write_insert::<InitialInsertValue>(&mut <_>::default(), buffer, &mut None)?;
-    let _ = pool.execute(buffer.as_ref()).await.map_err(Into::into)?;
-    Ok(())
+    let mut mode = SqlWriterMode::parameterized();
+    self.write_delete(&mut <_>::default(), buffer, DB::DIALECT, &mut mode)?;
+    Ok(execute(buffer, executor, &mode).await?)
+  }
+
+  /// Deletes a single stored entity based on its id. Associations are not touched.
+  #[inline]
+  pub async fn delete_by_id<'exec, A, B, DB>(
+    &self,
+    buffer: &mut B,
+    executor: A,
+    id: &TD::PrimaryKeyValue,
+  ) -> Result<AffectedRows, TdError<'entity, TD>>
+  where
+    A: Acquire<'exec, Database = DB> + Send,
+    B: cl_traits::String,
+    DB: Database,
+    TD::Associations: SqlWriter<B, Error = TD::Error>,
+  {
+    let mut table = Table::<TD>::new(self.suffix());
+    *table.id_field_mut().value_mut() = Some(*id);
+    let mut mode = SqlWriterMode::parameterized();
+    table.write_delete(&mut <_>::default(), buffer, DB::DIALECT, &mut mode)?;
+    Ok(execute(buffer, executor, &mode).await?)
   }
 
   /// Gets all stored entities.
   #[inline]
-  pub async fn read_all<B>(
+  pub async fn read_all<'exec, A, B, DB>(
     &self,
     buffer: &mut B,
-    pool: &PgPool,
+    executor: A,
   ) -> Result<Vec<TdEntity<'entity, TD>>, TdError<'entity, TD>>
   where
+    A: Acquire<'exec, Database = DB> + Send,
     B: cl_traits::String,
-    TD::Entity: FromRowsSuffix<B, Error = TD::Error>,
+    DB: Database,
+    TD::Entity: FromRowsSuffix<B, SelectedRow<DB::Row>, Error = TD::Error>,
     TD::Associations: SqlWriter<B, Error = TD::Error>,
+    TD::PrimaryKeyValue: DecodePrimaryKey<SelectedRow<DB::Row>>,
   {
-    read_all(buffer, pool, self).await
+    read_all(buffer, executor, self).await
   }
 
-  /// Auxiliary method that gets all stored entities filtered by a field.
+  /// Auxiliary method that gets all stored entities that match an optional [Filter], only
+  /// loading the associations included by [EagerLoading].
   #[inline]
-  pub async fn read_all_with_params<B>(
+  pub async fn read_all_with_params<'exec, A, B, DB>(
     &self,
     buffer: &mut B,
-    pool: &PgPool,
-    order_by: SelectOrderBy,
+    executor: A,
+    eager_loading: EagerLoading<'_>,
+    orders_by: &[OrderBy],
     limit: SelectLimit,
-    where_str: &str,
+    filter: Option<&Filter>,
   ) -> Result<Vec<TdEntity<'entity, TD>>, TdError<'entity, TD>>
   where
+    A: Acquire<'exec, Database = DB> + Send,
     B: cl_traits::String,
-    TD::Entity: FromRowsSuffix<B, Error = TD::Error>,
+    DB: Database,
+    TD::Entity: FromRowsSuffix<B, SelectedRow<DB::Row>, Error = TD::Error>,
     TD::Associations: SqlWriter<B, Error = TD::Error>,
+    TD::PrimaryKeyValue: DecodePrimaryKey<SelectedRow<DB::Row>>,
   {
-    read_all_with_params(buffer, pool, self, order_by, limit, where_str).await
+    read_all_with_params(buffer, executor, self, eager_loading, orders_by, limit, filter).await
   }
 
   /// Gets a single stored entity based on its id.
   #[inline]
-  pub async fn read_by_id<B>(
+  pub async fn read_by_id<'exec, A, B, DB>(
     &self,
     buffer: &mut B,
+    executor: A,
     id: &TD::PrimaryKeyValue,
-    pool: &PgPool,
   ) -> Result<TdEntity<'entity, TD>, TdError<'entity, TD>>
   where
+    A: Acquire<'exec, Database = DB> + Send,
+    B: cl_traits::String,
+    DB: Database,
+    TD::Entity: FromRowsSuffix<B, SelectedRow<DB::Row>, Error = TD::Error>,
+    TD::Associations: SqlWriter<B, Error = TD::Error>,
+    TD::PrimaryKeyValue: DecodePrimaryKey<SelectedRow<DB::Row>>,
+  {
+    read_by_id(buffer, executor, id, self).await
+  }
+
+  /// Gets at most [Keyset::count] stored entities whose primary keys come after [Keyset::last],
+  /// sorted by their primary keys, that also match an optional [Filter]. Only the associations
+  /// included by [EagerLoading] are loaded.
+  #[inline]
+  pub async fn read_keyset<'exec, A, B, DB>(
+    &self,
+    buffer: &mut B,
+    executor: A,
+    eager_loading: EagerLoading<'_>,
+    keyset: Keyset<TD::PrimaryKeyValue>,
+    filter: Option<&Filter>,
+  ) -> Result<Vec<TdEntity<'entity, TD>>, TdError<'entity, TD>>
+  where
+    A: Acquire<'exec, Database = DB> + Send,
+    B: cl_traits::String,
+    DB: Database,
+    TD::Entity: FromRowsSuffix<B, SelectedRow<DB::Row>, Error = TD::Error>,
+    TD::Associations: SqlWriter<B, Error = TD::Error>,
+    TD::PrimaryKeyValue: DecodePrimaryKey<SelectedRow<DB::Row>>,
+  {
+    let keyset_filter = keyset.last().and_then(|elem| self.keyset_filter(*elem));
+    let local_filter = match (keyset_filter, filter) {
+      (Some(lhs), Some(rhs)) => Some(lhs.and(rhs.clone())),
+      (lhs, rhs) => lhs.or_else(|| rhs.cloned()),
+    };
+    let limit = SelectLimit::Count(keyset.count());
+    read_all_with_params(buffer, executor, self, eager_loading, &[], limit, local_filter.as_ref())
+      .await
+  }
+
+  /// Streams all stored entities that match an optional [Filter], only loading the associations
+  /// included by [EagerLoading].
+  ///
+  /// Rows are consumed as they arrive and each entity is yielded as soon as the next root entity
+  /// starts, so only the rows of one root entity are kept in memory.
+  #[inline]
+  pub fn read_stream<'args, 'exec, A, B, DB>(
+    &'args self,
+    buffer: &'args mut B,
+    executor: A,
+    eager_loading: EagerLoading<'args>,
+    orders_by: &'args [OrderBy],
+    limit: SelectLimit,
+    filter: Option<&'args Filter>,
+  ) -> impl Stream<Item = Result<TdEntity<'entity, TD>, TdError<'entity, TD>>> + 'args
+  where
+    'exec: 'args,
+    A: Acquire<'exec, Database = DB> + Send + 'args,
+    B: cl_traits::String,
+    DB: Database,
+    TD::Entity: FromRowsSuffix<B, SelectedRow<DB::Row>, Error = TD::Error>,
+    TD::Associations: SqlWriter<B, Error = TD::Error>,
+    TD::PrimaryKeyValue: DecodePrimaryKey<SelectedRow<DB::Row>>,
+  {
+    read_stream(buffer, executor, self, eager_loading, orders_by, limit, filter)
+  }
+
+  /// Runs `cb` inside a new transaction, which is committed if `cb` succeeds or rolled back
+  /// otherwise.
+  ///
+  /// `cb` receives this table and the transaction, which can be used as the executor of other
+  /// CRUD operations.
+  #[inline]
+  pub async fn transaction<'exec, A, DB, F, R>(
+    &mut self,
+    executor: A,
+    cb: F,
+  ) -> Result<R, TdError<'entity, TD>>
+  where
+    A: Acquire<'exec, Database = DB>,
+    DB: Database,
+    F: AsyncFnOnce(&mut Self, &mut Transaction<'exec, DB>) -> Result<R, TD::Error>,
+  {
+    let mut transaction = executor.begin().await.map_err(crate::Error::from)?;
+    match cb(self, &mut transaction).await {
+      Ok(elem) => {
+        transaction.commit().await.map_err(crate::Error::from)?;
+        Ok(elem)
+      }
+      Err(err) => {
+        transaction.rollback().await.map_err(crate::Error::from)?;
+        Err(err)
+      }
+    }
+  }
+
+  /// Creates or, depending on [OnConflict], updates an entity and all its associations
+  #[inline]
+  pub async fn upsert<'exec, A, B, DB>(
+    &mut self,
+    buffer: &mut B,
+    executor: A,
+    on_conflict: OnConflict<'_>,
+    table: &'entity TD::Entity,
+  ) -> Result<AffectedRows, TdError<'entity, TD>>
+  where
+    A: Acquire<'exec, Database = DB> + Send,
     B: cl_traits::String,
-    TD::Entity: FromRowsSuffix<B, Error = TD::Error>,
+    DB: Database,
     TD::Associations: SqlWriter<B, Error = TD::Error>,
   {
-    read_by_id(buffer, id, pool, self).await
+    let options = InsertOptions::new().with_on_conflict(on_conflict);
+    self.create_with_options(buffer, executor, options, table).await
+  }
+
+  /// Updates an entity and all its associations
+  #[inline]
+  pub async fn update<'exec, A, B, DB>(
+    &mut self,
+    buffer: &mut B,
+    executor: A,
+    table: &'entity TD::Entity,
+  ) -> Result<AffectedRows, TdError<'entity, TD>>
+  where
+    A: Acquire<'exec, Database = DB> + Send,
+    B: cl_traits::String,
+    DB: Database,
+    TD::Associations: SqlWriter<B, Error = TD::Error>,
+  {
+    self.update_all_table_fields(table);
+    let mut mode = SqlWriterMode::parameterized();
+    self.write_update(&mut <_>::default(), buffer, DB::DIALECT, &mut mode)?;
+    Ok(execute(buffer, executor, &mode).await?)
   }
 }
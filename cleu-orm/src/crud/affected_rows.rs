@@ -0,0 +1,54 @@
+use crate::{BindValue, SqlWriterMode};
+
+/// Number of rows affected by an operation in each table of an entity graph as well as the
+/// primary keys generated by the database
+#[derive(Debug, Default, PartialEq)]
+pub struct AffectedRows {
+  generated_keys: Vec<(&'static str, BindValue)>,
+  tables: Vec<(&'static str, u64)>,
+}
+
+impl AffectedRows {
+  /// Table names and primary keys generated for inserted rows without ids, in the order they were
+  /// returned by each statement
+  #[inline]
+  pub fn generated_keys(&self) -> &[(&'static str, BindValue)] {
+    &self.generated_keys
+  }
+
+  /// Rows affected in the table named `name`
+  #[inline]
+  pub fn table(&self, name: &str) -> u64 {
+    self.tables.iter().find(|elem| elem.0 == name).map_or(0, |elem| elem.1)
+  }
+
+  /// Table names and their affected rows, in the order they were first modified
+  #[inline]
+  pub fn tables(&self) -> &[(&'static str, u64)] {
+    &self.tables
+  }
+
+  /// Rows affected in all tables
+  #[inline]
+  pub fn total(&self) -> u64 {
+    self.tables.iter().map(|elem| elem.1).sum()
+  }
+
+  /// Pairs the statements written with `mode` with the counts returned by
+  /// [crate::crud::Database::execute_parameterized].
+  #[inline]
+  pub(crate) fn new(mode: &SqlWriterMode, rslts: &[(u64, Vec<BindValue>)]) -> Self {
+    let mut generated_keys = Vec::new();
+    let mut tables: Vec<(&'static str, u64)> = Vec::new();
+    let names = mode.bind_arguments().into_iter().flat_map(|elem| elem.tables());
+    for (name, &(count, ref keys)) in names.zip(rslts) {
+      generated_keys.extend(keys.iter().map(|elem| (name, elem.clone())));
+      if let Some(elem) = tables.iter_mut().find(|elem| elem.0 == name) {
+        elem.1 = elem.1.wrapping_add(count);
+      } else {
+        tables.push((name, count));
+      }
+    }
+    Self { generated_keys, tables }
+  }
+}
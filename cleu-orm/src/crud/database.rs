@@ -0,0 +1,160 @@
+#[cfg(feature = "sqlx-postgres")]
+mod postgres;
+#[cfg(feature = "sqlx-sqlite")]
+mod sqlite;
+
+use crate::{BindValue, ColumnType, DbRow, SqlDialect, SqlWriterMode};
+use core::future::Future;
+use futures_util::{future::Either, stream, Stream, TryStreamExt};
+use sqlx_core::{
+  acquire::Acquire,
+  arguments::IntoArguments,
+  database::HasArguments,
+  executor::Executor,
+  query::{query, Query},
+};
+
+type SqlxQuery<'query, DB> = Query<'query, DB, <DB as HasArguments<'query>>::Arguments>;
+
+/// A `sqlx` database that can back the CRUD operations of [crate::Table].
+pub trait Database: sqlx_core::database::Database<Row: DbRow> {
+  /// See [SqlDialect].
+  type Dialect: SqlDialect;
+
+  /// Instance used to write all commands sent to this database
+  const DIALECT: Self::Dialect;
+
+  /// Executes all statements written with [SqlWriterMode::Parameterized] inside a single
+  /// transaction. Statements written with [SqlWriterMode::Inline] are sent as they are.
+  ///
+  /// Statements that return generated primary keys are executed before the statements that use
+  /// them. Returns the number of rows affected and the generated keys, if any, of each executed
+  /// statement.
+  ///
+  /// `executor` can be a pool, a connection or an ongoing transaction. In the latter case, a
+  /// nested transaction (savepoint) is used.
+  fn execute_parameterized<'args, 'exec, A>(
+    buffer: &'args str,
+    executor: A,
+    mode: &'args SqlWriterMode,
+  ) -> impl Future<Output = crate::Result<Vec<(u64, Vec<BindValue>)>>> + Send + 'args
+  where
+    'exec: 'args,
+    A: Acquire<'exec, Database = Self> + Send + 'args;
+
+  /// Fetches all rows returned by `statement` after binding `values` to its placeholders.
+  fn fetch_all<'args, 'exec, A>(
+    statement: &'args str,
+    executor: A,
+    values: &'args [BindValue],
+  ) -> impl Future<Output = crate::Result<Vec<Self::Row>>> + Send + 'args
+  where
+    'exec: 'args,
+    A: Acquire<'exec, Database = Self> + Send + 'args;
+
+  /// Streams all rows returned by `statement` after binding `values` to its placeholders. Rows
+  /// are yielded as they arrive from `connection`.
+  fn fetch_stream<'args>(
+    statement: &'args str,
+    connection: &'args mut Self::Connection,
+    values: &'args [BindValue],
+  ) -> impl Stream<Item = crate::Result<Self::Row>> + Send + 'args;
+}
+
+/// Parts of the CRUD operations that differ between `sqlx` backends. Everything else is shared by
+/// the generic functions of this module.
+trait Backend: sqlx_core::database::Database {
+  /// Binds `value` to the next placeholder of `query`.
+  fn bind_value<'query>(
+    query: SqlxQuery<'query, Self>,
+    value: &'query BindValue,
+  ) -> crate::Result<SqlxQuery<'query, Self>>;
+
+  /// Decodes the primary key of type `column_type` returned by the first column of `row`.
+  fn decode_generated_key(row: &Self::Row, column_type: ColumnType) -> crate::Result<BindValue>;
+
+  /// Number of rows affected by an executed statement
+  fn rows_affected(rslt: &Self::QueryResult) -> u64;
+}
+
+async fn execute_parameterized<'exec, A, DB>(
+  buffer: &str,
+  executor: A,
+  mode: &SqlWriterMode,
+) -> crate::Result<Vec<(u64, Vec<BindValue>)>>
+where
+  A: Acquire<'exec, Database = DB>,
+  DB: Backend,
+  for<'conn> &'conn mut DB::Connection: Executor<'conn, Database = DB>,
+  for<'query> <DB as HasArguments<'query>>::Arguments: IntoArguments<'query, DB>,
+{
+  let mut rslts = Vec::new();
+  let mut transaction = executor.begin().await?;
+  if let Some(args) = mode.bind_arguments() {
+    let mut keys = Vec::new();
+    for (idx, (statement, _)) in args.statements(buffer).enumerate() {
+      let values = args.values_with_generated_keys(idx, &keys)?;
+      let local_query = bind_values(query(statement), &values)?;
+      let rslt = if let Some(column_type) = args.returned_key(idx) {
+        let rows = local_query.fetch_all(&mut *transaction).await?;
+        let keys = rows
+          .iter()
+          .map(|elem| DB::decode_generated_key(elem, column_type))
+          .collect::<crate::Result<Vec<_>>>()?;
+        (u64::try_from(keys.len()).unwrap_or(u64::MAX), keys)
+      } else {
+        (DB::rows_affected(&local_query.execute(&mut *transaction).await?), Vec::new())
+      };
+      keys.push(rslt.1.clone());
+      rslts.push(rslt);
+    }
+  } else {
+    rslts.push((DB::rows_affected(&query(buffer).execute(&mut *transaction).await?), Vec::new()));
+  }
+  transaction.commit().await?;
+  Ok(rslts)
+}
+
+async fn fetch_all<'exec, A, DB>(
+  statement: &str,
+  executor: A,
+  values: &[BindValue],
+) -> crate::Result<Vec<DB::Row>>
+where
+  A: Acquire<'exec, Database = DB>,
+  DB: Backend,
+  for<'conn> &'conn mut DB::Connection: Executor<'conn, Database = DB>,
+  for<'query> <DB as HasArguments<'query>>::Arguments: IntoArguments<'query, DB>,
+{
+  let mut connection = executor.acquire().await?;
+  Ok(bind_values(query(statement), values)?.fetch_all(&mut *connection).await?)
+}
+
+fn fetch_stream<'args, DB>(
+  statement: &'args str,
+  connection: &'args mut DB::Connection,
+  values: &'args [BindValue],
+) -> impl Stream<Item = crate::Result<DB::Row>> + Send + 'args
+where
+  DB: Backend,
+  for<'conn> &'conn mut DB::Connection: Executor<'conn, Database = DB>,
+  for<'query> <DB as HasArguments<'query>>::Arguments: IntoArguments<'query, DB>,
+{
+  match bind_values(query(statement), values) {
+    Ok(elem) => Either::Left(elem.fetch(connection).map_err(crate::Error::from)),
+    Err(err) => Either::Right(stream::once(async { Err(err) })),
+  }
+}
+
+fn bind_values<'query, DB>(
+  mut query: SqlxQuery<'query, DB>,
+  values: &'query [BindValue],
+) -> crate::Result<SqlxQuery<'query, DB>>
+where
+  DB: Backend,
+{
+  for value in values {
+    query = DB::bind_value(query, value)?;
+  }
+  Ok(query)
+}
@@ -0,0 +1,112 @@
+use crate::{
+  crud::{
+    database::{Backend, SqlxQuery},
+    Database,
+  },
+  BindValue, ColumnType, DbRow, DecodeColumn, PostgresDialect, SqlWriterMode,
+};
+use futures_util::Stream;
+use sqlx_core::{
+  acquire::Acquire,
+  decode::Decode,
+  postgres::{PgRow, Postgres},
+  row::Row,
+  types::Type,
+};
+
+impl DbRow for PgRow {}
+
+impl<T> DecodeColumn<T> for PgRow
+where
+  T: for<'row> Decode<'row, Postgres> + Type<Postgres>,
+{
+  #[inline]
+  fn decode_column(&self, column: &str) -> crate::Result<Option<T>> {
+    Ok(self.try_get(column)?)
+  }
+}
+
+impl Database for Postgres {
+  type Dialect = PostgresDialect;
+
+  const DIALECT: Self::Dialect = PostgresDialect;
+
+  #[inline]
+  async fn execute_parameterized<'args, 'exec, A>(
+    buffer: &'args str,
+    executor: A,
+    mode: &'args SqlWriterMode,
+  ) -> crate::Result<Vec<(u64, Vec<BindValue>)>>
+  where
+    'exec: 'args,
+    A: Acquire<'exec, Database = Self> + Send + 'args,
+  {
+    super::execute_parameterized(buffer, executor, mode).await
+  }
+
+  #[inline]
+  async fn fetch_all<'args, 'exec, A>(
+    statement: &'args str,
+    executor: A,
+    values: &'args [BindValue],
+  ) -> crate::Result<Vec<Self::Row>>
+  where
+    'exec: 'args,
+    A: Acquire<'exec, Database = Self> + Send + 'args,
+  {
+    super::fetch_all(statement, executor, values).await
+  }
+
+  #[inline]
+  fn fetch_stream<'args>(
+    statement: &'args str,
+    connection: &'args mut Self::Connection,
+    values: &'args [BindValue],
+  ) -> impl Stream<Item = crate::Result<Self::Row>> + Send + 'args {
+    super::fetch_stream::<Self>(statement, connection, values)
+  }
+}
+
+impl Backend for Postgres {
+  #[inline]
+  fn bind_value<'query>(
+    query: SqlxQuery<'query, Self>,
+    value: &'query BindValue,
+  ) -> crate::Result<SqlxQuery<'query, Self>> {
+    Ok(match *value {
+      BindValue::Bool(elem) => query.bind(elem),
+      BindValue::Bytes(ref elem) => query.bind(elem.as_slice()),
+      #[cfg(feature = "rust_decimal")]
+      BindValue::Decimal(elem) => query.bind(elem),
+      BindValue::I32(elem) => query.bind(elem),
+      BindValue::I64(elem) => query.bind(elem),
+      BindValue::Null => return Err(crate::Error::UnsupportedBindValue),
+      BindValue::String(ref elem) => query.bind(elem.as_str()),
+      BindValue::U64(elem) => {
+        query.bind(i64::try_from(elem).map_err(|_err| crate::Error::UnsupportedBindValue)?)
+      }
+      #[cfg(feature = "uuid")]
+      BindValue::Uuid(elem) => query.bind(elem),
+    })
+  }
+
+  #[inline]
+  fn decode_generated_key(row: &Self::Row, column_type: ColumnType) -> crate::Result<BindValue> {
+    Ok(match column_type {
+      #[cfg(feature = "rust_decimal")]
+      ColumnType::Decimal => BindValue::Decimal(row.try_get(0)?),
+      ColumnType::I32 => BindValue::I32(row.try_get(0)?),
+      ColumnType::I64 => BindValue::I64(row.try_get(0)?),
+      ColumnType::String => BindValue::String(row.try_get(0)?),
+      #[cfg(feature = "uuid")]
+      ColumnType::Uuid => BindValue::Uuid(row.try_get(0)?),
+      // `U64` columns are `NUMERIC(20,0)`, which can't be identities
+      _ => return Err(crate::Error::UnsupportedGeneratedKey(column_type)),
+    })
+  }
+
+  #[inline]
+  fn rows_affected(rslt: &Self::QueryResult) -> u64 {
+    rslt.rows_affected()
+  }
+}
@@ -1,166 +1,243 @@
 use crate::{
-  buffer_try_push_str, buffer_write_fmt,
-  crud::{TdEntity, TdError},
-  write_column_alias, write_select_field, FromRowsSuffix, SelectLimit, SelectOrderBy, SqlWriter,
-  Suffix, Table, TableDefs,
-};
-use sqlx_core::{
-  postgres::{PgPool, PgRow},
-  query::query,
-  row::Row,
+  crud::{AffectedRows, Database, TdEntity, TdError},
+  decode_table_key, seek_related_entities, DbRow, DecodePrimaryKey, EagerLoading, Filter,
+  FromRowsSuffix, JoinKind, OrderBy, SelectLimit, SelectedRow, SqlWriter, SqlWriterMode, Suffix,
+  Table, TableDefs,
 };
+use core::pin::pin;
+use futures_channel::mpsc;
+use futures_util::{future, stream, SinkExt, Stream, StreamExt, TryStreamExt};
+use sqlx_core::acquire::Acquire;
+use std::sync::Arc;
 
-/// Seeks all rows that equals `TD`'s primary key and suffix. Can be `TD` itself or any other
-/// associated/related entity.
 #[inline]
-pub fn seek_related_entities<'entity, B, F, R, TD>(
+pub(crate) async fn execute<'exec, A, B, DB>(
   buffer: &mut B,
-  rows: &[PgRow],
-  suffix: Suffix,
-  suffix_related: Suffix,
-  mut cb: F,
-) -> Result<usize, TD::Error>
+  executor: A,
+  mode: &SqlWriterMode,
+) -> crate::Result<AffectedRows>
 where
+  A: Acquire<'exec, Database = DB> + Send,
   B: cl_traits::String,
-  F: FnMut(R) -> Result<(), TD::Error>,
-  R: FromRowsSuffix<B, Error = TD::Error>,
-  TD: TableDefs<'entity>,
+  DB: Database,
 {
-  if rows.is_empty() {
-    return Ok(0);
-  }
-
-  let first_row = if let Some(elem) = rows.first() {
-    elem
-  } else {
-    return Ok(0);
-  };
-
-  let first_rslt = R::from_rows_suffix(rows, buffer, suffix_related, first_row);
-  let (mut counter, mut previous) = if let Ok((skip, entity)) = first_rslt {
-    write_column_alias(buffer, TD::TABLE_NAME, suffix, TD::PRIMARY_KEY_NAME)?;
-    let previous = first_row.try_get(buffer.as_ref()).map_err(Into::into)?;
-    buffer.clear();
-    cb(entity)?;
-    (skip, previous)
-  } else {
-    buffer.clear();
-    return Ok(1);
-  };
-
-  loop {
-    if counter >= rows.len() {
-      break;
-    }
-
-    let row = if let Some(elem) = rows.get(counter) {
-      elem
-    } else {
-      break;
-    };
-
-    let curr_rows = rows.get(counter..).unwrap_or_default();
-    let (skip, entity) = R::from_rows_suffix(curr_rows, buffer, suffix_related, row)?;
-
-    write_column_alias(buffer, TD::TABLE_NAME, suffix, TD::PRIMARY_KEY_NAME)?;
-    let curr: i64 = row.try_get(buffer.as_ref()).map_err(Into::into)?;
-    buffer.clear();
-    if previous == curr {
-      cb(entity)?;
-      counter = counter.wrapping_add(skip);
-    } else {
-      break;
-    }
-    previous = curr;
-  }
-
-  Ok(counter)
+  let rslts = DB::execute_parameterized(buffer.as_ref(), executor, mode).await?;
+  buffer.clear();
+  Ok(AffectedRows::new(mode, &rslts))
 }
 
 #[inline]
-pub(crate) async fn read_all<'entity, R, B, TD>(
+pub(crate) async fn read_all<'entity, 'exec, A, R, B, DB, TD>(
   buffer: &mut B,
-  pool: &PgPool,
+  executor: A,
   table: &Table<'entity, TD>,
 ) -> Result<Vec<R>, TdError<'entity, TD>>
 where
+  A: Acquire<'exec, Database = DB> + Send,
   B: cl_traits::String,
-  R: FromRowsSuffix<B, Error = TD::Error>,
+  DB: Database,
+  R: FromRowsSuffix<B, SelectedRow<DB::Row>, Error = TD::Error>,
   TD: TableDefs<'entity>,
   TD::Associations: SqlWriter<B, Error = TD::Error>,
   TD::Error: From<crate::Error>,
+  TD::PrimaryKeyValue: DecodePrimaryKey<SelectedRow<DB::Row>>,
 {
-  table.write_select(buffer, SelectOrderBy::Ascending, SelectLimit::All, &mut |_| Ok(()))?;
-  let rows = query(buffer.as_ref()).fetch_all(pool).await.map_err(Into::into)?;
+  table.write_select(
+    buffer,
+    DB::DIALECT,
+    EagerLoading::all(),
+    &[],
+    SelectLimit::All,
+    &mut |_| Ok(()),
+  )?;
+  let rows = DB::fetch_all(buffer.as_ref(), executor, &[]).await?;
   buffer.clear();
+  let rows = selected_rows::<B, _, _>(EagerLoading::all(), rows, table);
   collect_entities_tables(buffer, &rows, table)
 }
 
 #[inline]
-pub(crate) async fn read_by_id<'entity, B, TD>(
+pub(crate) async fn read_by_id<'entity, 'exec, A, B, DB, TD>(
   buffer: &mut B,
+  executor: A,
   id: &TD::PrimaryKeyValue,
-  pool: &PgPool,
   table: &Table<'entity, TD>,
 ) -> Result<TdEntity<'entity, TD>, TdError<'entity, TD>>
 where
+  A: Acquire<'exec, Database = DB> + Send,
   B: cl_traits::String,
+  DB: Database,
   TD: TableDefs<'entity>,
   TD::Associations: SqlWriter<B, Error = TD::Error>,
-  TD::Entity: FromRowsSuffix<B, Error = TD::Error>,
+  TD::Entity: FromRowsSuffix<B, SelectedRow<DB::Row>, Error = TD::Error>,
   TD::Error: From<crate::Error>,
+  TD::PrimaryKeyValue: DecodePrimaryKey<SelectedRow<DB::Row>>,
 {
-  table.write_select(buffer, SelectOrderBy::Ascending, SelectLimit::All, &mut |b| {
-    write_select_field(
-      b,
-      TD::TABLE_NAME,
-      TD::TABLE_NAME_ALIAS,
-      table.suffix(),
-      table.id_field().name(),
-    )?;
-    buffer_write_fmt(b, format_args!(" = {id}"))
-  })?;
-  let rows = query(buffer.as_ref()).fetch_all(pool).await.map_err(Into::into)?;
+  let filter = table.id_filter(*id);
+  let mut mode = SqlWriterMode::parameterized();
+  table.write_select(
+    buffer,
+    DB::DIALECT,
+    EagerLoading::all(),
+    &[],
+    SelectLimit::All,
+    &mut |b| {
+      if let Some(elem) = &filter {
+        elem.write(b, DB::DIALECT, &mut mode)?;
+      }
+      Ok(())
+    },
+  )?;
+  let values = mode.bind_arguments().map(|elem| elem.values()).unwrap_or_default();
+  let rows = DB::fetch_all(buffer.as_ref(), executor, values).await?;
   buffer.clear();
+  let rows = selected_rows::<B, _, _>(EagerLoading::all(), rows, table);
   let first_row = rows.first().ok_or(crate::Error::NoDatabaseRowResult)?;
   Ok(TD::Entity::from_rows_suffix(&rows, buffer, table.suffix(), first_row)?.1)
 }
 
 #[inline]
-pub(crate) async fn read_all_with_params<'entity, R, B, TD>(
+pub(crate) async fn read_all_with_params<'entity, 'exec, A, R, B, DB, TD>(
   buffer: &mut B,
-  pool: &PgPool,
+  executor: A,
   table: &Table<'entity, TD>,
-  order_by: SelectOrderBy,
+  eager_loading: EagerLoading<'_>,
+  orders_by: &[OrderBy],
   select_limit: SelectLimit,
-  where_str: &str,
+  filter: Option<&Filter>,
 ) -> Result<Vec<R>, TdError<'entity, TD>>
 where
+  A: Acquire<'exec, Database = DB> + Send,
   B: cl_traits::String,
-  R: FromRowsSuffix<B, Error = TD::Error>,
+  DB: Database,
+  R: FromRowsSuffix<B, SelectedRow<DB::Row>, Error = TD::Error>,
   TD: TableDefs<'entity>,
   TD::Associations: SqlWriter<B, Error = TD::Error>,
   TD::Error: From<crate::Error>,
+  TD::PrimaryKeyValue: DecodePrimaryKey<SelectedRow<DB::Row>>,
 {
-  table.write_select(buffer, order_by, select_limit, &mut |b| buffer_try_push_str(b, where_str))?;
-  let rows = query(buffer.as_ref()).fetch_all(pool).await.map_err(Into::into)?;
+  let mut mode = SqlWriterMode::parameterized();
+  table.write_select(buffer, DB::DIALECT, eager_loading, orders_by, select_limit, &mut |b| {
+    if let Some(elem) = filter {
+      elem.write(b, DB::DIALECT, &mut mode)?;
+    }
+    Ok(())
+  })?;
+  let values = mode.bind_arguments().map(|elem| elem.values()).unwrap_or_default();
+  let rows = DB::fetch_all(buffer.as_ref(), executor, values).await?;
   buffer.clear();
+  let rows = selected_rows::<B, _, _>(eager_loading, rows, table);
   collect_entities_tables(buffer, &rows, table)
 }
 
+#[inline]
+pub(crate) fn read_stream<'args, 'entity, 'exec, A, B, DB, TD>(
+  buffer: &'args mut B,
+  executor: A,
+  table: &'args Table<'entity, TD>,
+  eager_loading: EagerLoading<'args>,
+  orders_by: &'args [OrderBy],
+  select_limit: SelectLimit,
+  filter: Option<&'args Filter>,
+) -> impl Stream<Item = Result<TdEntity<'entity, TD>, TdError<'entity, TD>>> + 'args
+where
+  'exec: 'args,
+  A: Acquire<'exec, Database = DB> + Send + 'args,
+  B: cl_traits::String,
+  DB: Database,
+  TD: TableDefs<'entity>,
+  TD::Associations: SqlWriter<B, Error = TD::Error>,
+  TD::Entity: FromRowsSuffix<B, SelectedRow<DB::Row>, Error = TD::Error>,
+  TD::Error: From<crate::Error>,
+  TD::PrimaryKeyValue: DecodePrimaryKey<SelectedRow<DB::Row>>,
+{
+  // A zero-sized channel makes the driver wait for the consumer, which means that only the rows
+  // of the current root entity are kept in memory.
+  let (mut sender, receiver) = mpsc::channel(0);
+  let driver = async move {
+    let mut mode = SqlWriterMode::parameterized();
+    table.write_select(buffer, DB::DIALECT, eager_loading, orders_by, select_limit, &mut |b| {
+      if let Some(elem) = filter {
+        elem.write(b, DB::DIALECT, &mut mode)?;
+      }
+      Ok(())
+    })?;
+    let statement = String::from(buffer.as_ref());
+    buffer.clear();
+    let values = mode.bind_arguments().map(|elem| elem.values()).unwrap_or_default();
+    let mut connection = executor.acquire().await.map_err(crate::Error::from)?;
+    let mut rows_stream = pin!(DB::fetch_stream(&statement, &mut connection, values));
+    let excluded = excluded_table_refs::<B, _>(eager_loading, table);
+    let mut previous = None;
+    let mut rows = Vec::new();
+    while let Some(row) = rows_stream.try_next().await? {
+      let row = SelectedRow::new(Arc::clone(&excluded), row);
+      let curr = decode_table_key::<_, _, TD>(buffer, &row, table.suffix())?;
+      if previous.as_ref().is_some_and(|elem| *elem != curr) {
+        for entity in collect_entities_tables(buffer, &rows, table)? {
+          let _rslt = sender.send(entity).await;
+        }
+        rows.clear();
+      }
+      previous = Some(curr);
+      rows.push(row);
+    }
+    for entity in collect_entities_tables(buffer, &rows, table)? {
+      let _rslt = sender.send(entity).await;
+    }
+    Ok(())
+  };
+  let errors = stream::once(driver).filter_map(|rslt| future::ready(rslt.err().map(Err)));
+  stream::select(receiver.map(Ok), errors)
+}
+
+#[inline]
+fn excluded_table_refs<'entity, B, TD>(
+  eager_loading: EagerLoading<'_>,
+  table: &Table<'entity, TD>,
+) -> Arc<[(&'static str, Suffix)]>
+where
+  B: cl_traits::String,
+  TD: TableDefs<'entity>,
+  TD::Associations: SqlWriter<B, Error = TD::Error>,
+  TD::Error: From<crate::Error>,
+{
+  let mut excluded = Vec::new();
+  SqlWriter::<B>::push_excluded_table_refs(table, eager_loading, &mut excluded);
+  excluded.into()
+}
+
+#[inline]
+fn selected_rows<'entity, B, ROW, TD>(
+  eager_loading: EagerLoading<'_>,
+  rows: Vec<ROW>,
+  table: &Table<'entity, TD>,
+) -> Vec<SelectedRow<ROW>>
+where
+  B: cl_traits::String,
+  TD: TableDefs<'entity>,
+  TD::Associations: SqlWriter<B, Error = TD::Error>,
+  TD::Error: From<crate::Error>,
+{
+  let excluded = excluded_table_refs::<B, TD>(eager_loading, table);
+  rows.into_iter().map(|row| SelectedRow::new(Arc::clone(&excluded), row)).collect()
+}
+
 /// Collects all entities composed by all different rows.
 ///
 /// One entity can constructed by more than one row.
 #[inline]
-fn collect_entities_tables<'entity, R, B, TD>(
+fn collect_entities_tables<'entity, B, R, ROW, TD>(
   buffer: &mut B,
-  rows: &[PgRow],
+  rows: &[ROW],
   table: &Table<'entity, TD>,
 ) -> Result<Vec<R>, TD::Error>
 where
   B: cl_traits::String,
-  R: FromRowsSuffix<B, Error = TD::Error>,
+  R: FromRowsSuffix<B, ROW, Error = TD::Error>,
+  ROW: DbRow,
   TD: TableDefs<'entity>,
+  TD::PrimaryKeyValue: DecodePrimaryKey<ROW>,
 {
   let mut rslt = Vec::new();
   let mut counter: usize = 0;
@@ -170,11 +247,12 @@ where
       break;
     }
     let actual_rows = rows.get(counter..).unwrap_or_default();
-    let skip = seek_related_entities::<_, _, _, TD>(
+    let skip = seek_related_entities::<_, _, _, _, TD, TD>(
       buffer,
       actual_rows,
       table.suffix(),
       table.suffix(),
+      JoinKind::Inner,
       |entity| {
         rslt.push(entity);
         Ok(())
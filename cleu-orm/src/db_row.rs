@@ -0,0 +1,32 @@
+use crate::{DecodeColumn, Suffix};
+
+/// A single row returned by a database.
+///
+/// Implemented for the rows of every supported backend so that one [crate::FromRowsSuffix]
+/// implementation can serve all of them.
+pub trait DbRow {
+  /// Decodes the value of `column`. NULL values are considered errors.
+  #[inline]
+  fn decode<T>(&self, column: &str) -> crate::Result<T>
+  where
+    Self: DecodeColumn<T>,
+  {
+    self.decode_column(column)?.ok_or(crate::Error::UnexpectedNullValue)
+  }
+
+  /// If the columns of the table identified by `table` and `suffix` weren't projected because
+  /// such table was excluded by [crate::EagerLoading].
+  #[inline]
+  fn is_excluded(&self, _table: &str, _suffix: Suffix) -> bool {
+    false
+  }
+
+  /// Decodes the value of `column`, returning `None` for NULL values.
+  #[inline]
+  fn decode_opt<T>(&self, column: &str) -> crate::Result<Option<T>>
+  where
+    Self: DecodeColumn<T>,
+  {
+    self.decode_column(column)
+  }
+}
@@ -0,0 +1,5 @@
+/// Decodes a value of type `T` stored in a column of a row. See [crate::DbRow].
+pub trait DecodeColumn<T> {
+  /// Returns `None` if the stored value is NULL. Unknown columns are errors.
+  fn decode_column(&self, column: &str) -> crate::Result<Option<T>>;
+}
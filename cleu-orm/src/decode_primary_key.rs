@@ -0,0 +1,141 @@
+use crate::{write_column_alias, DecodeColumn, Suffix};
+
+/// Primary key whose columns can be decoded from rows of type `ROW`.
+///
+/// Decoded keys are used to group the rows that belong to the same entity, which is why they only
+/// need to be comparable. Composite keys are decoded by tuples, one element for each column of
+/// [crate::TableDefs::PRIMARY_KEY_NAMES].
+pub trait DecodePrimaryKey<ROW> {
+  /// Owned representation of the decoded columns
+  type Decoded: PartialEq;
+
+  /// Decodes the columns `names` of `table` that are identified by `suffix`. NULL values are
+  /// considered errors.
+  fn decode_primary_key<B>(
+    buffer: &mut B,
+    names: &[&str],
+    row: &ROW,
+    suffix: Suffix,
+    table: &str,
+  ) -> crate::Result<Self::Decoded>
+  where
+    B: cl_traits::String;
+}
+
+impl<ROW, T> DecodePrimaryKey<ROW> for &'_ T
+where
+  T: DecodePrimaryKey<ROW> + ?Sized,
+{
+  type Decoded = T::Decoded;
+
+  #[inline]
+  fn decode_primary_key<B>(
+    buffer: &mut B,
+    names: &[&str],
+    row: &ROW,
+    suffix: Suffix,
+    table: &str,
+  ) -> crate::Result<Self::Decoded>
+  where
+    B: cl_traits::String,
+  {
+    T::decode_primary_key(buffer, names, row, suffix, table)
+  }
+}
+
+#[inline]
+fn decode_column<B, ROW, T>(
+  buffer: &mut B,
+  name: &str,
+  row: &ROW,
+  suffix: Suffix,
+  table: &str,
+) -> crate::Result<T>
+where
+  B: cl_traits::String,
+  ROW: DecodeColumn<T>,
+{
+  write_column_alias(buffer, table, suffix, name)?;
+  let rslt = row.decode_column(buffer.as_ref());
+  buffer.clear();
+  rslt?.ok_or(crate::Error::UnexpectedNullValue)
+}
+
+macro_rules! column_impls {
+  ($( $ty:ty => $decoded:ty ),+ $(,)?) => {
+    $(
+      impl<ROW> DecodePrimaryKey<ROW> for $ty
+      where
+        ROW: DecodeColumn<$decoded>,
+      {
+        type Decoded = $decoded;
+
+        #[inline]
+        fn decode_primary_key<B>(
+          buffer: &mut B,
+          names: &[&str],
+          row: &ROW,
+          suffix: Suffix,
+          table: &str,
+        ) -> crate::Result<Self::Decoded>
+        where
+          B: cl_traits::String,
+        {
+          let name = names.first().copied().unwrap_or_default();
+          decode_column(buffer, name, row, suffix, table)
+        }
+      }
+    )+
+  }
+}
+
+macro_rules! tuple_impls {
+  ($( ($($idx:tt $T:ident)+) )+) => {
+    $(
+      impl<ROW, $($T),+> DecodePrimaryKey<ROW> for ($($T,)+)
+      where
+        $($T: DecodePrimaryKey<ROW>,)+
+      {
+        type Decoded = ($($T::Decoded,)+);
+
+        #[inline]
+        fn decode_primary_key<B>(
+          buffer: &mut B,
+          names: &[&str],
+          row: &ROW,
+          suffix: Suffix,
+          table: &str,
+        ) -> crate::Result<Self::Decoded>
+        where
+          B: cl_traits::String,
+        {
+          Ok(($(
+            $T::decode_primary_key(
+              buffer,
+              names.get($idx..).unwrap_or_default(),
+              row,
+              suffix,
+              table
+            )?,
+          )+))
+        }
+      }
+    )+
+  }
+}
+
+column_impls!(
+  i32 => i32,
+  i64 => i64,
+  str => String,
+  String => String,
+);
+
+#[cfg(feature = "uuid")]
+column_impls!(uuid::Uuid => uuid::Uuid);
+
+tuple_impls! {
+  (0 T0 1 T1)
+  (0 T0 1 T1 2 T2)
+  (0 T0 1 T1 2 T2 3 T3)
+}
@@ -0,0 +1,77 @@
+use crate::Suffix;
+
+/// Associations that are joined, projected and ordered by SELECT operations.
+///
+/// Excluded associations, as well as their descendants, are left empty when entities are
+/// assembled by [crate::seek_related_entities] or [crate::seek_optional_entity], regardless of
+/// their [crate::JoinKind]. Filters must only reference included tables.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EagerLoading<'any> {
+  depth: u32,
+  excluded: &'any [(&'static str, Suffix)],
+  max_depth: u32,
+  // Name and maximum depth of the innermost self-referencing table. See
+  // `crate::SelfTableAssociation`.
+  self_limit: Option<(&'static str, u32)>,
+}
+
+impl<'any> EagerLoading<'any> {
+  /// Includes all associations
+  #[inline]
+  pub const fn all() -> Self {
+    Self { depth: 0, excluded: &[], max_depth: u32::MAX, self_limit: None }
+  }
+
+  /// Only includes the root table
+  #[inline]
+  pub const fn none() -> Self {
+    Self::all().with_max_depth(0)
+  }
+
+  /// Excludes the tables identified by [crate::Table::table_ref].
+  #[inline]
+  pub const fn with_excluded(mut self, excluded: &'any [(&'static str, Suffix)]) -> Self {
+    self.excluded = excluded;
+    self
+  }
+
+  /// Excludes associations that are more than `max_depth` levels below the root table.
+  #[inline]
+  pub const fn with_max_depth(mut self, max_depth: u32) -> Self {
+    self.max_depth = max_depth;
+    self
+  }
+
+  /// Instance used by the associations of the current level
+  #[inline]
+  pub(crate) const fn descend(mut self) -> Self {
+    self.depth = self.depth.saturating_add(1);
+    self
+  }
+
+  /// Only includes `table` in the current level and in the `levels - 1` levels below it. Other
+  /// tables aren't affected.
+  #[inline]
+  pub(crate) fn limit_self_depth(mut self, table: &'static str, levels: u32) -> Self {
+    let max_depth = self.depth.saturating_add(levels.saturating_sub(1));
+    self.self_limit = match self.self_limit {
+      Some((name, limit)) if name == table => Some((name, limit.min(max_depth))),
+      _ => Some((table, max_depth)),
+    };
+    self
+  }
+
+  #[inline]
+  pub(crate) fn includes(&self, table_ref: (&'static str, Suffix)) -> bool {
+    let is_beyond_self_limit =
+      self.self_limit.is_some_and(|(table, limit)| table == table_ref.0 && self.depth > limit);
+    self.depth <= self.max_depth && !is_beyond_self_limit && !self.excluded.contains(&table_ref)
+  }
+}
+
+impl Default for EagerLoading<'_> {
+  #[inline]
+  fn default() -> Self {
+    Self::all()
+  }
+}
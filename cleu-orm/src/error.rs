@@ -1,3 +1,4 @@
+use crate::ColumnType;
 use core::fmt;
 
 /// All possible errors
@@ -12,11 +13,36 @@ pub enum Error {
   /// Some internal operation found a hash collision of two table ids (likely) or a hash collision
   /// due to a number of nested associations larger than `MAX_NODES_NUM` (unlikely).
   HashCollision(u64, &'static str, &'static str),
+  /// The statements of a migration, identified by its name, were modified after being applied.
+  /// See [crate::migrations::Migration::checksum].
+  MigrationChecksumMismatch(&'static str),
+  /// A statement references the primary key generated by a previous statement, which modifies
+  /// the given table, but no key was returned
+  MissingGeneratedKey(&'static str),
   /// No row was returned by the database
   NoDatabaseRowResult,
+  /// A string literal contains a NUL byte, which can't be represented in SQL text
+  NulByteInStringLiteral,
   /// Errors of the `sqlx_core` crate
-  #[cfg(any(feature = "sqlx-postgres", feature = "sqlx-runtime-tokio-native-tls"))]
+  #[cfg(any(
+    feature = "sqlx-postgres",
+    feature = "sqlx-runtime-tokio-native-tls",
+    feature = "sqlx-sqlite"
+  ))]
   Sqlx(sqlx_core::error::Error),
+  /// A NULL value was found in a column that must have a value
+  UnexpectedNullValue,
+  /// A table schema doesn't have a column, identified by the table name and by the column name,
+  /// that is required by an association
+  UnknownColumn(&'static str, &'static str),
+  /// The target database can't change the type or the nullability of a column, identified by the
+  /// table name and by the column name, without rebuilding its table
+  UnsupportedAlterColumn(&'static str, &'static str),
+  /// A bind value can't be represented by the target database or is [crate::BindValue::Null]
+  UnsupportedBindValue,
+  /// The target database can't generate primary keys of the given type or return them through
+  /// `RETURNING`
+  UnsupportedGeneratedKey(ColumnType),
 }
 
 impl From<Error> for () {
@@ -38,7 +64,11 @@ impl From<fmt::Error> for Error {
   }
 }
 
-#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-runtime-tokio-native-tls"))]
+#[cfg(any(
+  feature = "sqlx-postgres",
+  feature = "sqlx-runtime-tokio-native-tls",
+  feature = "sqlx-sqlite"
+))]
 impl From<sqlx_core::error::Error> for Error {
   #[inline]
   fn from(from: sqlx_core::error::Error) -> Self {
@@ -0,0 +1,137 @@
+use crate::{write_select_field, write_sql_value, BindValue, Column, SqlDialect, SqlWriterMode};
+use core::ops::Not;
+
+/// A composable SQL boolean expression used by the WHERE clause of SELECT commands.
+///
+/// Leafs are created from [Column]s, which are obtained from [crate::Table] instances, and can
+/// then be combined with [Filter::and], [Filter::or] and `!`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filter {
+  node: FilterNode,
+}
+
+impl Filter {
+  /// `(self AND other)`
+  #[inline]
+  pub fn and(self, other: Self) -> Self {
+    Self { node: FilterNode::And(Box::new((self, other))) }
+  }
+
+  /// `(self OR other)`
+  #[inline]
+  pub fn or(self, other: Self) -> Self {
+    Self { node: FilterNode::Or(Box::new((self, other))) }
+  }
+
+  /// Writes the expression. Values are inlined or become placeholders according to `mode`.
+  #[inline]
+  pub fn write<B, D>(
+    &self,
+    buffer: &mut B,
+    dialect: D,
+    mode: &mut SqlWriterMode,
+  ) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+    D: SqlDialect,
+  {
+    match self.node {
+      FilterNode::And(ref pair) => Self::write_binary(buffer, dialect, mode, pair, " AND "),
+      FilterNode::Compare(column, operator, ref value) => {
+        Self::write_column(buffer, dialect, column)?;
+        buffer.push(operator)?;
+        write_sql_value(buffer, dialect, mode, value)
+      }
+      FilterNode::In(column, ref values) => {
+        if values.is_empty() {
+          buffer.push("1 = 0")?;
+          return Ok(());
+        }
+        Self::write_column(buffer, dialect, column)?;
+        buffer.push(" IN (")?;
+        for (idx, value) in values.iter().enumerate() {
+          if idx > 0 {
+            buffer.push(",")?;
+          }
+          write_sql_value(buffer, dialect, mode, value)?;
+        }
+        buffer.push(")")?;
+        Ok(())
+      }
+      FilterNode::IsNull(column) => {
+        Self::write_column(buffer, dialect, column)?;
+        buffer.push(" IS NULL")?;
+        Ok(())
+      }
+      FilterNode::Not(ref filter) => {
+        buffer.push("NOT (")?;
+        filter.write(buffer, dialect, mode)?;
+        buffer.push(")")?;
+        Ok(())
+      }
+      FilterNode::Or(ref pair) => Self::write_binary(buffer, dialect, mode, pair, " OR "),
+    }
+  }
+
+  #[inline]
+  pub(crate) fn compare(column: Column<()>, operator: &'static str, value: BindValue) -> Self {
+    Self { node: FilterNode::Compare(column, operator, value) }
+  }
+
+  #[inline]
+  pub(crate) fn is_in(column: Column<()>, values: Vec<BindValue>) -> Self {
+    Self { node: FilterNode::In(column, values) }
+  }
+
+  #[inline]
+  pub(crate) fn is_null(column: Column<()>) -> Self {
+    Self { node: FilterNode::IsNull(column) }
+  }
+
+  fn write_binary<B, D>(
+    buffer: &mut B,
+    dialect: D,
+    mode: &mut SqlWriterMode,
+    (lhs, rhs): &(Self, Self),
+    operator: &str,
+  ) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+    D: SqlDialect,
+  {
+    buffer.push("(")?;
+    lhs.write(buffer, dialect, mode)?;
+    buffer.push(operator)?;
+    rhs.write(buffer, dialect, mode)?;
+    buffer.push(")")?;
+    Ok(())
+  }
+
+  fn write_column<B, D>(buffer: &mut B, dialect: D, column: Column<()>) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+    D: SqlDialect,
+  {
+    write_select_field(buffer, dialect, column.table(), None, column.suffix(), column.field())
+  }
+}
+
+impl Not for Filter {
+  type Output = Self;
+
+  /// `NOT (self)`
+  #[inline]
+  fn not(self) -> Self::Output {
+    Self { node: FilterNode::Not(Box::new(self)) }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum FilterNode {
+  And(Box<(Filter, Filter)>),
+  Compare(Column<()>, &'static str, BindValue),
+  In(Column<()>, Vec<BindValue>),
+  IsNull(Column<()>),
+  Not(Box<Filter>),
+  Or(Box<(Filter, Filter)>),
+}
@@ -0,0 +1,38 @@
+/// `FOREIGN KEY` constraint of a [crate::TableSchema]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForeignKeySchema {
+  columns: Vec<&'static str>,
+  referenced_columns: Vec<&'static str>,
+  referenced_table: &'static str,
+}
+
+impl ForeignKeySchema {
+  /// Creates a new instance from all parameters. `columns` and `referenced_columns` are paired
+  /// by their indices.
+  #[inline]
+  pub const fn new(
+    columns: Vec<&'static str>,
+    referenced_columns: Vec<&'static str>,
+    referenced_table: &'static str,
+  ) -> Self {
+    Self { columns, referenced_columns, referenced_table }
+  }
+
+  /// Columns of the table that holds this constraint
+  #[inline]
+  pub fn columns(&self) -> &[&'static str] {
+    &self.columns
+  }
+
+  /// Columns of [Self::referenced_table]
+  #[inline]
+  pub fn referenced_columns(&self) -> &[&'static str] {
+    &self.referenced_columns
+  }
+
+  /// Name of the referenced table
+  #[inline]
+  pub const fn referenced_table(&self) -> &'static str {
+    self.referenced_table
+  }
+}
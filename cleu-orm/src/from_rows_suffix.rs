@@ -1,21 +1,19 @@
-#![cfg(any(feature = "sqlx-postgres", feature = "sqlx-runtime-tokio-native-tls"))]
-
-use crate::Suffix;
-use sqlx_core::postgres::PgRow;
+use crate::{DbRow, Suffix};
 
 /// Constructs a single instance based on an arbitrary number of rows
-pub trait FromRowsSuffix<B>: Sized
+pub trait FromRowsSuffix<B, ROW>: Sized
 where
   B: cl_traits::String,
+  ROW: DbRow,
 {
   /// See [crate::Error]
   type Error: From<crate::Error>;
 
   /// See [FromRowsSuffix].
   fn from_rows_suffix(
-    all_rows: &[PgRow],
+    all_rows: &[ROW],
     buffer: &mut B,
     suffix: Suffix,
-    target_row: &PgRow,
+    target_row: &ROW,
   ) -> Result<(usize, Self), Self::Error>;
 }
@@ -0,0 +1,50 @@
+use crate::OnConflict;
+
+/// Parameters of INSERT operations
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InsertOptions<'any> {
+  batch_size: usize,
+  on_conflict: Option<OnConflict<'any>>,
+}
+
+impl<'any> InsertOptions<'any> {
+  /// One statement per row without upserts
+  #[inline]
+  pub const fn new() -> Self {
+    Self { batch_size: 1, on_conflict: None }
+  }
+
+  /// Maximum number of sibling rows of the same table that are written in a single multi-row
+  /// INSERT statement
+  #[inline]
+  pub const fn batch_size(&self) -> usize {
+    self.batch_size
+  }
+
+  /// See [OnConflict].
+  #[inline]
+  pub const fn on_conflict(&self) -> Option<OnConflict<'any>> {
+    self.on_conflict
+  }
+
+  /// Replaces the maximum batch size. Zero is treated as one.
+  #[inline]
+  pub const fn with_batch_size(mut self, batch_size: usize) -> Self {
+    self.batch_size = if batch_size == 0 { 1 } else { batch_size };
+    self
+  }
+
+  /// Turns INSERT operations into upserts
+  #[inline]
+  pub const fn with_on_conflict(mut self, on_conflict: OnConflict<'any>) -> Self {
+    self.on_conflict = Some(on_conflict);
+    self
+  }
+}
+
+impl Default for InsertOptions<'_> {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
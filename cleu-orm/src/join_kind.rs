@@ -0,0 +1,19 @@
+/// How an associated table is joined in SELECT operations
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JoinKind {
+  /// INNER JOIN. Only returns entities that have at least one related entity.
+  Inner,
+  /// LEFT JOIN. Related entities are optional.
+  Left,
+}
+
+impl JoinKind {
+  /// SQL keyword
+  #[inline]
+  pub const fn keyword(self) -> &'static str {
+    match self {
+      Self::Inner => "INNER JOIN",
+      Self::Left => "LEFT JOIN",
+    }
+  }
+}
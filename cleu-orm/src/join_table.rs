@@ -0,0 +1,37 @@
+/// Intermediate table of a many-to-many association, which holds one row for each pair of
+/// associated entities. See [crate::TableAssociation::with_join_table].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JoinTable {
+  from_column: &'static str,
+  name: &'static str,
+  to_column: &'static str,
+}
+
+impl JoinTable {
+  /// Creates a new instance from all parameters.
+  ///
+  /// `from_column` references the caller id and `to_column` references the callee id, e.g.,
+  /// `JoinTable::new("user_id", "user_groups", "group_id")`.
+  #[inline]
+  pub const fn new(from_column: &'static str, name: &'static str, to_column: &'static str) -> Self {
+    Self { from_column, name, to_column }
+  }
+
+  /// Column that references the caller id
+  #[inline]
+  pub const fn from_column(&self) -> &'static str {
+    self.from_column
+  }
+
+  /// Table name
+  #[inline]
+  pub const fn name(&self) -> &'static str {
+    self.name
+  }
+
+  /// Column that references the callee id
+  #[inline]
+  pub const fn to_column(&self) -> &'static str {
+    self.to_column
+  }
+}
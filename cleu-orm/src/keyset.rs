@@ -0,0 +1,37 @@
+/// Cursor of keyset pagination. See `Table::read_keyset` and [crate::Table::keyset_filter].
+///
+/// Root entities are sorted by their primary keys and only the ones whose keys come after
+/// [Keyset::last] are selected, i.e., `WHERE key > last ORDER BY key LIMIT count`. Unlike
+/// [crate::SelectLimit::Offset], previous pages aren't scanned again.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Keyset<V> {
+  count: u32,
+  last: Option<V>,
+}
+
+impl<V> Keyset<V> {
+  /// Page with at most `count` root entities whose primary keys come after `last`, which is
+  /// usually the primary key of the last entity of the previous page.
+  #[inline]
+  pub const fn after(count: u32, last: V) -> Self {
+    Self { count, last: Some(last) }
+  }
+
+  /// First page with at most `count` root entities
+  #[inline]
+  pub const fn first(count: u32) -> Self {
+    Self { count, last: None }
+  }
+
+  /// Maximum number of root entities
+  #[inline]
+  pub const fn count(&self) -> u32 {
+    self.count
+  }
+
+  /// Primary key that precedes the page, if any
+  #[inline]
+  pub const fn last(&self) -> Option<&V> {
+    self.last.as_ref()
+  }
+}
@@ -1,18 +1,48 @@
 //! # Cleu ORM
 
+mod associations_schema;
+mod aux_nodes;
+mod bind_arguments;
+mod bind_value;
+mod column;
+mod column_schema;
+mod column_type;
+mod column_types;
+mod conflict_action;
 pub mod crud;
+mod db_row;
+mod decode_column;
+mod decode_primary_key;
+mod eager_loading;
 mod error;
-#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-runtime-tokio-native-tls"))]
+mod filter;
+mod foreign_key_schema;
 mod from_rows_suffix;
 mod full_table_association;
 mod fx_hasher;
+mod insert_options;
+mod join_kind;
+mod join_table;
+mod keyset;
+pub mod migrations;
 mod no_table_association;
 mod no_table_entity;
 mod no_table_field;
+mod nulls_order;
+mod on_conflict;
+mod optional_table;
+mod order_by;
+mod primary_key;
+mod schema;
 mod select_limit;
 mod select_order_by;
+mod selected_row;
+mod self_table_association;
+mod sql_dialect;
+mod sql_type;
 mod sql_value;
 mod sql_writer;
+mod sql_writer_mode;
 mod table;
 mod table_association;
 mod table_association_wrapper;
@@ -20,27 +50,58 @@ mod table_associations;
 mod table_defs;
 mod table_field;
 mod table_fields;
+mod table_schema;
 mod table_source_association;
+mod table_storage;
 #[cfg(test)]
 mod tests;
 mod tuple_impls;
 mod utils;
 
+pub use associations_schema::*;
+pub use aux_nodes::*;
+pub use bind_arguments::*;
+pub use bind_value::*;
 pub use cl_traits::String;
 #[cfg(feature = "derive")]
 pub use cleu_orm_derive::*;
+pub use column::*;
+pub use column_schema::*;
+pub use column_type::*;
+pub use column_types::*;
+pub use conflict_action::*;
+pub use db_row::*;
+pub use decode_column::*;
+pub use decode_primary_key::*;
+pub use eager_loading::*;
 pub use error::*;
-#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-runtime-tokio-native-tls"))]
+pub use filter::*;
+pub use foreign_key_schema::*;
 pub use from_rows_suffix::*;
 pub use full_table_association::*;
 pub(crate) use fx_hasher::*;
+pub use insert_options::*;
+pub use join_kind::*;
+pub use join_table::*;
+pub use keyset::*;
 pub use no_table_association::*;
 pub use no_table_entity::*;
 pub use no_table_field::*;
+pub use nulls_order::*;
+pub use on_conflict::*;
+pub use optional_table::*;
+pub use order_by::*;
+pub use primary_key::*;
+pub use schema::*;
 pub use select_limit::*;
 pub use select_order_by::*;
+pub use selected_row::*;
+pub use self_table_association::*;
+pub use sql_dialect::*;
+pub use sql_type::*;
 pub use sql_value::*;
 pub use sql_writer::*;
+pub use sql_writer_mode::*;
 pub use table::*;
 pub use table_association::*;
 pub use table_association_wrapper::*;
@@ -48,7 +109,9 @@ pub use table_associations::*;
 pub use table_defs::*;
 pub use table_field::*;
 pub use table_fields::*;
+pub use table_schema::*;
 pub use table_source_association::*;
+pub use table_storage::*;
 pub use utils::*;
 
 /// Shortcut to avoid having to manually type the result of [TableDefs::new]
@@ -60,5 +123,3 @@ pub type InitialInsertValue = i32;
 pub type Result<T> = core::result::Result<T, Error>;
 /// Used by some operations to identify different tables
 pub type Suffix = u32;
-
-type AuxNodes = smallvec::SmallVec<[(u64, &'static str); 64]>;
@@ -0,0 +1,26 @@
+//! Differences between the schema defined in Rust and the schema of a live database, as well as
+//! tracked application of the resulting statements.
+
+mod db_column;
+mod db_foreign_key;
+mod db_table;
+mod migrate;
+mod migration;
+mod postgres;
+mod schema_change;
+mod utils;
+
+pub use db_column::*;
+pub use db_foreign_key::*;
+pub use db_table::*;
+#[cfg(any(
+  feature = "sqlx-postgres",
+  feature = "sqlx-runtime-tokio-native-tls",
+  feature = "sqlx-sqlite"
+))]
+pub use migrate::*;
+pub use migration::*;
+#[cfg(feature = "sqlx-postgres")]
+pub use postgres::*;
+pub use schema_change::*;
+pub use utils::*;
@@ -0,0 +1,42 @@
+/// Column of a [crate::migrations::DbTable]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DbColumn {
+  data_type: String,
+  is_generated: bool,
+  is_nullable: bool,
+  name: String,
+}
+
+impl DbColumn {
+  /// Creates a new instance from all parameters
+  #[inline]
+  pub const fn new(data_type: String, is_generated: bool, is_nullable: bool, name: String) -> Self {
+    Self { data_type, is_generated, is_nullable, name }
+  }
+
+  /// SQL type as reported by the database, which is compared case-insensitively with
+  /// [crate::SqlDialect::write_column_type].
+  #[inline]
+  pub fn data_type(&self) -> &str {
+    &self.data_type
+  }
+
+  /// If values are generated by the database, which is compared with
+  /// [crate::ColumnSchema::is_generated].
+  #[inline]
+  pub const fn is_generated(&self) -> bool {
+    self.is_generated
+  }
+
+  /// If NULL values are accepted
+  #[inline]
+  pub const fn is_nullable(&self) -> bool {
+    self.is_nullable
+  }
+
+  /// Column name
+  #[inline]
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+}
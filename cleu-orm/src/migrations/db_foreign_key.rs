@@ -0,0 +1,45 @@
+/// `FOREIGN KEY` constraint of a [crate::migrations::DbTable]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DbForeignKey {
+  columns: Vec<String>,
+  name: String,
+  referenced_columns: Vec<String>,
+  referenced_table: String,
+}
+
+impl DbForeignKey {
+  /// Creates a new instance from all parameters
+  #[inline]
+  pub const fn new(
+    columns: Vec<String>,
+    name: String,
+    referenced_columns: Vec<String>,
+    referenced_table: String,
+  ) -> Self {
+    Self { columns, name, referenced_columns, referenced_table }
+  }
+
+  /// Columns of the table that holds this constraint
+  #[inline]
+  pub fn columns(&self) -> &[String] {
+    &self.columns
+  }
+
+  /// Constraint name
+  #[inline]
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Columns of [Self::referenced_table]
+  #[inline]
+  pub fn referenced_columns(&self) -> &[String] {
+    &self.referenced_columns
+  }
+
+  /// Name of the referenced table
+  #[inline]
+  pub fn referenced_table(&self) -> &str {
+    &self.referenced_table
+  }
+}
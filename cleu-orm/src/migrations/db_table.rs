@@ -0,0 +1,62 @@
+use crate::migrations::{DbColumn, DbForeignKey};
+
+/// Columns and constraints of a table as they currently are in the database, which are compared
+/// with a [crate::TableSchema] by [crate::migrations::schema_changes].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DbTable {
+  columns: Vec<DbColumn>,
+  foreign_keys: Vec<DbForeignKey>,
+  name: String,
+  primary_key: Vec<String>,
+  primary_key_name: Option<String>,
+}
+
+impl DbTable {
+  /// Creates a new instance from all parameters
+  #[inline]
+  pub const fn new(
+    columns: Vec<DbColumn>,
+    foreign_keys: Vec<DbForeignKey>,
+    name: String,
+    primary_key: Vec<String>,
+    primary_key_name: Option<String>,
+  ) -> Self {
+    Self { columns, foreign_keys, name, primary_key, primary_key_name }
+  }
+
+  /// Column named `name`, if any
+  #[inline]
+  pub fn column(&self, name: &str) -> Option<&DbColumn> {
+    self.columns.iter().find(|elem| elem.name() == name)
+  }
+
+  /// All columns in declaration order
+  #[inline]
+  pub fn columns(&self) -> &[DbColumn] {
+    &self.columns
+  }
+
+  /// See [DbForeignKey].
+  #[inline]
+  pub fn foreign_keys(&self) -> &[DbForeignKey] {
+    &self.foreign_keys
+  }
+
+  /// Table name
+  #[inline]
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Names of all columns that compose the primary key, if any
+  #[inline]
+  pub fn primary_key(&self) -> &[String] {
+    &self.primary_key
+  }
+
+  /// Name of the primary key constraint, if any
+  #[inline]
+  pub fn primary_key_name(&self) -> Option<&str> {
+    self.primary_key_name.as_deref()
+  }
+}
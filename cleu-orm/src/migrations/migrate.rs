@@ -0,0 +1,82 @@
+#![cfg(any(
+  feature = "sqlx-postgres",
+  feature = "sqlx-runtime-tokio-native-tls",
+  feature = "sqlx-sqlite"
+))]
+
+use crate::{
+  crud::Database, migrations::Migration, DbRow, DecodeColumn, SqlDialect, SqlWriterMode,
+};
+use core::fmt::Write;
+use sqlx_core::{acquire::Acquire, transaction::Transaction};
+
+/// Table that tracks applied migrations
+pub const MIGRATIONS_TABLE: &str = "schema_migrations";
+
+/// Applies, in order, all `migrations` that aren't recorded in [MIGRATIONS_TABLE], which is
+/// created if it doesn't exist. Returns the names of the applied migrations.
+///
+/// Everything is executed inside a single transaction. Recorded migrations whose checksums don't
+/// match [Migration::checksum] abort the whole operation with
+/// [crate::Error::MigrationChecksumMismatch]. If `dry_run` is `true`, checksums are verified and
+/// the names of the pending migrations are returned but the transaction is rolled back without
+/// executing them.
+#[inline]
+pub async fn migrate<'exec, A, DB>(
+  dry_run: bool,
+  executor: A,
+  migrations: &[Migration],
+) -> crate::Result<Vec<&'static str>>
+where
+  A: Acquire<'exec, Database = DB> + Send,
+  DB: Database,
+  DB::Row: DecodeColumn<String>,
+  for<'tr> &'tr mut Transaction<'exec, DB>: Acquire<'tr, Database = DB> + Send,
+{
+  let mut transaction = executor.begin().await?;
+  let q = <DB::Dialect as SqlDialect>::IDENTIFIER_QUOTE;
+  let create = format!(
+    "CREATE TABLE IF NOT EXISTS {q}{MIGRATIONS_TABLE}{q} ({q}name{q} VARCHAR(255) NOT \
+     NULL,{q}checksum{q} VARCHAR(16) NOT NULL,PRIMARY KEY ({q}name{q}))"
+  );
+  let _ = DB::execute_parameterized(&create, &mut transaction, &SqlWriterMode::Inline).await?;
+  let select = format!("SELECT {q}name{q},{q}checksum{q} FROM {q}{MIGRATIONS_TABLE}{q}");
+  let mut applied = Vec::new();
+  for row in DB::fetch_all(&select, &mut transaction, &[]).await? {
+    applied.push((row.decode::<String>("name")?, row.decode::<String>("checksum")?));
+  }
+  let mut buffer = String::new();
+  let mut rslt = Vec::new();
+  for migration in migrations {
+    let checksum = format!("{:016x}", migration.checksum());
+    if let Some((_, applied_checksum)) = applied.iter().find(|elem| elem.0 == migration.name()) {
+      if *applied_checksum != checksum {
+        return Err(crate::Error::MigrationChecksumMismatch(migration.name()));
+      }
+      continue;
+    }
+    rslt.push(migration.name());
+    if dry_run {
+      continue;
+    }
+    for statement in migration.statements() {
+      let _ =
+        DB::execute_parameterized(statement, &mut transaction, &SqlWriterMode::Inline).await?;
+    }
+    buffer.clear();
+    buffer.write_fmt(format_args!(
+      "INSERT INTO {q}{MIGRATIONS_TABLE}{q} ({q}name{q},{q}checksum{q}) VALUES ("
+    ))?;
+    DB::DIALECT.write_str(&mut buffer, migration.name())?;
+    buffer.write_str(",")?;
+    DB::DIALECT.write_str(&mut buffer, &checksum)?;
+    buffer.write_str(")")?;
+    let _ = DB::execute_parameterized(&buffer, &mut transaction, &SqlWriterMode::Inline).await?;
+  }
+  if dry_run {
+    transaction.rollback().await?;
+  } else {
+    transaction.commit().await?;
+  }
+  Ok(rslt)
+}
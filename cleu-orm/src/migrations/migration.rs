@@ -0,0 +1,62 @@
+use crate::{migrations::SchemaChange, FxHasher, SqlDialect, SqlWriterMode};
+use core::hash::Hasher;
+
+/// Named group of statements that is applied at most once. See [crate::migrations::migrate].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Migration {
+  checksum: u64,
+  name: &'static str,
+  statements: Vec<String>,
+}
+
+impl Migration {
+  /// Creates a new instance from all parameters. `statements` are executed in order and each
+  /// element must contain a single statement.
+  #[inline]
+  pub fn new(name: &'static str, statements: Vec<String>) -> Self {
+    let mut hasher = FxHasher::default();
+    for statement in &statements {
+      hasher.write(statement.as_bytes());
+      hasher.write_u8(0);
+    }
+    Self { checksum: hasher.finish(), name, statements }
+  }
+
+  /// Creates a new instance with one statement for each element of `changes`.
+  #[inline]
+  pub fn from_changes<D>(
+    changes: &[SchemaChange],
+    dialect: D,
+    name: &'static str,
+  ) -> crate::Result<Self>
+  where
+    D: SqlDialect,
+  {
+    let mut statements = Vec::with_capacity(changes.len());
+    for change in changes {
+      let mut statement = String::new();
+      change.write(&mut statement, dialect, &mut SqlWriterMode::Inline)?;
+      statements.push(statement);
+    }
+    Ok(Self::new(name, statements))
+  }
+
+  /// Hash of all statements, which is stored along with [Self::name] when this migration is
+  /// applied so that later modifications can be detected.
+  #[inline]
+  pub const fn checksum(&self) -> u64 {
+    self.checksum
+  }
+
+  /// Unique name, e.g., `0001_create_users`
+  #[inline]
+  pub const fn name(&self) -> &'static str {
+    self.name
+  }
+
+  /// All statements in execution order
+  #[inline]
+  pub fn statements(&self) -> &[String] {
+    &self.statements
+  }
+}
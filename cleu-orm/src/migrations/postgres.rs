@@ -0,0 +1,94 @@
+#![cfg(feature = "sqlx-postgres")]
+
+use crate::{
+  crud::Database,
+  migrations::{DbColumn, DbForeignKey, DbTable},
+  DbRow, PostgresDialect, Schema, SqlDialect,
+};
+use core::fmt::Write;
+use sqlx_core::{acquire::Acquire, postgres::Postgres};
+
+/// Reads, from the current schema of a PostgreSQL database, the columns and the constraints of
+/// all tables of `schema` that exist. Types are the ones returned by `format_type` and identity
+/// columns are the generated ones. See
+/// [crate::migrations::schema_changes].
+#[inline]
+pub async fn read_postgres_tables<'exec, A>(
+  executor: A,
+  schema: &Schema,
+) -> crate::Result<Vec<DbTable>>
+where
+  A: Acquire<'exec, Database = Postgres> + Send,
+{
+  let mut names = String::new();
+  for (idx, table) in schema.tables().iter().enumerate() {
+    names.write_str(if idx == 0 { "" } else { "," })?;
+    PostgresDialect.write_str(&mut names, table.name())?;
+  }
+  if names.is_empty() {
+    return Ok(Vec::new());
+  }
+  let mut connection = executor.acquire().await?;
+  let columns = format!(
+    "SELECT c.relname::text AS table_name,a.attname::text AS name,format_type(a.atttypid, \
+     a.atttypmod) AS data_type,a.attidentity <> '' AS is_generated,NOT a.attnotnull AS \
+     is_nullable FROM pg_catalog.pg_attribute AS a JOIN pg_catalog.pg_class AS c ON c.oid = \
+     a.attrelid JOIN pg_catalog.pg_namespace AS n ON n.oid = c.relnamespace WHERE n.nspname = \
+     current_schema() AND c.relkind = 'r' AND a.attnum > 0 AND NOT a.attisdropped AND c.relname \
+     IN ({names}) ORDER BY c.relname,a.attnum"
+  );
+  let mut tables =
+    Vec::<(String, Vec<DbColumn>, Vec<DbForeignKey>, Vec<String>, Option<String>)>::new();
+  for row in Postgres::fetch_all(&columns, &mut *connection, &[]).await? {
+    let table_name: String = row.decode("table_name")?;
+    let column = DbColumn::new(
+      row.decode("data_type")?,
+      row.decode("is_generated")?,
+      row.decode("is_nullable")?,
+      row.decode("name")?,
+    );
+    if let Some(table) = tables.last_mut().filter(|elem| elem.0 == table_name) {
+      table.1.push(column);
+    } else {
+      tables.push((table_name, vec![column], Vec::new(), Vec::new(), None));
+    }
+  }
+  let constraints = format!(
+    "SELECT con.conname::text AS name,con.contype::text AS kind,c.relname::text AS \
+     table_name,ARRAY(SELECT a.attname::text FROM unnest(con.conkey) WITH ORDINALITY AS k(num, \
+     ord) JOIN pg_catalog.pg_attribute AS a ON a.attrelid = con.conrelid AND a.attnum = k.num \
+     ORDER BY k.ord) AS columns,fc.relname::text AS referenced_table,ARRAY(SELECT a.attname::text \
+     FROM unnest(con.confkey) WITH ORDINALITY AS k(num, ord) JOIN pg_catalog.pg_attribute AS a ON \
+     a.attrelid = con.confrelid AND a.attnum = k.num ORDER BY k.ord) AS referenced_columns FROM \
+     pg_catalog.pg_constraint AS con JOIN pg_catalog.pg_class AS c ON c.oid = con.conrelid JOIN \
+     pg_catalog.pg_namespace AS n ON n.oid = c.relnamespace LEFT JOIN pg_catalog.pg_class AS fc \
+     ON fc.oid = con.confrelid WHERE n.nspname = current_schema() AND con.contype IN ('f','p') \
+     AND c.relname IN ({names}) ORDER BY c.relname,con.conname"
+  );
+  for row in Postgres::fetch_all(&constraints, &mut *connection, &[]).await? {
+    let table_name: String = row.decode("table_name")?;
+    let table = if let Some(elem) = tables.iter_mut().find(|elem| elem.0 == table_name) {
+      elem
+    } else {
+      continue;
+    };
+    let name: String = row.decode("name")?;
+    let columns: Vec<String> = row.decode("columns")?;
+    if row.decode::<String>("kind")? == "p" {
+      table.3 = columns;
+      table.4 = Some(name);
+    } else {
+      let referenced_columns = row.decode("referenced_columns")?;
+      let referenced_table = row.decode_opt("referenced_table")?.unwrap_or_default();
+      table.2.push(DbForeignKey::new(columns, name, referenced_columns, referenced_table));
+    }
+  }
+  Ok(
+    tables
+      .into_iter()
+      .map(|(name, columns, foreign_keys, primary_key, primary_key_name)| {
+        DbTable::new(columns, foreign_keys, name, primary_key, primary_key_name)
+      })
+      .collect(),
+  )
+}
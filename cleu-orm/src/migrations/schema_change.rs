@@ -0,0 +1,92 @@
+use crate::{
+  write_names, write_statement_terminator, ColumnSchema, ForeignKeySchema, SqlDialect,
+  SqlWriterMode, TableSchema,
+};
+
+/// A single difference between the Rust definitions and the database, which is fixed by one
+/// statement. See [crate::migrations::schema_changes].
+#[derive(Clone, Debug, PartialEq)]
+pub enum SchemaChange {
+  /// Column, of the given table, that doesn't exist in the database. Tables with rows can only
+  /// receive nullable columns.
+  AddColumn(&'static str, ColumnSchema),
+  /// Foreign key, of the given table, that doesn't exist in the database
+  AddForeignKey(&'static str, ForeignKeySchema),
+  /// Primary key, of the given table, that doesn't exist in the database
+  AddPrimaryKey(&'static str, Vec<&'static str>),
+  /// Column, of the given table, whose type, nullability or generation differs, followed by
+  /// whether its values are currently generated by the database
+  AlterColumn(&'static str, ColumnSchema, bool),
+  /// Table that doesn't exist in the database
+  CreateTable(TableSchema),
+  /// Column, of the given table, that only exists in the database. Only returned when requested
+  /// because its data is lost.
+  DropColumn(&'static str, String),
+  /// Constraint, of the given table, that only exists in the database or that must be replaced
+  DropConstraint(&'static str, String),
+}
+
+impl SchemaChange {
+  /// Name of the affected table
+  #[inline]
+  pub fn table(&self) -> &'static str {
+    match *self {
+      Self::AddColumn(table, _)
+      | Self::AddForeignKey(table, _)
+      | Self::AddPrimaryKey(table, _)
+      | Self::AlterColumn(table, ..)
+      | Self::DropColumn(table, _)
+      | Self::DropConstraint(table, _) => table,
+      Self::CreateTable(ref elem) => elem.name(),
+    }
+  }
+
+  /// Writes the statement that applies this change
+  #[inline]
+  pub fn write<B, D>(
+    &self,
+    buffer: &mut B,
+    dialect: D,
+    mode: &mut SqlWriterMode,
+  ) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+    D: SqlDialect,
+  {
+    if let Self::CreateTable(ref elem) = *self {
+      return elem.write_create_table(buffer, dialect, mode);
+    }
+    let q = D::IDENTIFIER_QUOTE;
+    buffer.write_fmt(format_args!("ALTER TABLE {q}{}{q} ", self.table()))?;
+    match *self {
+      Self::AddColumn(_, ref column) => {
+        buffer.push("ADD COLUMN ")?;
+        column.write(buffer, dialect)?;
+      }
+      Self::AddForeignKey(_, ref foreign_key) => {
+        buffer.push("ADD FOREIGN KEY (")?;
+        write_names(buffer, dialect, foreign_key.columns())?;
+        buffer
+          .write_fmt(format_args!(") REFERENCES {q}{}{q} (", foreign_key.referenced_table()))?;
+        write_names(buffer, dialect, foreign_key.referenced_columns())?;
+        buffer.push(")")?;
+      }
+      Self::AddPrimaryKey(_, ref columns) => {
+        buffer.push("ADD PRIMARY KEY (")?;
+        write_names(buffer, dialect, columns)?;
+        buffer.push(")")?;
+      }
+      Self::AlterColumn(table, ref column, was_generated) => {
+        dialect.write_alter_column(buffer, column, table, was_generated)?
+      }
+      Self::CreateTable(_) => {}
+      Self::DropColumn(_, ref column) => {
+        buffer.write_fmt(format_args!("DROP COLUMN {q}{column}{q}"))?
+      }
+      Self::DropConstraint(_, ref constraint) => {
+        buffer.write_fmt(format_args!("DROP CONSTRAINT {q}{constraint}{q}"))?
+      }
+    }
+    write_statement_terminator(buffer, dialect, mode, None, self.table())
+  }
+}
@@ -0,0 +1,112 @@
+use crate::{
+  migrations::{DbForeignKey, DbTable, SchemaChange},
+  ForeignKeySchema, Schema, SqlDialect,
+};
+
+/// Compares the tables of `schema`, which are derived from the Rust definitions, with `current`,
+/// which are read from the database, and returns the changes that make the database match
+/// `schema`.
+///
+/// Changes are ordered so that each one only depends on the previous ones: constraints are
+/// dropped first, missing tables are created in dependency order, columns are added or altered,
+/// primary and foreign keys are added and, lastly, columns that only exist in the database are
+/// dropped if `drop_columns` is `true`. Otherwise, such columns and their data are kept.
+///
+/// Replaced primary keys are only dropped after the foreign keys that reference them, which are
+/// added again after the new primary keys. Tables of `current` that aren't part of `schema` are
+/// ignored, including their foreign keys.
+#[inline]
+pub fn schema_changes<D>(
+  current: &[DbTable],
+  dialect: D,
+  drop_columns: bool,
+  schema: &Schema,
+) -> crate::Result<Vec<SchemaChange>>
+where
+  D: SqlDialect,
+{
+  let existing = || {
+    schema.tables().iter().filter_map(|table| {
+      let db_table = current.iter().find(|elem| elem.name() == table.name())?;
+      Some((db_table, table))
+    })
+  };
+  let replaced_primary_keys: Vec<_> = existing()
+    .filter_map(|(db_table, table)| {
+      let name = db_table.primary_key_name()?;
+      let is_replaced = !eq_names(table.primary_key(), db_table.primary_key());
+      is_replaced.then_some((table.name(), name))
+    })
+    .collect();
+  let is_kept = |db_foreign_key: &DbForeignKey, foreign_key: &ForeignKeySchema| {
+    !replaced_primary_keys.iter().any(|elem| elem.0 == db_foreign_key.referenced_table())
+      && eq_foreign_keys(db_foreign_key, foreign_key)
+  };
+  let mut changes = Vec::new();
+  for (db_table, table) in existing() {
+    for db_foreign_key in db_table.foreign_keys() {
+      if !table.foreign_keys().iter().any(|elem| is_kept(db_foreign_key, elem)) {
+        changes.push(SchemaChange::DropConstraint(table.name(), db_foreign_key.name().into()));
+      }
+    }
+  }
+  for &(table, name) in &replaced_primary_keys {
+    changes.push(SchemaChange::DropConstraint(table, name.into()));
+  }
+  for table in schema.tables_by_dependency() {
+    if !current.iter().any(|elem| elem.name() == table.name()) {
+      changes.push(SchemaChange::CreateTable(table.clone()));
+    }
+  }
+  let mut data_type = String::new();
+  for (db_table, table) in existing() {
+    for column in table.columns() {
+      if let Some(db_column) = db_table.column(column.name()) {
+        data_type.clear();
+        dialect.write_column_type(&mut data_type, column.column_type())?;
+        if !db_column.data_type().eq_ignore_ascii_case(&data_type)
+          || db_column.is_generated() != column.is_generated()
+          || db_column.is_nullable() != column.is_nullable()
+        {
+          changes.push(SchemaChange::AlterColumn(table.name(), *column, db_column.is_generated()));
+        }
+      } else {
+        changes.push(SchemaChange::AddColumn(table.name(), *column));
+      }
+    }
+  }
+  for (db_table, table) in existing() {
+    if !table.primary_key().is_empty() && !eq_names(table.primary_key(), db_table.primary_key()) {
+      changes.push(SchemaChange::AddPrimaryKey(table.name(), table.primary_key().to_vec()));
+    }
+  }
+  for (db_table, table) in existing() {
+    for foreign_key in table.foreign_keys() {
+      if !db_table.foreign_keys().iter().any(|elem| is_kept(elem, foreign_key)) {
+        changes.push(SchemaChange::AddForeignKey(table.name(), foreign_key.clone()));
+      }
+    }
+  }
+  if drop_columns {
+    for (db_table, table) in existing() {
+      for db_column in db_table.columns() {
+        if table.column(db_column.name()).is_none() {
+          changes.push(SchemaChange::DropColumn(table.name(), db_column.name().into()));
+        }
+      }
+    }
+  }
+  Ok(changes)
+}
+
+#[inline]
+fn eq_foreign_keys(db_foreign_key: &DbForeignKey, foreign_key: &ForeignKeySchema) -> bool {
+  db_foreign_key.referenced_table() == foreign_key.referenced_table()
+    && eq_names(foreign_key.columns(), db_foreign_key.columns())
+    && eq_names(foreign_key.referenced_columns(), db_foreign_key.referenced_columns())
+}
+
+#[inline]
+fn eq_names(names: &[&str], db_names: &[String]) -> bool {
+  names.len() == db_names.len() && names.iter().zip(db_names).all(|(a, b)| a == b)
+}
@@ -1,5 +1,6 @@
 use crate::{
-  AuxNodes, FullTableAssociation, SelectLimit, SelectOrderBy, SqlWriter, TableAssociations,
+  AssociationsSchema, AuxNodes, EagerLoading, FullTableAssociation, InsertOptions, OrderBy, Schema,
+  SelectLimit, SqlDialect, SqlWriter, SqlWriterMode, Suffix, TableAssociations,
   TableSourceAssociation,
 };
 use core::{array, marker::PhantomData};
@@ -16,6 +17,13 @@ impl<E> NoTableAssociation<E> {
   }
 }
 
+impl<E> AssociationsSchema for NoTableAssociation<E> {
+  #[inline]
+  fn push_schema(&self, _: &mut Schema, _: &'static str) -> crate::Result<()> {
+    Ok(())
+  }
+}
+
 impl<E> TableAssociations for NoTableAssociation<E> {
   type FullTableAssociations = array::IntoIter<FullTableAssociation, 0>;
 
@@ -23,6 +31,11 @@ impl<E> TableAssociations for NoTableAssociation<E> {
   fn full_associations(&self) -> Self::FullTableAssociations {
     [].into_iter()
   }
+
+  #[inline]
+  fn has_dependent_tables(&self) -> bool {
+    false
+  }
 }
 
 impl<B, E> SqlWriter<B> for NoTableAssociation<E>
@@ -33,7 +46,21 @@ where
   type Error = E;
 
   #[inline]
-  fn write_delete(&self, _: &mut AuxNodes, _: &mut B) -> Result<(), Self::Error> {
+  fn push_excluded_table_refs(&self, _: EagerLoading<'_>, _: &mut Vec<(&'static str, Suffix)>) {}
+
+  #[inline]
+  fn register_insert_parents(&self, _: &mut AuxNodes) -> Result<(), Self::Error> {
+    Ok(())
+  }
+
+  #[inline]
+  fn write_delete(
+    &self,
+    _: &mut AuxNodes,
+    _: &mut B,
+    _: impl SqlDialect,
+    _: &mut SqlWriterMode,
+  ) -> Result<(), Self::Error> {
     Ok(())
   }
 
@@ -42,16 +69,34 @@ where
     &self,
     _: &mut AuxNodes,
     _: &mut B,
+    _: impl SqlDialect,
+    _: &mut SqlWriterMode,
+    _: InsertOptions<'_>,
     _: &mut Option<TableSourceAssociation<'value, V>>,
   ) -> Result<(), Self::Error> {
     Ok(())
   }
 
+  #[inline]
+  fn write_insert_referenced(
+    &self,
+    _: &mut AuxNodes,
+    _: &mut B,
+    _: impl SqlDialect,
+    _: u64,
+    _: &mut SqlWriterMode,
+    _: InsertOptions<'_>,
+  ) -> Result<(), Self::Error> {
+    Ok(())
+  }
+
   #[inline]
   fn write_select(
     &self,
     _: &mut B,
-    _: SelectOrderBy,
+    _: impl SqlDialect,
+    _: EagerLoading<'_>,
+    _: &[OrderBy],
     _: SelectLimit,
     _: &mut impl FnMut(&mut B) -> Result<(), Self::Error>,
   ) -> Result<(), Self::Error> {
@@ -59,22 +104,44 @@ where
   }
 
   #[inline]
-  fn write_select_associations(&self, _: &mut B) -> Result<(), Self::Error> {
+  fn write_select_associations(
+    &self,
+    _: &mut B,
+    _: impl SqlDialect,
+    _: EagerLoading<'_>,
+  ) -> Result<(), Self::Error> {
     Ok(())
   }
 
   #[inline]
-  fn write_select_fields(&self, _: &mut B) -> Result<(), Self::Error> {
+  fn write_select_fields(
+    &self,
+    _: &mut B,
+    _: impl SqlDialect,
+    _: EagerLoading<'_>,
+  ) -> Result<(), Self::Error> {
     Ok(())
   }
 
   #[inline]
-  fn write_select_orders_by(&self, _: &mut B) -> Result<(), Self::Error> {
+  fn write_select_orders_by(
+    &self,
+    _: &mut B,
+    _: impl SqlDialect,
+    _: EagerLoading<'_>,
+    _: &[OrderBy],
+  ) -> Result<(), Self::Error> {
     Ok(())
   }
 
   #[inline]
-  fn write_update(&self, _: &mut AuxNodes, _: &mut B) -> Result<(), Self::Error> {
+  fn write_update(
+    &self,
+    _: &mut AuxNodes,
+    _: &mut B,
+    _: impl SqlDialect,
+    _: &mut SqlWriterMode,
+  ) -> Result<(), Self::Error> {
     Ok(())
   }
 }
@@ -12,20 +12,20 @@ impl<E> NoTableEntity<E> {
   }
 }
 
-#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-runtime-tokio-native-tls"))]
-impl<B, E> crate::FromRowsSuffix<B> for NoTableEntity<E>
+impl<B, E, ROW> crate::FromRowsSuffix<B, ROW> for NoTableEntity<E>
 where
   B: cl_traits::String,
   E: From<crate::Error>,
+  ROW: crate::DbRow,
 {
   type Error = E;
 
   #[inline]
   fn from_rows_suffix(
-    _: &[sqlx_core::postgres::PgRow],
+    _: &[ROW],
     _: &mut B,
     _: crate::Suffix,
-    _: &sqlx_core::postgres::PgRow,
+    _: &ROW,
   ) -> Result<(usize, Self), Self::Error> {
     Ok((1, Self::new()))
   }
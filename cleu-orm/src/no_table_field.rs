@@ -1,4 +1,4 @@
-use crate::TableFields;
+use crate::{ColumnType, ColumnTypes, SqlDialect, SqlWriterMode, TableFields};
 use core::{array, marker::PhantomData};
 
 /// For entities that don't have fields beyond the primary key
@@ -13,6 +13,13 @@ impl<E> NoTableField<E> {
   }
 }
 
+impl<E> ColumnTypes for NoTableField<E> {
+  #[inline]
+  fn column_type(_: usize) -> Option<(ColumnType, bool)> {
+    None
+  }
+}
+
 impl<E> TableFields for NoTableField<E>
 where
   E: From<crate::Error>,
@@ -26,17 +33,29 @@ where
   }
 
   #[inline]
-  fn write_insert_values<BUFFER>(&self, _: &mut BUFFER) -> Result<(), Self::Error>
+  fn write_insert_values<BUFFER, D>(
+    &self,
+    _: &mut BUFFER,
+    _: D,
+    _: &mut SqlWriterMode,
+  ) -> Result<(), Self::Error>
   where
     BUFFER: cl_traits::String,
+    D: SqlDialect,
   {
     Ok(())
   }
 
   #[inline]
-  fn write_update_values<BUFFER>(&self, _: &mut BUFFER) -> Result<(), Self::Error>
+  fn write_update_values<BUFFER, D>(
+    &self,
+    _: &mut BUFFER,
+    _: D,
+    _: &mut SqlWriterMode,
+  ) -> Result<(), Self::Error>
   where
     BUFFER: cl_traits::String,
+    D: SqlDialect,
   {
     Ok(())
   }
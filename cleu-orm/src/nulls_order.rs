@@ -0,0 +1,8 @@
+/// Position of NULL values in an [crate::OrderBy]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NullsOrder {
+  /// NULLS FIRST
+  First,
+  /// NULLS LAST
+  Last,
+}
@@ -0,0 +1,46 @@
+use crate::ConflictAction;
+
+/// Turns INSERT operations into upserts. Applies to the root table as well as to every
+/// associated table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OnConflict<'any> {
+  action: ConflictAction,
+  targets: &'any [(&'static str, &'any [&'static str])],
+}
+
+impl<'any> OnConflict<'any> {
+  /// Conflicting rows are kept as they are
+  #[inline]
+  pub const fn do_nothing() -> Self {
+    Self { action: ConflictAction::DoNothing, targets: &[] }
+  }
+
+  /// Non-key fields of conflicting rows are updated
+  #[inline]
+  pub const fn do_update() -> Self {
+    Self { action: ConflictAction::DoUpdate, targets: &[] }
+  }
+
+  /// See [ConflictAction].
+  #[inline]
+  pub const fn action(&self) -> ConflictAction {
+    self.action
+  }
+
+  /// Custom conflict targets as pairs of table names and columns. Tables without a custom target
+  /// use their primary key.
+  #[inline]
+  pub const fn with_targets(
+    mut self,
+    targets: &'any [(&'static str, &'any [&'static str])],
+  ) -> Self {
+    self.targets = targets;
+    self
+  }
+
+  /// Custom conflict target of `table`, if any
+  #[inline]
+  pub fn target(&self, table: &str) -> Option<&'any [&'static str]> {
+    self.targets.iter().find(|elem| elem.0 == table).map(|elem| elem.1)
+  }
+}
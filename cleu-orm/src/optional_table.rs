@@ -0,0 +1,73 @@
+use crate::{Suffix, Table, TableDefs, TableStorage};
+use cl_traits::SingleTypeStorage;
+use core::fmt::{self, Debug, Formatter};
+
+/// Storage of zero or one table used by the [crate::TableAssociationWrapper]s of optional
+/// associations, i.e., entities that have an `Option` of another entity.
+///
+/// Absent tables are skipped by INSERT, UPDATE and DELETE operations. See
+/// [crate::seek_optional_entity] for the assembly of such entities.
+pub struct OptionalTable<'entity, TD>(pub Option<Table<'entity, TD>>)
+where
+  TD: TableDefs<'entity>;
+
+impl<'entity, TD> OptionalTable<'entity, TD>
+where
+  TD: TableDefs<'entity>,
+{
+  /// Creates a table of `suffix` that is filled with the fields of `entity`, if any.
+  #[inline]
+  pub fn from_entity(entity: Option<&'entity TD::Entity>, suffix: Suffix) -> Self {
+    Self(entity.map(|elem| {
+      let mut table = Table::new(suffix);
+      table.update_all_table_fields(elem);
+      table
+    }))
+  }
+}
+
+impl<'entity, TD> AsRef<[Table<'entity, TD>]> for OptionalTable<'entity, TD>
+where
+  TD: TableDefs<'entity>,
+{
+  #[inline]
+  fn as_ref(&self) -> &[Table<'entity, TD>] {
+    self.0.as_slice()
+  }
+}
+
+// Derived implementations would only require `TD: Debug`
+impl<'entity, TD> Debug for OptionalTable<'entity, TD>
+where
+  TD: TableDefs<'entity>,
+  Table<'entity, TD>: Debug,
+{
+  #[inline]
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.debug_tuple("OptionalTable").field(&self.0).finish()
+  }
+}
+
+impl<'entity, TD> Default for OptionalTable<'entity, TD>
+where
+  TD: TableDefs<'entity>,
+{
+  #[inline]
+  fn default() -> Self {
+    Self(None)
+  }
+}
+
+impl<'entity, TD> SingleTypeStorage for OptionalTable<'entity, TD>
+where
+  TD: TableDefs<'entity>,
+{
+  type Item = Table<'entity, TD>;
+}
+
+impl<'entity, TD> TableStorage for OptionalTable<'entity, TD>
+where
+  TD: TableDefs<'entity>,
+{
+  const IS_OPTIONAL: bool = true;
+}
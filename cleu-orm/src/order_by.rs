@@ -0,0 +1,67 @@
+use crate::{write_select_field, Column, NullsOrder, SelectOrderBy, SqlDialect};
+
+/// A single sort key of the `ORDER BY` clause of SELECT commands. Created by [Column::asc] or
+/// [Column::desc].
+///
+/// Keys are written right before the primary key of the table they belong to, which means that
+/// keys of associated tables only sort rows within the same parent entity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrderBy {
+  column: Column<()>,
+  direction: SelectOrderBy,
+  nulls: Option<NullsOrder>,
+}
+
+impl OrderBy {
+  /// NULL values come before non-NULL values
+  #[inline]
+  pub const fn nulls_first(mut self) -> Self {
+    self.nulls = Some(NullsOrder::First);
+    self
+  }
+
+  /// NULL values come after non-NULL values
+  #[inline]
+  pub const fn nulls_last(mut self) -> Self {
+    self.nulls = Some(NullsOrder::Last);
+    self
+  }
+
+  #[inline]
+  pub(crate) const fn new(column: Column<()>, direction: SelectOrderBy) -> Self {
+    Self { column, direction, nulls: None }
+  }
+
+  #[inline]
+  pub(crate) const fn column(&self) -> &Column<()> {
+    &self.column
+  }
+
+  #[inline]
+  pub(crate) fn write<B, D>(&self, buffer: &mut B, dialect: D) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+    D: SqlDialect,
+  {
+    let column = &self.column;
+    if let (Some(nulls), false) = (self.nulls, D::SUPPORTS_NULLS_ORDERING) {
+      write_select_field(buffer, dialect, column.table(), None, column.suffix(), column.field())?;
+      buffer.push(match nulls {
+        NullsOrder::First => " IS NULL DESC,",
+        NullsOrder::Last => " IS NULL ASC,",
+      })?;
+    }
+    write_select_field(buffer, dialect, column.table(), None, column.suffix(), column.field())?;
+    buffer.push(match self.direction {
+      SelectOrderBy::Ascending => " ASC",
+      SelectOrderBy::Descending => " DESC",
+    })?;
+    if let (Some(nulls), true) = (self.nulls, D::SUPPORTS_NULLS_ORDERING) {
+      buffer.push(match nulls {
+        NullsOrder::First => " NULLS FIRST",
+        NullsOrder::Last => " NULLS LAST",
+      })?;
+    }
+    Ok(())
+  }
+}
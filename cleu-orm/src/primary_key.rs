@@ -0,0 +1,103 @@
+use crate::{
+  write_sql_value, BindValue, ColumnTypes, SqlDialect, SqlType, SqlValue, SqlWriterMode,
+};
+use core::hash::Hash;
+
+/// Value of a primary key.
+///
+/// Single-column keys are represented by a [SqlValue] while composite keys are represented by a
+/// tuple of [SqlValue]s, one for each column of [crate::TableDefs::PRIMARY_KEY_NAMES] and in the
+/// same order.
+pub trait PrimaryKey: ColumnTypes + Copy + Hash {
+  /// Owned version of the value of the column at index `idx`. Out of bounds indices return
+  /// [BindValue::Null].
+  fn to_bind_value(&self, idx: usize) -> BindValue;
+
+  /// Writes the value of the column at index `idx`. See [write_sql_value].
+  fn write_value<B, D>(
+    &self,
+    buffer: &mut B,
+    dialect: D,
+    mode: &mut SqlWriterMode,
+    idx: usize,
+  ) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+    D: SqlDialect;
+}
+
+impl<T> PrimaryKey for T
+where
+  T: Copy + Hash + SqlType + SqlValue,
+{
+  #[inline]
+  fn to_bind_value(&self, idx: usize) -> BindValue {
+    if idx == 0 {
+      SqlValue::to_bind_value(self)
+    } else {
+      BindValue::Null
+    }
+  }
+
+  #[inline]
+  fn write_value<B, D>(
+    &self,
+    buffer: &mut B,
+    dialect: D,
+    mode: &mut SqlWriterMode,
+    idx: usize,
+  ) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+    D: SqlDialect,
+  {
+    if idx == 0 {
+      write_sql_value(buffer, dialect, mode, self)
+    } else {
+      write_sql_value(buffer, dialect, mode, &BindValue::Null)
+    }
+  }
+}
+
+macro_rules! tuple_impls {
+  ($( ($($idx:tt $T:ident)+) )+) => {
+    $(
+      impl<$($T),+> PrimaryKey for ($($T,)+)
+      where
+        $($T: Copy + Hash + SqlType + SqlValue,)+
+      {
+        #[inline]
+        fn to_bind_value(&self, idx: usize) -> BindValue {
+          match idx {
+            $($idx => self.$idx.to_bind_value(),)+
+            _ => BindValue::Null,
+          }
+        }
+
+        #[inline]
+        fn write_value<B, D>(
+          &self,
+          buffer: &mut B,
+          dialect: D,
+          mode: &mut SqlWriterMode,
+          idx: usize,
+        ) -> crate::Result<()>
+        where
+          B: cl_traits::String,
+          D: SqlDialect,
+        {
+          match idx {
+            $($idx => write_sql_value(buffer, dialect, mode, &self.$idx),)+
+            _ => write_sql_value(buffer, dialect, mode, &BindValue::Null),
+          }
+        }
+      }
+    )+
+  }
+}
+
+tuple_impls! {
+  (0 T0 1 T1)
+  (0 T0 1 T1 2 T2)
+  (0 T0 1 T1 2 T2 3 T3)
+}
@@ -0,0 +1,238 @@
+use crate::{
+  AssociationsSchema, ColumnSchema, ColumnType, ColumnTypes, ForeignKeySchema, SqlDialect,
+  SqlWriterMode, Table, TableAssociation, TableDefs, TableFields, TableSchema,
+};
+
+/// Tables of a graph of [TableDefs] and their relationships, used to write `CREATE TABLE`
+/// statements.
+///
+/// Foreign key columns that aren't fields are derived from each [TableAssociation]: callees
+/// receive the [TableAssociation::to_id] columns that reference the caller, callers of
+/// [TableAssociation::with_belongs_to] receive the [TableAssociation::from_id] columns that
+/// reference the callee and [TableAssociation::with_join_table] creates a table that references
+/// both sides. Their types are the same of the referenced columns and callers of optional
+/// belongs-to associations receive nullable columns.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Schema {
+  tables: Vec<TableSchema>,
+}
+
+impl Schema {
+  /// Creates an instance without tables
+  #[inline]
+  pub const fn new() -> Self {
+    Self { tables: Vec::new() }
+  }
+
+  /// Pushes the foreign key of `association` as well as the table reached by `guide` and all its
+  /// associations, if not already present. `is_optional` is [crate::TableStorage::IS_OPTIONAL]
+  /// of the associated tables.
+  #[inline]
+  pub fn push_associated<'entity, TD>(
+    &mut self,
+    association: &TableAssociation,
+    from_table: &'static str,
+    guide: &Table<'entity, TD>,
+    is_optional: bool,
+  ) -> crate::Result<()>
+  where
+    TD: TableDefs<'entity>,
+    TD::Associations: AssociationsSchema,
+    TD::Fields: ColumnTypes,
+    TD::PrimaryKeyValue: ColumnTypes,
+  {
+    let is_new = self.push_columns(guide);
+    self.push_association(association, from_table, is_optional, TD::TABLE_NAME)?;
+    if is_new {
+      guide.associations().push_schema(self, TD::TABLE_NAME)?;
+    }
+    Ok(())
+  }
+
+  /// Pushes the foreign key, or the join table, of `association`. Both `from_table` and
+  /// `to_table` must already be part of this instance. Foreign key columns of belongs-to
+  /// associations are nullable when `is_optional` is `true`.
+  #[inline]
+  pub fn push_association(
+    &mut self,
+    association: &TableAssociation,
+    from_table: &'static str,
+    is_optional: bool,
+    to_table: &'static str,
+  ) -> crate::Result<()> {
+    if let Some(join_table) = association.join_table() {
+      if self.table(join_table.name()).is_some() {
+        return Ok(());
+      }
+      let from_column = join_table.from_column();
+      let to_column = join_table.to_column();
+      let table = TableSchema::new(
+        vec![
+          ColumnSchema::new(
+            self.column_type(from_table, association.from_id())?,
+            false,
+            false,
+            from_column,
+          ),
+          ColumnSchema::new(
+            self.column_type(to_table, association.to_id())?,
+            false,
+            false,
+            to_column,
+          ),
+        ],
+        vec![
+          ForeignKeySchema::new(vec![from_column], vec![association.from_id()], from_table),
+          ForeignKeySchema::new(vec![to_column], vec![association.to_id()], to_table),
+        ],
+        join_table.name(),
+        vec![from_column, to_column],
+      );
+      self.tables.push(table);
+      return Ok(());
+    }
+    let (owner, referenced) =
+      if association.belongs_to() { (from_table, to_table) } else { (to_table, from_table) };
+    // Roots of self-referencing trees don't reference anything
+    let is_nullable = owner == referenced || (is_optional && association.belongs_to());
+    let mut columns = Vec::new();
+    let mut new_columns = Vec::new();
+    let mut referenced_columns = Vec::new();
+    for (from_id, to_id) in association.ids() {
+      let (column, referenced_column) =
+        if association.belongs_to() { (from_id, to_id) } else { (to_id, from_id) };
+      let column_type = self.column_type(referenced, referenced_column)?;
+      new_columns.push(ColumnSchema::new(column_type, false, is_nullable, column));
+      columns.push(column);
+      referenced_columns.push(referenced_column);
+    }
+    let foreign_key = ForeignKeySchema::new(columns, referenced_columns, referenced);
+    if let Some(table) = self.tables.iter_mut().find(|elem| elem.name() == owner) {
+      for column in new_columns {
+        if table.column(column.name()).is_none() {
+          table.columns_mut().push(column);
+        }
+      }
+      if !table.foreign_keys().contains(&foreign_key) {
+        table.foreign_keys_mut().push(foreign_key);
+      }
+      Ok(())
+    } else {
+      let column = foreign_key.columns().first().copied().unwrap_or_default();
+      Err(crate::Error::UnknownColumn(owner, column))
+    }
+  }
+
+  /// Pushes `table` and all tables reached by its associations, if not already present.
+  #[inline]
+  pub fn push_table<'entity, TD>(&mut self, table: &Table<'entity, TD>) -> crate::Result<()>
+  where
+    TD: TableDefs<'entity>,
+    TD::Associations: AssociationsSchema,
+    TD::Fields: ColumnTypes,
+    TD::PrimaryKeyValue: ColumnTypes,
+  {
+    if self.push_columns(table) {
+      table.associations().push_schema(self, TD::TABLE_NAME)?;
+    }
+    Ok(())
+  }
+
+  /// Table named `name`, if any
+  #[inline]
+  pub fn table(&self, name: &str) -> Option<&TableSchema> {
+    self.tables.iter().find(|elem| elem.name() == name)
+  }
+
+  /// All tables in the order they were pushed
+  #[inline]
+  pub fn tables(&self) -> &[TableSchema] {
+    &self.tables
+  }
+
+  /// All tables ordered in a way that referenced tables come before the tables that reference
+  /// them. Tables with circular references are yielded last in the order they were pushed.
+  #[inline]
+  pub fn tables_by_dependency(&self) -> Vec<&TableSchema> {
+    let mut rslt: Vec<&TableSchema> = Vec::with_capacity(self.tables.len());
+    loop {
+      let len = rslt.len();
+      for table in &self.tables {
+        let is_sorted = |name: &str| rslt.iter().any(|elem| elem.name() == name);
+        if is_sorted(table.name()) {
+          continue;
+        }
+        let has_dependencies = table.foreign_keys().iter().any(|elem| {
+          elem.referenced_table() != table.name() && !is_sorted(elem.referenced_table())
+        });
+        if !has_dependencies {
+          rslt.push(table);
+        }
+      }
+      if rslt.len() == len {
+        break;
+      }
+    }
+    for table in &self.tables {
+      if !rslt.iter().any(|elem| elem.name() == table.name()) {
+        rslt.push(table);
+      }
+    }
+    rslt
+  }
+
+  /// Writes a `CREATE TABLE` statement for each table. See [Self::tables_by_dependency].
+  #[inline]
+  pub fn write_create_tables<B, D>(
+    &self,
+    buffer: &mut B,
+    dialect: D,
+    mode: &mut SqlWriterMode,
+  ) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+    D: SqlDialect,
+  {
+    for table in self.tables_by_dependency() {
+      table.write_create_table(buffer, dialect, mode)?;
+    }
+    Ok(())
+  }
+
+  #[inline]
+  fn column_type(&self, table: &'static str, column: &'static str) -> crate::Result<ColumnType> {
+    self
+      .table(table)
+      .and_then(|elem| elem.column(column))
+      .map(ColumnSchema::column_type)
+      .ok_or(crate::Error::UnknownColumn(table, column))
+  }
+
+  // Returns `false` if `TD` is already present
+  #[inline]
+  fn push_columns<'entity, TD>(&mut self, table: &Table<'entity, TD>) -> bool
+  where
+    TD: TableDefs<'entity>,
+    TD::Fields: ColumnTypes,
+    TD::PrimaryKeyValue: ColumnTypes,
+  {
+    if self.table(TD::TABLE_NAME).is_some() {
+      return false;
+    }
+    let mut columns = Vec::new();
+    let is_generated = TD::PRIMARY_KEY_IS_GENERATED && TD::PRIMARY_KEY_NAMES.len() == 1;
+    for (idx, name) in TD::PRIMARY_KEY_NAMES.iter().enumerate() {
+      if let Some((column_type, _)) = TD::PrimaryKeyValue::column_type(idx) {
+        columns.push(ColumnSchema::new(column_type, is_generated, false, name));
+      }
+    }
+    for (idx, name) in table.fields().field_names().enumerate() {
+      if let Some((column_type, is_nullable)) = TD::Fields::column_type(idx) {
+        columns.push(ColumnSchema::new(column_type, false, is_nullable, name));
+      }
+    }
+    let primary_key = TD::PRIMARY_KEY_NAMES.to_vec();
+    self.tables.push(TableSchema::new(columns, Vec::new(), TD::TABLE_NAME, primary_key));
+    true
+  }
+}
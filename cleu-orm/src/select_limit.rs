@@ -1,8 +1,19 @@
 /// Sql select `LIMIT` clause
-#[derive(Clone, Copy, Debug)]
+///
+/// Everything but [SelectLimit::All] counts root entities instead of joined rows. In such cases,
+/// the WHERE clause only selects the root entities and their associations are loaded without
+/// being filtered. See [crate::Keyset] for keyset pagination.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum SelectLimit {
   /// LIMIT ALL
   All,
   /// LIMIT `n`
   Count(u32),
+  /// LIMIT `count` OFFSET `offset`
+  Offset {
+    /// Maximum number of root entities
+    count: u32,
+    /// Number of root entities to skip
+    offset: u32,
+  },
 }
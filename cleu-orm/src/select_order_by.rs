@@ -1,5 +1,5 @@
-/// Sql select `ORDER BY` clause
-#[derive(Clone, Copy, Debug)]
+/// Direction of an [crate::OrderBy]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum SelectOrderBy {
   /// ORDER BY ... ASC
   Ascending,
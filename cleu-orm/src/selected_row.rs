@@ -0,0 +1,65 @@
+use crate::{DbRow, DecodeColumn, Suffix};
+use std::sync::Arc;
+
+/// A [DbRow] returned by a SELECT operation that knows which associations were excluded by
+/// [crate::EagerLoading].
+///
+/// The columns of excluded associations aren't projected, so they are explicitly decoded as NULL
+/// values. Any other missing column is an error.
+#[derive(Debug)]
+pub struct SelectedRow<ROW> {
+  excluded: Arc<[(&'static str, Suffix)]>,
+  row: ROW,
+}
+
+impl<ROW> SelectedRow<ROW> {
+  /// `excluded` are the [crate::Table::table_ref]s of the excluded associations. Descendants of
+  /// excluded associations don't need to be present.
+  #[inline]
+  pub fn new(excluded: Arc<[(&'static str, Suffix)]>, row: ROW) -> Self {
+    Self { excluded, row }
+  }
+
+  /// Underlying row returned by the database
+  #[inline]
+  pub fn row(&self) -> &ROW {
+    &self.row
+  }
+
+  // Projected columns are named `{table}{suffix}__{field}`.
+  #[inline]
+  fn is_excluded_column(&self, column: &str) -> bool {
+    self.excluded.iter().any(|&(table, suffix)| {
+      let Some(rest) = column.strip_prefix(table) else {
+        return false;
+      };
+      let Some((column_suffix, _)) = rest.split_once("__") else {
+        return false;
+      };
+      column_suffix.parse::<Suffix>().is_ok_and(|elem| elem == suffix)
+    })
+  }
+}
+
+impl<ROW> DbRow for SelectedRow<ROW>
+where
+  ROW: DbRow,
+{
+  #[inline]
+  fn is_excluded(&self, table: &str, suffix: Suffix) -> bool {
+    self.excluded.contains(&(table, suffix))
+  }
+}
+
+impl<ROW, T> DecodeColumn<T> for SelectedRow<ROW>
+where
+  ROW: DecodeColumn<T>,
+{
+  #[inline]
+  fn decode_column(&self, column: &str) -> crate::Result<Option<T>> {
+    if self.is_excluded_column(column) {
+      return Ok(None);
+    }
+    self.row.decode_column(column)
+  }
+}
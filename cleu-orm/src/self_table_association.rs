@@ -0,0 +1,351 @@
+use crate::{
+  AssociationsSchema, AuxNodes, EagerLoading, FullTableAssociation, InsertOptions,
+  NoTableAssociation, OrderBy, PrimaryKey, Schema, SelectLimit, SqlDialect, SqlWriter,
+  SqlWriterMode, Suffix, Table, TableAssociation, TableAssociations, TableDefs,
+  TableSourceAssociation,
+};
+use core::{
+  fmt::{self, Debug, Formatter},
+  iter, option,
+};
+
+/// Association of a table with itself, e.g., categories with sub-categories or employees with
+/// managers. The other associations of such table are stored in [Self::others].
+///
+/// Self-referencing trees are loaded by SELECT operations with one JOIN per level so nodes that
+/// are more than [Self::max_depth] levels below the current table are left empty, like the ones
+/// excluded by [EagerLoading]. The other associations of loaded nodes aren't limited.
+/// INSERT, UPDATE and DELETE operations always visit all [Self::tables].
+pub struct SelfTableAssociation<
+  'entity,
+  TD,
+  A = NoTableAssociation<<TD as TableDefs<'entity>>::Error>,
+> where
+  TD: TableDefs<'entity>,
+{
+  association: TableAssociation,
+  max_depth: u32,
+  others: A,
+  suffix: Suffix,
+  tables: Vec<Table<'entity, TD>>,
+}
+
+impl<'entity, TD> SelfTableAssociation<'entity, TD>
+where
+  TD: TableDefs<'entity, Associations = Self>,
+{
+  /// Self-referencing table without other associations. See [Self::with_others].
+  #[inline]
+  pub const fn new(association: TableAssociation, max_depth: u32, suffix: Suffix) -> Self {
+    Self::with_others(association, max_depth, NoTableAssociation::new(), suffix)
+  }
+}
+
+impl<'entity, TD, A> SelfTableAssociation<'entity, TD, A>
+where
+  TD: TableDefs<'entity, Associations = Self>,
+{
+  /// `suffix` is the suffix of the associated tables, which is allocated like the suffix of
+  /// [crate::TableAssociationWrapper::guide], i.e., it must not be used by any of the tables of
+  /// `others`. A `max_depth` of zero is the same as one.
+  #[inline]
+  pub const fn with_others(
+    association: TableAssociation,
+    max_depth: u32,
+    others: A,
+    suffix: Suffix,
+  ) -> Self {
+    let max_depth = if max_depth == 0 { 1 } else { max_depth };
+    Self { association, max_depth, others, suffix, tables: Vec::new() }
+  }
+
+  /// See [TableAssociation].
+  #[inline]
+  pub const fn association(&self) -> &TableAssociation {
+    &self.association
+  }
+
+  /// Number of levels below the current table that are loaded by SELECT operations
+  #[inline]
+  pub const fn max_depth(&self) -> u32 {
+    self.max_depth
+  }
+
+  /// Associations of the current table with other tables
+  #[inline]
+  pub const fn others(&self) -> &A {
+    &self.others
+  }
+
+  /// Mutable version of [Self::others]
+  #[inline]
+  pub fn others_mut(&mut self) -> &mut A {
+    &mut self.others
+  }
+
+  /// A storage of zero, one or many tables used for INSERT and UPDATE operations
+  #[inline]
+  pub fn tables(&self) -> &[Table<'entity, TD>] {
+    &self.tables
+  }
+
+  /// Mutable version of [Self::tables]
+  #[inline]
+  pub fn tables_mut(&mut self) -> &mut Vec<Table<'entity, TD>> {
+    &mut self.tables
+  }
+
+  // Used to construct SELECT operations. Created on demand because an eagerly created guide would
+  // also have a guide, which would also have a guide and so on. The recursion is stopped by
+  // `EagerLoading::limit_self_depth`.
+  #[inline]
+  fn guide(&self) -> Table<'entity, TD> {
+    Table::new(self.suffix)
+  }
+
+  #[inline]
+  fn owns_tables(&self) -> bool {
+    !self.association.belongs_to() && self.association.join_table().is_none()
+  }
+
+  #[inline]
+  fn table() -> &'static str {
+    TD::TABLE_NAME_ALIAS.unwrap_or(TD::TABLE_NAME)
+  }
+}
+
+impl<'entity, TD, A> AssociationsSchema for SelfTableAssociation<'entity, TD, A>
+where
+  A: AssociationsSchema,
+  TD: TableDefs<'entity>,
+{
+  #[inline]
+  fn push_schema(&self, schema: &mut Schema, from_table: &'static str) -> crate::Result<()> {
+    schema.push_association(&self.association, from_table, false, TD::TABLE_NAME)?;
+    self.others.push_schema(schema, from_table)
+  }
+}
+
+// Derived implementations would only require `TD: Debug` and bounding `Table<'entity, TD>: Debug`
+// would require this implementation itself.
+impl<'entity, TD, A> Debug for SelfTableAssociation<'entity, TD, A>
+where
+  A: Debug,
+  TD: Debug + TableDefs<'entity, Associations = Self>,
+  TD::Error: Debug,
+  TD::Fields: Debug,
+  TD::PrimaryKeyValue: Debug,
+{
+  #[inline]
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.debug_struct("SelfTableAssociation")
+      .field("association", &self.association)
+      .field("max_depth", &self.max_depth)
+      .field("others", &self.others)
+      .field("suffix", &self.suffix)
+      .field("tables", &self.tables)
+      .finish()
+  }
+}
+
+impl<'entity, TD, A> TableAssociations for SelfTableAssociation<'entity, TD, A>
+where
+  A: TableAssociations,
+  TD: TableDefs<'entity>,
+{
+  type FullTableAssociations =
+    iter::Chain<option::IntoIter<FullTableAssociation>, A::FullTableAssociations>;
+
+  #[inline]
+  fn full_associations(&self) -> Self::FullTableAssociations {
+    let full_association = FullTableAssociation::new(
+      self.association,
+      TD::TABLE_NAME,
+      TD::TABLE_NAME_ALIAS,
+      self.suffix,
+    );
+    Some(full_association).into_iter().chain(self.others.full_associations())
+  }
+
+  #[inline]
+  fn has_dependent_tables(&self) -> bool {
+    (!self.association.belongs_to() && !self.tables.is_empty())
+      || self.others.has_dependent_tables()
+  }
+}
+
+impl<'entity, A, B, TD> SqlWriter<B> for SelfTableAssociation<'entity, TD, A>
+where
+  A: SqlWriter<B, Error = TD::Error>,
+  B: cl_traits::String,
+  TD: TableDefs<'entity, Associations = Self>,
+  TD::Error: From<crate::Error>,
+{
+  type Error = TD::Error;
+
+  #[inline]
+  fn push_excluded_table_refs(
+    &self,
+    eager_loading: EagerLoading<'_>,
+    excluded: &mut Vec<(&'static str, Suffix)>,
+  ) {
+    let guide = self.guide();
+    let eager_loading = eager_loading.limit_self_depth(Self::table(), self.max_depth);
+    if eager_loading.includes(guide.table_ref()) {
+      SqlWriter::<B>::push_excluded_table_refs(&guide, eager_loading, excluded);
+    } else {
+      excluded.push(guide.table_ref());
+    }
+    self.others.push_excluded_table_refs(eager_loading, excluded);
+  }
+
+  #[inline]
+  fn register_insert_parents(&self, aux: &mut AuxNodes) -> Result<(), Self::Error> {
+    if self.owns_tables() {
+      for elem in &self.tables {
+        SqlWriter::<B>::register_insert_parents(elem, aux)?;
+      }
+    }
+    self.others.register_insert_parents(aux)
+  }
+
+  #[inline]
+  fn write_delete(
+    &self,
+    aux: &mut AuxNodes,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    mode: &mut SqlWriterMode,
+  ) -> Result<(), Self::Error> {
+    if self.owns_tables() {
+      for elem in &self.tables {
+        elem.write_delete(aux, buffer, dialect, mode)?;
+      }
+    }
+    self.others.write_delete(aux, buffer, dialect, mode)
+  }
+
+  #[inline]
+  fn write_insert<'value, V>(
+    &self,
+    aux: &mut AuxNodes,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    mode: &mut SqlWriterMode,
+    options: InsertOptions<'_>,
+    tsa: &mut Option<TableSourceAssociation<'value, V>>,
+  ) -> Result<(), Self::Error>
+  where
+    V: PrimaryKey,
+  {
+    if !self.association.belongs_to() {
+      if let Some(ref mut elem) = tsa.as_mut() {
+        *elem.source_association_mut() = self.association;
+      }
+      Table::write_insert_siblings(aux, buffer, dialect, mode, options, &self.tables, tsa)?;
+    }
+    self.others.write_insert(aux, buffer, dialect, mode, options, tsa)
+  }
+
+  #[inline]
+  fn write_insert_referenced(
+    &self,
+    aux: &mut AuxNodes,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    hash: u64,
+    mode: &mut SqlWriterMode,
+    options: InsertOptions<'_>,
+  ) -> Result<(), Self::Error> {
+    if self.association.belongs_to() {
+      for elem in &self.tables {
+        Table::write_insert_referenced(
+          aux,
+          &self.association,
+          buffer,
+          dialect,
+          hash,
+          mode,
+          options,
+          elem,
+        )?;
+      }
+    }
+    self.others.write_insert_referenced(aux, buffer, dialect, hash, mode, options)
+  }
+
+  #[inline]
+  fn write_select(
+    &self,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    eager_loading: EagerLoading<'_>,
+    orders_by: &[OrderBy],
+    limit: SelectLimit,
+    where_cb: &mut impl FnMut(&mut B) -> Result<(), Self::Error>,
+  ) -> Result<(), Self::Error> {
+    let eager_loading = eager_loading.limit_self_depth(Self::table(), self.max_depth);
+    self.guide().write_select(buffer, dialect, eager_loading, orders_by, limit, where_cb)?;
+    self.others.write_select(buffer, dialect, eager_loading, orders_by, limit, where_cb)
+  }
+
+  #[inline]
+  fn write_select_associations(
+    &self,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    eager_loading: EagerLoading<'_>,
+  ) -> Result<(), Self::Error> {
+    let guide = self.guide();
+    let eager_loading = eager_loading.limit_self_depth(Self::table(), self.max_depth);
+    if eager_loading.includes(guide.table_ref()) {
+      guide.write_select_associations(buffer, dialect, eager_loading)?;
+    }
+    self.others.write_select_associations(buffer, dialect, eager_loading)
+  }
+
+  #[inline]
+  fn write_select_fields(
+    &self,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    eager_loading: EagerLoading<'_>,
+  ) -> Result<(), Self::Error> {
+    let guide = self.guide();
+    let eager_loading = eager_loading.limit_self_depth(Self::table(), self.max_depth);
+    if eager_loading.includes(guide.table_ref()) {
+      guide.write_select_fields(buffer, dialect, eager_loading)?;
+    }
+    self.others.write_select_fields(buffer, dialect, eager_loading)
+  }
+
+  #[inline]
+  fn write_select_orders_by(
+    &self,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    eager_loading: EagerLoading<'_>,
+    orders_by: &[OrderBy],
+  ) -> Result<(), Self::Error> {
+    let guide = self.guide();
+    let eager_loading = eager_loading.limit_self_depth(Self::table(), self.max_depth);
+    if eager_loading.includes(guide.table_ref()) {
+      guide.write_select_orders_by(buffer, dialect, eager_loading, orders_by)?;
+    }
+    self.others.write_select_orders_by(buffer, dialect, eager_loading, orders_by)
+  }
+
+  #[inline]
+  fn write_update(
+    &self,
+    aux: &mut AuxNodes,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    mode: &mut SqlWriterMode,
+  ) -> Result<(), Self::Error> {
+    for elem in &self.tables {
+      elem.write_update(aux, buffer, dialect, mode)?;
+    }
+    self.others.write_update(aux, buffer, dialect, mode)
+  }
+}
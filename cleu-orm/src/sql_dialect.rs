@@ -0,0 +1,117 @@
+mod my_sql;
+mod postgres;
+mod sqlite;
+
+use crate::{write_names, ColumnSchema, ColumnType, ConflictAction, SelectLimit};
+pub use my_sql::*;
+pub use postgres::*;
+pub use sqlite::*;
+
+/// SQL syntax that differs between database vendors.
+///
+/// Consulted by [crate::SqlWriter] and [crate::SqlValue] when writing identifiers, literals,
+/// placeholders and clauses that aren't part of the SQL standard.
+pub trait SqlDialect: Copy {
+  /// Character used to quote identifiers
+  const IDENTIFIER_QUOTE: char;
+  /// Character that ends a statement
+  const STATEMENT_TERMINATOR: char = ';';
+  /// If `NULLS FIRST` and `NULLS LAST` are understood. Otherwise, they are emulated with an
+  /// additional `IS NULL` sort key.
+  const SUPPORTS_NULLS_ORDERING: bool = true;
+  /// If INSERT operations can return database-generated primary keys with `RETURNING`
+  const SUPPORTS_RETURNING: bool = true;
+  /// If the rows returned by a multi-row INSERT follow the order of its `VALUES`, which allows
+  /// generated keys to be mapped back to their rows
+  const RETURNING_FOLLOWS_VALUES: bool = false;
+
+  /// Writes the clauses, after `ALTER TABLE {table} `, that change the type, the nullability and
+  /// the generation of an existing column of `table` to the ones of `column`. `was_generated`
+  /// tells if the values of the existing column are generated by the database.
+  fn write_alter_column<B>(
+    self,
+    buffer: &mut B,
+    column: &ColumnSchema,
+    table: &'static str,
+    was_generated: bool,
+  ) -> crate::Result<()>
+  where
+    B: cl_traits::String;
+
+  /// Writes a boolean literal
+  fn write_bool<B>(self, buffer: &mut B, value: bool) -> crate::Result<()>
+  where
+    B: cl_traits::String;
+
+  /// Writes a literal of raw bytes
+  fn write_bytes<B>(self, buffer: &mut B, value: &[u8]) -> crate::Result<()>
+  where
+    B: cl_traits::String;
+
+  /// Writes the SQL type of a column in `CREATE TABLE` statements
+  fn write_column_type<B>(self, buffer: &mut B, column_type: ColumnType) -> crate::Result<()>
+  where
+    B: cl_traits::String;
+
+  /// Writes the attribute, including its leading space, that makes the database generate the
+  /// values of a primary key column of type `column_type`
+  fn write_generated<B>(self, buffer: &mut B, column_type: ColumnType) -> crate::Result<()>
+  where
+    B: cl_traits::String;
+
+  /// Writes the `LIMIT` clause, including its leading space, if `limit` restricts anything
+  fn write_limit<B>(self, buffer: &mut B, limit: SelectLimit) -> crate::Result<()>
+  where
+    B: cl_traits::String;
+
+  /// Writes the upsert clause, including its leading space, of an INSERT. `fields` are the
+  /// non-key columns replaced by [ConflictAction::DoUpdate].
+  #[inline]
+  fn write_on_conflict<B>(
+    self,
+    buffer: &mut B,
+    action: ConflictAction,
+    target: &[&str],
+    fields: impl Iterator<Item = &'static str>,
+  ) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    buffer.push(" ON CONFLICT (")?;
+    write_names(buffer, self, target)?;
+    let q = Self::IDENTIFIER_QUOTE;
+    let mut fields = fields.peekable();
+    // `DO UPDATE` requires at least one column
+    if let (ConflictAction::DoUpdate, Some(_)) = (action, fields.peek()) {
+      buffer.push(") DO UPDATE SET ")?;
+      for (idx, field) in fields.enumerate() {
+        let comma = if idx == 0 { "" } else { "," };
+        buffer.write_fmt(format_args!("{comma}{q}{field}{q}=EXCLUDED.{q}{field}{q}"))?;
+      }
+    } else {
+      buffer.push(") DO NOTHING")?;
+    }
+    Ok(())
+  }
+
+  /// Writes the placeholder of the `n`-th (1-based) argument of a statement
+  fn write_placeholder<B>(self, buffer: &mut B, n: usize) -> crate::Result<()>
+  where
+    B: cl_traits::String;
+
+  /// Writes a quoted and escaped string literal
+  fn write_str<B>(self, buffer: &mut B, value: &str) -> crate::Result<()>
+  where
+    B: cl_traits::String;
+
+  /// Writes a UUID literal. Defaults to the 16 raw bytes of `value`, which is how UUIDs are stored
+  /// by databases without a native type.
+  #[cfg(feature = "uuid")]
+  #[inline]
+  fn write_uuid<B>(self, buffer: &mut B, value: uuid::Uuid) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    self.write_bytes(buffer, value.as_bytes())
+  }
+}
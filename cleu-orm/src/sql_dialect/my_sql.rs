@@ -0,0 +1,144 @@
+use crate::{
+  write_hex, write_str_literal, ColumnSchema, ColumnType, ConflictAction, SelectLimit, SqlDialect,
+};
+
+/// MySQL and MariaDB
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MySqlDialect;
+
+impl SqlDialect for MySqlDialect {
+  const IDENTIFIER_QUOTE: char = '`';
+  const SUPPORTS_NULLS_ORDERING: bool = false;
+  const SUPPORTS_RETURNING: bool = false;
+
+  /// Columns are redefined as a whole with `MODIFY COLUMN`, including `AUTO_INCREMENT`.
+  #[inline]
+  fn write_alter_column<B>(
+    self,
+    buffer: &mut B,
+    column: &ColumnSchema,
+    _: &'static str,
+    _: bool,
+  ) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    buffer.push("MODIFY COLUMN ")?;
+    column.write(buffer, self)
+  }
+
+  #[inline]
+  fn write_bool<B>(self, buffer: &mut B, value: bool) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    buffer.push(if value { "TRUE" } else { "FALSE" })?;
+    Ok(())
+  }
+
+  #[inline]
+  fn write_bytes<B>(self, buffer: &mut B, value: &[u8]) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    buffer.push("X'")?;
+    write_hex(buffer, value)?;
+    buffer.push("'")?;
+    Ok(())
+  }
+
+  /// Text is stored as `VARCHAR(255)` because `TEXT` columns can't be part of keys without a
+  /// prefix length.
+  #[inline]
+  fn write_column_type<B>(self, buffer: &mut B, column_type: ColumnType) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    buffer.push(match column_type {
+      ColumnType::Bool => "BOOLEAN",
+      ColumnType::Bytes => "BLOB",
+      #[cfg(feature = "rust_decimal")]
+      ColumnType::Decimal => "DECIMAL(65,30)",
+      ColumnType::I32 => "INT",
+      ColumnType::I64 => "BIGINT",
+      ColumnType::String => "VARCHAR(255)",
+      ColumnType::U64 => "BIGINT UNSIGNED",
+      #[cfg(feature = "uuid")]
+      ColumnType::Uuid => "BINARY(16)",
+    })?;
+    Ok(())
+  }
+
+  #[inline]
+  fn write_generated<B>(self, buffer: &mut B, column_type: ColumnType) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    match column_type {
+      ColumnType::I32 | ColumnType::I64 | ColumnType::U64 => buffer.push(" AUTO_INCREMENT")?,
+      _ => return Err(crate::Error::UnsupportedGeneratedKey(column_type)),
+    }
+    Ok(())
+  }
+
+  #[inline]
+  fn write_limit<B>(self, buffer: &mut B, limit: SelectLimit) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    match limit {
+      SelectLimit::All => {}
+      SelectLimit::Count(n) => buffer.write_fmt(format_args!(" LIMIT {n}"))?,
+      SelectLimit::Offset { count, offset } => {
+        buffer.write_fmt(format_args!(" LIMIT {count} OFFSET {offset}"))?
+      }
+    }
+    Ok(())
+  }
+
+  /// Conflicts are detected with every unique index so `target` is ignored. `DO NOTHING` is
+  /// emulated by assigning the first column of `target` to itself.
+  #[inline]
+  fn write_on_conflict<B>(
+    self,
+    buffer: &mut B,
+    action: ConflictAction,
+    target: &[&str],
+    fields: impl Iterator<Item = &'static str>,
+  ) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    let q = Self::IDENTIFIER_QUOTE;
+    buffer.push(" ON DUPLICATE KEY UPDATE ")?;
+    let mut fields = fields.peekable();
+    if let (ConflictAction::DoUpdate, Some(_)) = (action, fields.peek()) {
+      for (idx, field) in fields.enumerate() {
+        let comma = if idx == 0 { "" } else { "," };
+        buffer.write_fmt(format_args!("{comma}{q}{field}{q}=VALUES({q}{field}{q})"))?;
+      }
+    } else if let Some(column) = target.first() {
+      buffer.write_fmt(format_args!("{q}{column}{q}={q}{column}{q}"))?;
+    }
+    Ok(())
+  }
+
+  #[inline]
+  fn write_placeholder<B>(self, buffer: &mut B, _: usize) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    buffer.push("?")?;
+    Ok(())
+  }
+
+  /// Backslashes are escape characters unless `NO_BACKSLASH_ESCAPES` is enabled, which is not the
+  /// default, so both single quotes and backslashes are doubled.
+  #[inline]
+  fn write_str<B>(self, buffer: &mut B, value: &str) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    write_str_literal(buffer, "'", value, true)
+  }
+}
@@ -0,0 +1,144 @@
+use crate::{write_hex, write_str_literal, ColumnSchema, ColumnType, SelectLimit, SqlDialect};
+
+/// PostgreSQL
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PostgresDialect;
+
+impl SqlDialect for PostgresDialect {
+  const IDENTIFIER_QUOTE: char = '"';
+  const RETURNING_FOLLOWS_VALUES: bool = true;
+
+  /// Identities are added or dropped according to `was_generated`. Added identities start at 1
+  /// so columns that already have values must also be restarted with `RESTART WITH`.
+  #[inline]
+  fn write_alter_column<B>(
+    self,
+    buffer: &mut B,
+    column: &ColumnSchema,
+    _: &'static str,
+    was_generated: bool,
+  ) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    let name = column.name();
+    let q = Self::IDENTIFIER_QUOTE;
+    buffer.write_fmt(format_args!("ALTER COLUMN {q}{name}{q} TYPE "))?;
+    self.write_column_type(buffer, column.column_type())?;
+    let nullability = if column.is_nullable() { "DROP" } else { "SET" };
+    buffer.write_fmt(format_args!(",ALTER COLUMN {q}{name}{q} {nullability} NOT NULL"))?;
+    match (was_generated, column.is_generated()) {
+      (false, true) => {
+        buffer.write_fmt(format_args!(",ALTER COLUMN {q}{name}{q} ADD"))?;
+        self.write_generated(buffer, column.column_type())?;
+      }
+      (true, false) => {
+        buffer.write_fmt(format_args!(",ALTER COLUMN {q}{name}{q} DROP IDENTITY"))?
+      }
+      _ => {}
+    }
+    Ok(())
+  }
+
+  #[inline]
+  fn write_bool<B>(self, buffer: &mut B, value: bool) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    buffer.write_fmt(format_args!("'{value}'"))?;
+    Ok(())
+  }
+
+  /// Like [Self::write_str], the `E''` syntax makes the output independent of
+  /// `standard_conforming_strings`.
+  #[inline]
+  fn write_bytes<B>(self, buffer: &mut B, value: &[u8]) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    buffer.push("E'\\\\x")?;
+    write_hex(buffer, value)?;
+    buffer.push("'")?;
+    Ok(())
+  }
+
+  /// Unsigned 64-bit integers don't fit into `BIGINT` so they are stored as `NUMERIC(20,0)`.
+  /// Types are written the same way they are reported by `format_type`.
+  #[inline]
+  fn write_column_type<B>(self, buffer: &mut B, column_type: ColumnType) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    buffer.push(match column_type {
+      ColumnType::Bool => "BOOLEAN",
+      ColumnType::Bytes => "BYTEA",
+      #[cfg(feature = "rust_decimal")]
+      ColumnType::Decimal => "NUMERIC",
+      ColumnType::I32 => "INTEGER",
+      ColumnType::I64 => "BIGINT",
+      ColumnType::String => "TEXT",
+      ColumnType::U64 => "NUMERIC(20,0)",
+      #[cfg(feature = "uuid")]
+      ColumnType::Uuid => "UUID",
+    })?;
+    Ok(())
+  }
+
+  /// Only integers can be identity columns.
+  #[inline]
+  fn write_generated<B>(self, buffer: &mut B, column_type: ColumnType) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    match column_type {
+      ColumnType::I32 | ColumnType::I64 => buffer.push(" GENERATED BY DEFAULT AS IDENTITY")?,
+      _ => return Err(crate::Error::UnsupportedGeneratedKey(column_type)),
+    }
+    Ok(())
+  }
+
+  #[inline]
+  fn write_limit<B>(self, buffer: &mut B, limit: SelectLimit) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    match limit {
+      SelectLimit::All => buffer.push(" LIMIT ALL")?,
+      SelectLimit::Count(n) => buffer.write_fmt(format_args!(" LIMIT {n}"))?,
+      SelectLimit::Offset { count, offset } => {
+        buffer.write_fmt(format_args!(" LIMIT {count} OFFSET {offset}"))?
+      }
+    }
+    Ok(())
+  }
+
+  #[inline]
+  fn write_placeholder<B>(self, buffer: &mut B, n: usize) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    buffer.write_fmt(format_args!("${n}"))?;
+    Ok(())
+  }
+
+  /// Single quotes and backslashes are doubled and the `E''` syntax is used when backslashes are
+  /// present, which makes the output independent of `standard_conforming_strings`.
+  #[inline]
+  fn write_str<B>(self, buffer: &mut B, value: &str) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    let has_backslash = value.contains('\\');
+    write_str_literal(buffer, if has_backslash { "E'" } else { "'" }, value, has_backslash)
+  }
+
+  #[cfg(feature = "uuid")]
+  #[inline]
+  fn write_uuid<B>(self, buffer: &mut B, value: uuid::Uuid) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    buffer.write_fmt(format_args!("'{value}'"))?;
+    Ok(())
+  }
+}
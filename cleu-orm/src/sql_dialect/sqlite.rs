@@ -0,0 +1,113 @@
+use crate::{write_hex, write_str_literal, ColumnSchema, ColumnType, SelectLimit, SqlDialect};
+
+/// SQLite
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SqliteDialect;
+
+impl SqlDialect for SqliteDialect {
+  const IDENTIFIER_QUOTE: char = '"';
+
+  /// Existing columns can't be modified so an error is returned. The table must be rebuilt
+  /// instead.
+  #[inline]
+  fn write_alter_column<B>(
+    self,
+    _: &mut B,
+    column: &ColumnSchema,
+    table: &'static str,
+    _: bool,
+  ) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    Err(crate::Error::UnsupportedAlterColumn(table, column.name()))
+  }
+
+  #[inline]
+  fn write_bool<B>(self, buffer: &mut B, value: bool) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    buffer.push(if value { "1" } else { "0" })?;
+    Ok(())
+  }
+
+  #[inline]
+  fn write_bytes<B>(self, buffer: &mut B, value: &[u8]) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    buffer.push("X'")?;
+    write_hex(buffer, value)?;
+    buffer.push("'")?;
+    Ok(())
+  }
+
+  /// Integers of all sizes share the same storage class and UUIDs are stored as raw bytes.
+  #[inline]
+  fn write_column_type<B>(self, buffer: &mut B, column_type: ColumnType) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    buffer.push(match column_type {
+      ColumnType::Bool => "BOOLEAN",
+      ColumnType::Bytes => "BLOB",
+      #[cfg(feature = "rust_decimal")]
+      ColumnType::Decimal => "TEXT",
+      ColumnType::I32 => "INTEGER",
+      ColumnType::I64 => "INTEGER",
+      ColumnType::String => "TEXT",
+      ColumnType::U64 => "INTEGER",
+      #[cfg(feature = "uuid")]
+      ColumnType::Uuid => "BLOB",
+    })?;
+    Ok(())
+  }
+
+  /// Single-column `INTEGER` primary keys are aliases of `ROWID`, which is already generated, so
+  /// nothing is written. `AUTOINCREMENT` isn't used because it can't be declared along a
+  /// `PRIMARY KEY` table constraint.
+  #[inline]
+  fn write_generated<B>(self, _: &mut B, column_type: ColumnType) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    match column_type {
+      ColumnType::I32 | ColumnType::I64 | ColumnType::U64 => Ok(()),
+      _ => Err(crate::Error::UnsupportedGeneratedKey(column_type)),
+    }
+  }
+
+  #[inline]
+  fn write_limit<B>(self, buffer: &mut B, limit: SelectLimit) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    match limit {
+      SelectLimit::All => {}
+      SelectLimit::Count(n) => buffer.write_fmt(format_args!(" LIMIT {n}"))?,
+      SelectLimit::Offset { count, offset } => {
+        buffer.write_fmt(format_args!(" LIMIT {count} OFFSET {offset}"))?
+      }
+    }
+    Ok(())
+  }
+
+  #[inline]
+  fn write_placeholder<B>(self, buffer: &mut B, n: usize) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    buffer.write_fmt(format_args!("?{n}"))?;
+    Ok(())
+  }
+
+  /// Backslashes have no special meaning, only single quotes are doubled.
+  #[inline]
+  fn write_str<B>(self, buffer: &mut B, value: &str) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+  {
+    write_str_literal(buffer, "'", value, false)
+  }
+}
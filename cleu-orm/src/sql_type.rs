@@ -0,0 +1,50 @@
+use crate::ColumnType;
+
+/// SQL column type of a [crate::SqlValue], used to write `CREATE TABLE` statements.
+pub trait SqlType {
+  /// See [ColumnType]
+  const COLUMN_TYPE: ColumnType;
+  /// If the column accepts NULL values
+  const IS_NULLABLE: bool = false;
+}
+
+impl<T> SqlType for &'_ T
+where
+  T: SqlType + ?Sized,
+{
+  const COLUMN_TYPE: ColumnType = T::COLUMN_TYPE;
+  const IS_NULLABLE: bool = T::IS_NULLABLE;
+}
+
+impl<T> SqlType for Option<T>
+where
+  T: SqlType,
+{
+  const COLUMN_TYPE: ColumnType = T::COLUMN_TYPE;
+  const IS_NULLABLE: bool = true;
+}
+
+macro_rules! impl_column_type {
+  ($ty:ty, $column_type:expr $(, $($bounds:tt)+)?) => {
+    impl<$($($bounds)+)?> SqlType for $ty {
+      const COLUMN_TYPE: ColumnType = $column_type;
+    }
+  }
+}
+
+impl_column_type!([u8], ColumnType::Bytes);
+impl_column_type!(Vec<u8>, ColumnType::Bytes);
+impl_column_type!(bool, ColumnType::Bool);
+impl_column_type!(i32, ColumnType::I32);
+impl_column_type!(i64, ColumnType::I64);
+impl_column_type!(u32, ColumnType::I64);
+impl_column_type!(u64, ColumnType::U64);
+impl_column_type!(str, ColumnType::String);
+impl_column_type!(String, ColumnType::String);
+
+#[cfg(feature = "arrayvec")]
+impl_column_type!(arrayvec::ArrayString<N>, ColumnType::String, const N: usize);
+#[cfg(feature = "rust_decimal")]
+impl_column_type!(rust_decimal::Decimal, ColumnType::Decimal);
+#[cfg(feature = "uuid")]
+impl_column_type!(uuid::Uuid, ColumnType::Uuid);
@@ -1,11 +1,15 @@
-use crate::{buffer_try_push_str, buffer_write_fmt};
+use crate::{buffer_try_push_str, buffer_write_fmt, BindValue, SqlDialect};
 
 /// Raw SQL representation of a type
 pub trait SqlValue {
+  /// Owned version used by [crate::SqlWriterMode::Parameterized]
+  fn to_bind_value(&self) -> BindValue;
+
   /// See [SqlValue]
-  fn write<B>(&self, buffer: &mut B) -> crate::Result<()>
+  fn write<B, D>(&self, buffer: &mut B, dialect: D) -> crate::Result<()>
   where
-    B: cl_traits::String;
+    B: cl_traits::String,
+    D: SqlDialect;
 }
 
 impl<T> SqlValue for &'_ T
@@ -13,11 +17,17 @@ where
   T: SqlValue,
 {
   #[inline]
-  fn write<B>(&self, buffer: &mut B) -> crate::Result<()>
+  fn to_bind_value(&self) -> BindValue {
+    (**self).to_bind_value()
+  }
+
+  #[inline]
+  fn write<B, D>(&self, buffer: &mut B, dialect: D) -> crate::Result<()>
   where
     B: cl_traits::String,
+    D: SqlDialect,
   {
-    (**self).write(buffer)
+    (**self).write(buffer, dialect)
   }
 }
 
@@ -26,25 +36,117 @@ where
   T: SqlValue,
 {
   #[inline]
-  fn write<B>(&self, buffer: &mut B) -> crate::Result<()>
+  fn to_bind_value(&self) -> BindValue {
+    if let Some(ref elem) = *self {
+      elem.to_bind_value()
+    } else {
+      BindValue::Null
+    }
+  }
+
+  #[inline]
+  fn write<B, D>(&self, buffer: &mut B, dialect: D) -> crate::Result<()>
   where
     B: cl_traits::String,
+    D: SqlDialect,
   {
     if let Some(ref elem) = *self {
-      elem.write(buffer)
+      elem.write(buffer, dialect)
     } else {
       buffer_try_push_str(buffer, "null")
     }
   }
 }
 
+impl SqlValue for &'_ [u8] {
+  #[inline]
+  fn to_bind_value(&self) -> BindValue {
+    BindValue::Bytes(self.to_vec())
+  }
+
+  #[inline]
+  fn write<B, D>(&self, buffer: &mut B, dialect: D) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+    D: SqlDialect,
+  {
+    dialect.write_bytes(buffer, self)
+  }
+}
+
+impl SqlValue for Vec<u8> {
+  #[inline]
+  fn to_bind_value(&self) -> BindValue {
+    BindValue::Bytes(self.clone())
+  }
+
+  #[inline]
+  fn write<B, D>(&self, buffer: &mut B, dialect: D) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+    D: SqlDialect,
+  {
+    self.as_slice().write(buffer, dialect)
+  }
+}
+
+impl SqlValue for bool {
+  #[inline]
+  fn to_bind_value(&self) -> BindValue {
+    BindValue::Bool(*self)
+  }
+
+  #[inline]
+  fn write<B, D>(&self, buffer: &mut B, dialect: D) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+    D: SqlDialect,
+  {
+    dialect.write_bool(buffer, *self)
+  }
+}
+
+impl SqlValue for BindValue {
+  #[inline]
+  fn to_bind_value(&self) -> BindValue {
+    self.clone()
+  }
+
+  #[inline]
+  fn write<B, D>(&self, buffer: &mut B, dialect: D) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+    D: SqlDialect,
+  {
+    match *self {
+      BindValue::Bool(elem) => elem.write(buffer, dialect),
+      BindValue::Bytes(ref elem) => elem.write(buffer, dialect),
+      #[cfg(feature = "rust_decimal")]
+      BindValue::Decimal(elem) => elem.write(buffer, dialect),
+      BindValue::I32(elem) => elem.write(buffer, dialect),
+      BindValue::I64(elem) => elem.write(buffer, dialect),
+      BindValue::Null => buffer_try_push_str(buffer, "null"),
+      BindValue::String(ref elem) => elem.write(buffer, dialect),
+      BindValue::U64(elem) => elem.write(buffer, dialect),
+      #[cfg(feature = "uuid")]
+      BindValue::Uuid(elem) => elem.write(buffer, dialect),
+    }
+  }
+}
+
 macro_rules! impl_display {
-  ($ty:ty $(, $($bounds:tt)+)?) => {
+  ($ty:ty, $bind_value:expr $(, $($bounds:tt)+)?) => {
     impl<$($($bounds)+)?> SqlValue for $ty {
       #[inline]
-      fn write<B>(&self, buffer: &mut B) -> crate::Result<()>
+      fn to_bind_value(&self) -> BindValue {
+        $bind_value(*self)
+      }
+
+      #[inline]
+      fn write<B, D>(&self, buffer: &mut B, _: D) -> crate::Result<()>
       where
         B: cl_traits::String,
+        D: SqlDialect,
       {
         buffer_write_fmt(buffer, format_args!("'{}'", self))
       }
@@ -52,15 +154,52 @@ macro_rules! impl_display {
   }
 }
 
-impl_display!(&'_ str);
-impl_display!(bool);
-impl_display!(i32);
-impl_display!(i64);
-impl_display!(u32);
-impl_display!(u64);
-impl_display!(String);
+macro_rules! impl_str {
+  ($ty:ty $(, $($bounds:tt)+)?) => {
+    impl<$($($bounds)+)?> SqlValue for $ty {
+      #[inline]
+      fn to_bind_value(&self) -> BindValue {
+        BindValue::String(self.to_string())
+      }
+
+      #[inline]
+      fn write<B, D>(&self, buffer: &mut B, dialect: D) -> crate::Result<()>
+      where
+        B: cl_traits::String,
+        D: SqlDialect,
+      {
+        dialect.write_str(buffer, self)
+      }
+    }
+  }
+}
+
+impl_display!(i32, BindValue::I32);
+impl_display!(i64, BindValue::I64);
+impl_display!(u32, |n: u32| BindValue::I64(n.into()));
+impl_display!(u64, BindValue::U64);
+
+impl_str!(&'_ str);
+impl_str!(String);
 
 #[cfg(feature = "arrayvec")]
-impl_display!(arrayvec::ArrayString<N>, const N: usize);
+impl_str!(arrayvec::ArrayString<N>, const N: usize);
 #[cfg(feature = "rust_decimal")]
-impl_display!(rust_decimal::Decimal);
+impl_display!(rust_decimal::Decimal, BindValue::Decimal);
+
+#[cfg(feature = "uuid")]
+impl SqlValue for uuid::Uuid {
+  #[inline]
+  fn to_bind_value(&self) -> BindValue {
+    BindValue::Uuid(*self)
+  }
+
+  #[inline]
+  fn write<B, D>(&self, buffer: &mut B, dialect: D) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+    D: SqlDialect,
+  {
+    dialect.write_uuid(buffer, *self)
+  }
+}
@@ -4,11 +4,11 @@ mod write_select;
 mod write_update;
 
 use crate::{
-  buffer_try_push_str, write_full_select_field, write_select_join, write_select_order_by, AuxNodes,
-  SelectLimit, SelectOrderBy, Table, TableAssociations, TableDefs, TableFields,
-  TableSourceAssociation,
+  buffer_try_push_str, buffer_write_fmt, write_full_select_field, write_select_join, AuxNodes,
+  EagerLoading, InsertOptions, OrderBy, PrimaryKey, SelectLimit, SqlDialect, SqlWriterMode, Suffix,
+  Table, TableAssociation, TableAssociations, TableDefs, TableFields, TableSourceAssociation,
 };
-use core::{fmt::Display, marker::PhantomData};
+use core::{marker::PhantomData, slice};
 
 /// Writes raw SQL commands
 pub trait SqlWriter<B>
@@ -18,39 +18,96 @@ where
   /// See [crate::Error].
   type Error: From<crate::Error>;
 
+  /// Pushes the [Table::table_ref]s of the associations excluded by `eager_loading`. Descendants
+  /// of excluded associations aren't pushed. See [crate::SelectedRow].
+  fn push_excluded_table_refs(
+    &self,
+    eager_loading: EagerLoading<'_>,
+    excluded: &mut Vec<(&'static str, Suffix)>,
+  );
+
+  /// Registers the parents of all nodes, which allows [SqlWriter::write_insert] to only write a
+  /// node after all its parents.
+  fn register_insert_parents(&self, aux: &mut AuxNodes) -> Result<(), Self::Error>;
+
   /// Writes an entire DELETE command
-  fn write_delete(&self, aux: &mut AuxNodes, buffer: &mut B) -> Result<(), Self::Error>;
+  fn write_delete(
+    &self,
+    aux: &mut AuxNodes,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    mode: &mut SqlWriterMode,
+  ) -> Result<(), Self::Error>;
 
-  /// Writes an entire INSERT command
+  /// Writes an entire INSERT command. See [InsertOptions].
   fn write_insert<'value, V>(
     &self,
     aux: &mut AuxNodes,
     buffer: &mut B,
+    dialect: impl SqlDialect,
+    mode: &mut SqlWriterMode,
+    options: InsertOptions<'_>,
     table_source_association: &mut Option<TableSourceAssociation<'value, V>>,
   ) -> Result<(), Self::Error>
   where
-    V: Display;
+    V: PrimaryKey;
+
+  /// Writes the nodes referenced by belongs-to associations, which must exist before the node
+  /// identified by `hash`, and contributes their keys as foreign keys of such node.
+  fn write_insert_referenced(
+    &self,
+    aux: &mut AuxNodes,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    hash: u64,
+    mode: &mut SqlWriterMode,
+    options: InsertOptions<'_>,
+  ) -> Result<(), Self::Error>;
 
   /// Writes an entire SELECT command
   fn write_select(
     &self,
     buffer: &mut B,
-    order_by: SelectOrderBy,
+    dialect: impl SqlDialect,
+    eager_loading: EagerLoading<'_>,
+    orders_by: &[OrderBy],
     limit: SelectLimit,
     where_cb: &mut impl FnMut(&mut B) -> Result<(), Self::Error>,
   ) -> Result<(), Self::Error>;
 
   /// Only writes JOIN commands that belong to SELECT
-  fn write_select_associations(&self, buffer: &mut B) -> Result<(), Self::Error>;
+  fn write_select_associations(
+    &self,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    eager_loading: EagerLoading<'_>,
+  ) -> Result<(), Self::Error>;
 
   /// Only writes querying fields that belong to SELECT
-  fn write_select_fields(&self, buffer: &mut B) -> Result<(), Self::Error>;
+  fn write_select_fields(
+    &self,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    eager_loading: EagerLoading<'_>,
+  ) -> Result<(), Self::Error>;
 
   /// Only writes ORDER BY commands that belong to SELECT
-  fn write_select_orders_by(&self, buffer: &mut B) -> Result<(), Self::Error>;
+  fn write_select_orders_by(
+    &self,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    eager_loading: EagerLoading<'_>,
+    orders_by: &[OrderBy],
+  ) -> Result<(), Self::Error>;
 
   /// Writes an entire UPDATE command
-  fn write_update(&self, aux: &mut AuxNodes, buffer: &mut B) -> Result<(), Self::Error>;
+  fn write_update(
+    &self,
+    aux: &mut AuxNodes,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    mode: &mut SqlWriterMode,
+  ) -> Result<(), Self::Error>;
 }
 
 impl<'entity, B, TD> SqlWriter<B> for Table<'entity, TD>
@@ -63,8 +120,32 @@ where
   type Error = TD::Error;
 
   #[inline]
-  fn write_delete(&self, aux: &mut AuxNodes, buffer: &mut B) -> Result<(), Self::Error> {
-    SqlWriterLogic::write_delete(aux, buffer, self)
+  fn push_excluded_table_refs(
+    &self,
+    eager_loading: EagerLoading<'_>,
+    excluded: &mut Vec<(&'static str, Suffix)>,
+  ) {
+    self.associations().push_excluded_table_refs(eager_loading.descend(), excluded);
+  }
+
+  #[inline]
+  fn register_insert_parents(&self, aux: &mut AuxNodes) -> Result<(), Self::Error> {
+    // Nodes without primary key values can't be identified so each one has a single parent
+    if self.id_field().value().is_none() || aux.add_parent(self.instance_hash()) {
+      self.associations().register_insert_parents(aux)?;
+    }
+    Ok(())
+  }
+
+  #[inline]
+  fn write_delete(
+    &self,
+    aux: &mut AuxNodes,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    mode: &mut SqlWriterMode,
+  ) -> Result<(), Self::Error> {
+    SqlWriterLogic::write_delete(aux, buffer, dialect, mode, self)
   }
 
   #[inline]
@@ -72,70 +153,179 @@ where
     &self,
     aux: &mut AuxNodes,
     buffer: &mut B,
+    dialect: impl SqlDialect,
+    mode: &mut SqlWriterMode,
+    options: InsertOptions<'_>,
     tsa: &mut Option<TableSourceAssociation<'value, V>>,
   ) -> Result<(), Self::Error>
   where
-    V: Display,
+    V: PrimaryKey,
   {
-    SqlWriterLogic::write_insert(aux, buffer, self, tsa)
+    self.register_insert_parents(aux)?;
+    SqlWriterLogic::write_insert(aux, buffer, dialect, mode, options, slice::from_ref(self), tsa)
+  }
+
+  #[inline]
+  fn write_insert_referenced(
+    &self,
+    aux: &mut AuxNodes,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    hash: u64,
+    mode: &mut SqlWriterMode,
+    options: InsertOptions<'_>,
+  ) -> Result<(), Self::Error> {
+    self.associations().write_insert_referenced(aux, buffer, dialect, hash, mode, options)
   }
 
   #[inline]
   fn write_select(
     &self,
     buffer: &mut B,
-    order_by: SelectOrderBy,
+    dialect: impl SqlDialect,
+    eager_loading: EagerLoading<'_>,
+    orders_by: &[OrderBy],
     select_limit: SelectLimit,
     where_cb: &mut impl FnMut(&mut B) -> Result<(), Self::Error>,
   ) -> Result<(), Self::Error> {
-    SqlWriterLogic::write_select(buffer, order_by, select_limit, self, where_cb)
+    SqlWriterLogic::write_select(
+      buffer,
+      dialect,
+      eager_loading,
+      orders_by,
+      select_limit,
+      self,
+      where_cb,
+    )
   }
 
   #[inline]
-  fn write_select_associations(&self, buffer: &mut B) -> Result<(), Self::Error> {
+  fn write_select_associations(
+    &self,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    eager_loading: EagerLoading<'_>,
+  ) -> Result<(), Self::Error> {
+    let associations_eager_loading = eager_loading.descend();
     for full_association in self.associations().full_associations() {
-      write_select_join(buffer, TD::TABLE_NAME, self.suffix(), full_association)?;
+      let to_table =
+        full_association.to_table_alias().unwrap_or_else(|| full_association.to_table());
+      if !associations_eager_loading.includes((to_table, full_association.to_table_suffix())) {
+        continue;
+      }
+      write_select_join(buffer, dialect, TD::TABLE_NAME, self.suffix(), full_association)?;
       buffer_try_push_str(buffer, " ")?;
     }
-    self.associations().write_select_associations(buffer)?;
+    self.associations().write_select_associations(buffer, dialect, associations_eager_loading)?;
     Ok(())
   }
 
   #[inline]
-  fn write_select_fields(&self, buffer: &mut B) -> Result<(), Self::Error> {
-    write_full_select_field(
-      buffer,
-      TD::TABLE_NAME,
-      TD::TABLE_NAME_ALIAS,
-      self.suffix(),
-      self.id_field().name(),
-    )?;
-    buffer_try_push_str(buffer, ",")?;
-    for field in self.fields().field_names() {
-      write_full_select_field(buffer, TD::TABLE_NAME, TD::TABLE_NAME_ALIAS, self.suffix(), field)?;
+  fn write_select_fields(
+    &self,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    eager_loading: EagerLoading<'_>,
+  ) -> Result<(), Self::Error> {
+    for field in TD::PRIMARY_KEY_NAMES.iter().copied().chain(self.fields().field_names()) {
+      write_full_select_field(
+        buffer,
+        dialect,
+        TD::TABLE_NAME,
+        TD::TABLE_NAME_ALIAS,
+        self.suffix(),
+        field,
+      )?;
       buffer_try_push_str(buffer, ",")?;
     }
-    self.associations().write_select_fields(buffer)?;
+    self.associations().write_select_fields(buffer, dialect, eager_loading.descend())?;
     Ok(())
   }
 
   #[inline]
-  fn write_select_orders_by(&self, buffer: &mut B) -> Result<(), Self::Error> {
-    write_select_order_by(
+  fn write_select_orders_by(
+    &self,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    eager_loading: EagerLoading<'_>,
+    orders_by: &[OrderBy],
+  ) -> Result<(), Self::Error> {
+    SqlWriterLogic::write_table_orders_by(buffer, dialect, orders_by, self)?;
+    self.associations().write_select_orders_by(
       buffer,
-      TD::TABLE_NAME,
-      TD::TABLE_NAME_ALIAS,
-      self.suffix(),
-      self.id_field().name(),
+      dialect,
+      eager_loading.descend(),
+      orders_by,
     )?;
-    buffer_try_push_str(buffer, ",")?;
-    self.associations().write_select_orders_by(buffer)?;
     Ok(())
   }
 
   #[inline]
-  fn write_update(&self, aux: &mut AuxNodes, buffer: &mut B) -> Result<(), Self::Error> {
-    SqlWriterLogic::write_update(aux, buffer, self)
+  fn write_update(
+    &self,
+    aux: &mut AuxNodes,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    mode: &mut SqlWriterMode,
+  ) -> Result<(), Self::Error> {
+    SqlWriterLogic::write_update(aux, buffer, dialect, mode, self)
+  }
+}
+
+impl<'entity, TD> Table<'entity, TD>
+where
+  TD: TableDefs<'entity>,
+{
+  /// Writes sibling `tables` that share the same source association. See
+  /// [InsertOptions::batch_size].
+  #[inline]
+  pub(crate) fn write_insert_siblings<'value, B, V>(
+    aux: &mut AuxNodes,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    mode: &mut SqlWriterMode,
+    options: InsertOptions<'_>,
+    tables: &[Self],
+    tsa: &mut Option<TableSourceAssociation<'value, V>>,
+  ) -> Result<(), TD::Error>
+  where
+    B: cl_traits::String,
+    TD::Associations: SqlWriter<B, Error = TD::Error>,
+    TD::Error: From<crate::Error>,
+    V: PrimaryKey,
+  {
+    SqlWriterLogic::write_insert(aux, buffer, dialect, mode, options, tables, tsa)
+  }
+
+  /// Writes `table`, which is referenced by the node identified by `hash` through the belongs-to
+  /// `association`, and contributes its key to such node.
+  #[allow(clippy::too_many_arguments)]
+  #[inline]
+  pub(crate) fn write_insert_referenced<B>(
+    aux: &mut AuxNodes,
+    association: &TableAssociation,
+    buffer: &mut B,
+    dialect: impl SqlDialect,
+    hash: u64,
+    mode: &mut SqlWriterMode,
+    options: InsertOptions<'_>,
+    table: &Self,
+  ) -> Result<(), TD::Error>
+  where
+    B: cl_traits::String,
+    TD::Associations: SqlWriter<B, Error = TD::Error>,
+    TD::Error: From<crate::Error>,
+  {
+    SqlWriterLogic::write_insert_referenced(
+      aux,
+      association,
+      buffer,
+      dialect,
+      hash,
+      mode,
+      options,
+      table,
+    )
   }
 }
 
@@ -145,3 +335,33 @@ where
   TD: TableDefs<'entity>,
   TD::Associations: SqlWriter<B, Error = TD::Error>,
   TD::Error: From<crate::Error>;
+
+impl<'entity, B, TD> SqlWriterLogic<'entity, B, TD>
+where
+  B: cl_traits::String,
+  TD: TableDefs<'entity>,
+  TD::Associations: SqlWriter<B, Error = TD::Error>,
+  TD::Error: From<crate::Error>,
+{
+  /// Writes `pk_column_0=id.0{separator}pk_column_1=id.1...`, used by the SET and WHERE clauses
+  /// of UPDATE and DELETE commands.
+  fn write_primary_key_pairs<D>(
+    buffer: &mut B,
+    dialect: D,
+    id: &TD::PrimaryKeyValue,
+    mode: &mut SqlWriterMode,
+    separator: &str,
+  ) -> Result<(), TD::Error>
+  where
+    D: SqlDialect,
+  {
+    for (idx, key_name) in TD::PRIMARY_KEY_NAMES.iter().enumerate() {
+      if idx > 0 {
+        buffer_try_push_str(buffer, separator)?;
+      }
+      buffer_write_fmt(buffer, format_args!("{q}{key_name}{q}=", q = D::IDENTIFIER_QUOTE))?;
+      id.write_value(buffer, dialect, mode, idx)?;
+    }
+    Ok(())
+  }
+}
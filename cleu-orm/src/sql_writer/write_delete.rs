@@ -1,6 +1,7 @@
 use crate::{
-  buffer_try_push_str, buffer_write_fmt, node_was_already_visited, sql_writer::SqlWriterLogic,
-  AuxNodes, SqlValue, SqlWriter, Table, TableDefs,
+  buffer_write_fmt, node_was_already_visited, sql_writer::SqlWriterLogic,
+  write_statement_terminator, AuxNodes, PrimaryKey, SqlDialect, SqlWriter, SqlWriterMode, Table,
+  TableAssociations, TableDefs,
 };
 
 impl<'entity, B, TD> SqlWriterLogic<'entity, B, TD>
@@ -11,27 +12,72 @@ where
   TD::Error: From<crate::Error>,
 {
   #[inline]
-  pub(crate) fn write_delete(
+  pub(crate) fn write_delete<D>(
     aux: &mut AuxNodes,
     buffer: &mut B,
+    dialect: D,
+    mode: &mut SqlWriterMode,
     table: &Table<'entity, TD>,
-  ) -> Result<(), TD::Error> {
+  ) -> Result<(), TD::Error>
+  where
+    D: SqlDialect,
+  {
     if node_was_already_visited(aux, table)? {
       return Ok(());
     }
-    table.associations().write_delete(aux, buffer)?;
-    Self::write_delete_manager(buffer, table)?;
+    table.associations().write_delete(aux, buffer, dialect, mode)?;
+    Self::write_delete_join_rows(buffer, dialect, mode, table)?;
+    Self::write_delete_manager(buffer, dialect, mode, table)?;
     Ok(())
   }
 
-  fn write_delete_manager(buffer: &mut B, table: &Table<'entity, TD>) -> Result<(), TD::Error> {
+  /// Removes all rows of the join tables of many-to-many associations that reference `table`.
+  fn write_delete_join_rows<D>(
+    buffer: &mut B,
+    dialect: D,
+    mode: &mut SqlWriterMode,
+    table: &Table<'entity, TD>,
+  ) -> Result<(), TD::Error>
+  where
+    D: SqlDialect,
+  {
+    let id_value = if let Some(el) = table.id_field().value() { el } else { return Ok(()) };
+    for full_association in table.associations().full_associations() {
+      let association = full_association.association();
+      let join_table = if let Some(elem) = association.join_table() { elem } else { continue };
+      let idx =
+        TD::PRIMARY_KEY_NAMES.iter().position(|name| *name == association.from_id()).unwrap_or(0);
+      buffer_write_fmt(
+        buffer,
+        format_args!(
+          "DELETE FROM {q}{}{q} WHERE {q}{}{q}=",
+          join_table.name(),
+          join_table.from_column(),
+          q = D::IDENTIFIER_QUOTE
+        ),
+      )?;
+      id_value.write_value(buffer, dialect, mode, idx)?;
+      write_statement_terminator(buffer, dialect, mode, None, join_table.name())?;
+    }
+    Ok(())
+  }
+
+  fn write_delete_manager<D>(
+    buffer: &mut B,
+    dialect: D,
+    mode: &mut SqlWriterMode,
+    table: &Table<'entity, TD>,
+  ) -> Result<(), TD::Error>
+  where
+    D: SqlDialect,
+  {
     let id_value = if let &Some(ref el) = table.id_field().value() { el } else { return Ok(()) };
     buffer_write_fmt(
       buffer,
-      format_args!("DELETE FROM {} WHERE {}=", TD::TABLE_NAME, TD::PRIMARY_KEY_NAME),
+      format_args!("DELETE FROM {q}{}{q} WHERE ", TD::TABLE_NAME, q = D::IDENTIFIER_QUOTE),
     )?;
-    id_value.write(buffer)?;
-    buffer_try_push_str(buffer, ";")?;
+    Self::write_primary_key_pairs(buffer, dialect, id_value, mode, " AND ")?;
+    write_statement_terminator(buffer, dialect, mode, None, TD::TABLE_NAME)?;
     Ok(())
   }
 }
@@ -1,9 +1,11 @@
 use crate::{
   buffer_try_push_str, buffer_write_fmt, node_was_already_visited, sql_writer::SqlWriterLogic,
-  truncate_if_ends_with_char, AuxNodes, SqlValue, SqlWriter, Table, TableDefs, TableFields,
+  truncate_if_ends_with_char, write_statement_terminator, AuxNodes, ColumnType, ColumnTypes,
+  ConflictAction, ForeignKey, InsertOptions, JoinTable, PrimaryKey, SqlDialect, SqlWriter,
+  SqlWriterMode, Table, TableAssociation, TableAssociations, TableDefs, TableFields,
   TableSourceAssociation,
 };
-use core::fmt::Display;
+use core::iter;
 
 impl<'entity, B, TD> SqlWriterLogic<'entity, B, TD>
 where
@@ -12,75 +14,382 @@ where
   TD::Associations: SqlWriter<B, Error = TD::Error>,
   TD::Error: From<crate::Error>,
 {
+  /// Writes `tables`, which are siblings that share the same source association, using INSERT
+  /// statements of at most [InsertOptions::batch_size] rows. Rows whose primary keys are generated
+  /// by the database are batched together and their returned keys are mapped back by position,
+  /// unless other rows need such keys and [SqlDialect::RETURNING_FOLLOWS_VALUES] is `false`, in
+  /// which case they are written alone.
+  ///
+  /// Returns [crate::Error::UnsupportedGeneratedKey] when the generated key of a row is needed by
+  /// other rows but can't be returned, i.e., without `RETURNING` support, in
+  /// [SqlWriterMode::Inline] or with composite keys.
+  ///
+  /// Tables that have more than one parent are only written after all their parents, alone and
+  /// with every foreign key contributed by them. Tables of many-to-many associations are handled
+  /// by [Self::write_insert_join_table].
   #[inline]
-  pub(crate) fn write_insert<'value, V>(
+  pub(crate) fn write_insert<'value, D, V>(
     aux: &mut AuxNodes,
     buffer: &mut B,
-    table: &Table<'entity, TD>,
+    dialect: D,
+    mode: &mut SqlWriterMode,
+    options: InsertOptions<'_>,
+    tables: &[Table<'entity, TD>],
     tsa: &mut Option<TableSourceAssociation<'value, V>>,
   ) -> Result<(), TD::Error>
   where
-    V: Display,
+    D: SqlDialect,
+    V: PrimaryKey,
   {
-    if node_was_already_visited(aux, table)? {
-      return Ok(());
+    if let Some(elem) = tsa.as_ref() {
+      if let Some(join_table) = elem.source_association().join_table() {
+        return Self::write_insert_join_table(
+          aux, buffer, dialect, join_table, mode, options, tables, elem,
+        );
+      }
     }
-
-    let elem_opt = || {
-      if let Some(ref el) = *tsa {
-        (el.source_field() != table.id_field().name()).then(|| el)
+    let source: Vec<_> = tsa
+      .iter()
+      .flat_map(|elem| elem.source_association().ids().map(move |ids| (ids, elem)))
+      .filter(|&((_, to_id), _)| !TD::PRIMARY_KEY_NAMES.contains(&to_id))
+      .map(|((from_id, to_id), elem)| (to_id, ForeignKey::new(from_id, elem)))
+      .collect();
+    let mut batch: Vec<&Table<'entity, TD>> = Vec::new();
+    for table in tables {
+      let is_generated = table.id_field().value().is_none();
+      // Generated keys aren't part of the inserted columns so such rows are batched apart
+      if batch.first().is_some_and(|elem| elem.id_field().value().is_none() != is_generated) {
+        Self::write_insert_batch(aux, buffer, dialect, mode, options, &batch, &source)?;
+        batch.clear();
+      }
+      if is_generated {
+        if !D::RETURNING_FOLLOWS_VALUES && table.associations().has_dependent_tables() {
+          Self::write_insert_batch(aux, buffer, dialect, mode, options, &batch, &source)?;
+          batch.clear();
+          Self::write_insert_batch(aux, buffer, dialect, mode, options, &[table], &source)?;
+          continue;
+        }
       } else {
-        None
+        let hash = table.instance_hash();
+        if !aux.remove_parent(hash) {
+          for (field, value) in &source {
+            aux.push_foreign_key(hash, field, value.clone());
+          }
+          continue;
+        }
+        if node_was_already_visited(aux, table)? {
+          continue;
+        }
+        let mut foreign_keys = aux.take_foreign_keys(hash);
+        if !foreign_keys.is_empty() {
+          foreign_keys.extend(source.iter().cloned());
+          Self::write_insert_batch(aux, buffer, dialect, mode, options, &batch, &source)?;
+          batch.clear();
+          Self::write_insert_batch(aux, buffer, dialect, mode, options, &[table], &foreign_keys)?;
+          continue;
+        }
       }
-    };
+      batch.push(table);
+      if batch.len() >= options.batch_size() {
+        Self::write_insert_batch(aux, buffer, dialect, mode, options, &batch, &source)?;
+        batch.clear();
+      }
+    }
+    Self::write_insert_batch(aux, buffer, dialect, mode, options, &batch, &source)?;
+    Ok(())
+  }
 
-    if let Some(elem) = elem_opt() {
-      Self::write_insert_manager(
+  /// Writes `batch` followed by its associations. Returns the index of the statement that
+  /// returns the generated primary key, if any.
+  ///
+  /// Tables with belongs-to associations are written alone, after the tables they reference.
+  fn write_insert_batch<D>(
+    aux: &mut AuxNodes,
+    buffer: &mut B,
+    dialect: D,
+    mode: &mut SqlWriterMode,
+    options: InsertOptions<'_>,
+    batch: &[&Table<'entity, TD>],
+    foreign_keys: &[(&'static str, ForeignKey)],
+  ) -> Result<Option<usize>, TD::Error>
+  where
+    D: SqlDialect,
+  {
+    let has_references = batch.first().is_some_and(|table| {
+      table.associations().full_associations().any(|elem| elem.association().belongs_to())
+    });
+    if !has_references {
+      return Self::write_insert_rows(aux, buffer, dialect, mode, options, batch, foreign_keys);
+    }
+    let mut returning_statement = None;
+    for table in batch {
+      let hash = table.instance_hash();
+      table.associations().write_insert_referenced(aux, buffer, dialect, hash, mode, options)?;
+      let mut local_foreign_keys = foreign_keys.to_vec();
+      local_foreign_keys.extend(aux.take_foreign_keys(hash));
+      returning_statement = Self::write_insert_rows(
+        aux,
         buffer,
-        table,
-        |local| buffer_write_fmt(local, format_args!(",{}", elem.source_field())),
-        |local| buffer_write_fmt(local, format_args!("'{}',", elem.source_value())),
+        dialect,
+        mode,
+        options,
+        &[table],
+        &local_foreign_keys,
       )?;
+    }
+    Ok(returning_statement)
+  }
+
+  /// Writes `table` if it wasn't already written and contributes the keys referenced by
+  /// `association` to the node identified by `hash`.
+  #[allow(clippy::too_many_arguments)]
+  pub(crate) fn write_insert_referenced<D>(
+    aux: &mut AuxNodes,
+    association: &TableAssociation,
+    buffer: &mut B,
+    dialect: D,
+    hash: u64,
+    mode: &mut SqlWriterMode,
+    options: InsertOptions<'_>,
+    table: &Table<'entity, TD>,
+  ) -> Result<(), TD::Error>
+  where
+    D: SqlDialect,
+  {
+    // Keys are only contributed after `table` is written, which can also have its own pending
+    // foreign keys
+    if let Some(elem) = table.id_field().value() {
+      if !node_was_already_visited(aux, table)? {
+        let _ = Self::write_insert_batch(aux, buffer, dialect, mode, options, &[table], &[])?;
+      }
+      for (from_id, to_id) in association.ids() {
+        let idx = TD::PRIMARY_KEY_NAMES.iter().position(|name| *name == to_id).unwrap_or(0);
+        aux.push_foreign_key(hash, from_id, ForeignKey::Value(elem.to_bind_value(idx)));
+      }
     } else {
-      Self::write_insert_manager(buffer, table, |_| Ok(()), |_| Ok(()))?;
+      let statement = Self::write_insert_batch(aux, buffer, dialect, mode, options, &[table], &[])?
+        .ok_or_else(Self::unsupported_generated_key)?;
+      aux.push_foreign_key(hash, association.from_id(), ForeignKey::GeneratedKey(statement, 0));
+    }
+    Ok(())
+  }
+
+  // Associations can only reference a key generated by the database if it is returned by the
+  // INSERT statement, which depends on the dialect, on the mode and on the number of key columns.
+  fn unsupported_generated_key() -> TD::Error {
+    let column_type = TD::PrimaryKeyValue::column_type(0).map_or(ColumnType::I64, |elem| elem.0);
+    crate::Error::UnsupportedGeneratedKey(column_type).into()
+  }
+
+  fn write_insert_rows<D>(
+    aux: &mut AuxNodes,
+    buffer: &mut B,
+    dialect: D,
+    mode: &mut SqlWriterMode,
+    options: InsertOptions<'_>,
+    batch: &[&Table<'entity, TD>],
+    foreign_keys: &[(&'static str, ForeignKey)],
+  ) -> Result<Option<usize>, TD::Error>
+  where
+    D: SqlDialect,
+  {
+    let mut rows = Vec::with_capacity(batch.len());
+    let returning_statement =
+      Self::write_insert_manager(buffer, dialect, foreign_keys, mode, options, &mut rows, batch)?;
+    for (table, row) in batch.iter().zip(rows) {
+      let mut new_tsa = if let Some(elem) = table.id_field().value() {
+        Some(TableSourceAssociation::new(TD::PRIMARY_KEY_NAMES, elem))
+      } else if let (Some(statement), Some(row)) = (returning_statement, row) {
+        Some(TableSourceAssociation::with_generated_key(TD::PRIMARY_KEY_NAMES, statement, row))
+      } else if table.associations().has_dependent_tables() {
+        return Err(Self::unsupported_generated_key());
+      } else {
+        None
+      };
+      table.associations().write_insert(aux, buffer, dialect, mode, options, &mut new_tsa)?;
     }
+    Ok(returning_statement)
+  }
 
-    let mut new_tsa = table.id_field().value().as_ref().map(TableSourceAssociation::new);
-    table.associations().write_insert(aux, buffer, &mut new_tsa)?;
+  /// Many-to-many version of [Self::write_insert]. Tables that weren't already written are
+  /// inserted without foreign keys and then each pair of source and table ids is inserted into
+  /// `join_table`.
+  #[allow(clippy::too_many_arguments)]
+  fn write_insert_join_table<'value, D, V>(
+    aux: &mut AuxNodes,
+    buffer: &mut B,
+    dialect: D,
+    join_table: JoinTable,
+    mode: &mut SqlWriterMode,
+    options: InsertOptions<'_>,
+    tables: &[Table<'entity, TD>],
+    tsa: &TableSourceAssociation<'value, V>,
+  ) -> Result<(), TD::Error>
+  where
+    D: SqlDialect,
+    V: PrimaryKey,
+  {
+    let association = tsa.source_association();
+    let source = ForeignKey::new(association.from_id(), tsa);
+    let to_idx =
+      TD::PRIMARY_KEY_NAMES.iter().position(|name| *name == association.to_id()).unwrap_or(0);
+    let mut batch = Vec::new();
+    let mut keys = Vec::new();
+    for table in tables {
+      if let Some(elem) = table.id_field().value() {
+        keys.push(ForeignKey::Value(elem.to_bind_value(to_idx)));
+        if node_was_already_visited(aux, table)? {
+          continue;
+        }
+        batch.push(table);
+        if batch.len() >= options.batch_size() {
+          let _ = Self::write_insert_batch(aux, buffer, dialect, mode, options, &batch, &[])?;
+          batch.clear();
+        }
+      } else {
+        let _ = Self::write_insert_batch(aux, buffer, dialect, mode, options, &batch, &[])?;
+        batch.clear();
+        let statement =
+          Self::write_insert_batch(aux, buffer, dialect, mode, options, &[table], &[])?
+            .ok_or_else(Self::unsupported_generated_key)?;
+        keys.push(ForeignKey::GeneratedKey(statement, 0));
+      }
+    }
+    let _ = Self::write_insert_batch(aux, buffer, dialect, mode, options, &batch, &[])?;
+    for chunk in keys.chunks(options.batch_size()) {
+      Self::write_insert_join_rows(buffer, dialect, join_table, chunk, mode, options, &source)?;
+    }
+    Ok(())
+  }
 
+  fn write_insert_join_rows<D>(
+    buffer: &mut B,
+    dialect: D,
+    join_table: JoinTable,
+    keys: &[ForeignKey],
+    mode: &mut SqlWriterMode,
+    options: InsertOptions<'_>,
+    source: &ForeignKey,
+  ) -> Result<(), TD::Error>
+  where
+    D: SqlDialect,
+  {
+    let from_column = join_table.from_column();
+    let to_column = join_table.to_column();
+    let q = D::IDENTIFIER_QUOTE;
+    buffer_write_fmt(
+      buffer,
+      format_args!(
+        "INSERT INTO {q}{}{q} ({q}{from_column}{q},{q}{to_column}{q}) VALUES ",
+        join_table.name(),
+      ),
+    )?;
+    for key in keys {
+      buffer_try_push_str(buffer, "(")?;
+      source.write(buffer, dialect, mode)?;
+      buffer_try_push_str(buffer, ",")?;
+      key.write(buffer, dialect, mode)?;
+      buffer_try_push_str(buffer, "),")?;
+    }
+    truncate_if_ends_with_char(buffer, ',');
+    // Join tables don't have other columns so existing pairs are kept as they are
+    if let Some(elem) = options.on_conflict() {
+      let columns = [from_column, to_column];
+      let targets = elem.target(join_table.name()).unwrap_or(&columns);
+      dialect.write_on_conflict(buffer, ConflictAction::DoNothing, targets, iter::empty())?;
+    }
+    write_statement_terminator(buffer, dialect, mode, None, join_table.name())?;
     Ok(())
   }
 
-  fn write_insert_manager(
+  /// Returns the index of the statement that returns the generated primary keys, if any. The
+  /// position of each element of `batch` within `VALUES` is pushed into `rows`, which is `None`
+  /// for rows without values because they aren't written.
+  #[allow(clippy::too_many_arguments)]
+  fn write_insert_manager<D>(
     buffer: &mut B,
-    table: &Table<'entity, TD>,
-    foreign_key_name_cb: impl Fn(&mut B) -> crate::Result<()>,
-    foreign_key_value_cb: impl Fn(&mut B) -> crate::Result<()>,
-  ) -> Result<(), TD::Error> {
+    dialect: D,
+    foreign_keys: &[(&'static str, ForeignKey)],
+    mode: &mut SqlWriterMode,
+    options: InsertOptions<'_>,
+    rows: &mut Vec<Option<usize>>,
+    batch: &[&Table<'entity, TD>],
+  ) -> Result<Option<usize>, TD::Error>
+  where
+    D: SqlDialect,
+  {
+    let first = if let Some(elem) = batch.first() { elem } else { return Ok(None) };
     let len_before_insert = buffer.as_ref().len();
+    let q = D::IDENTIFIER_QUOTE;
 
-    buffer_write_fmt(buffer, format_args!("INSERT INTO \"{}\" (", TD::TABLE_NAME))?;
-    buffer_try_push_str(buffer, table.id_field().name())?;
-    for field in table.fields().field_names() {
-      buffer_write_fmt(buffer, format_args!(",{}", field))?;
+    buffer_write_fmt(buffer, format_args!("INSERT INTO {q}{}{q} (", TD::TABLE_NAME))?;
+    if first.id_field().value().is_some() {
+      for key_name in TD::PRIMARY_KEY_NAMES {
+        buffer_write_fmt(buffer, format_args!("{q}{key_name}{q},"))?;
+      }
     }
-    foreign_key_name_cb(&mut *buffer)?;
+    for field in first.fields().field_names() {
+      buffer_write_fmt(buffer, format_args!("{q}{field}{q},"))?;
+    }
+    for &(field, _) in foreign_keys {
+      buffer_write_fmt(buffer, format_args!("{q}{field}{q},"))?;
+    }
+    truncate_if_ends_with_char(buffer, ',');
 
-    buffer_try_push_str(buffer, ") VALUES (")?;
-    let len_before_values = buffer.as_ref().len();
-    if let &Some(ref elem) = table.id_field().value() {
-      elem.write(buffer)?;
-      buffer_try_push_str(buffer, ",")?;
+    buffer_try_push_str(buffer, ") VALUES ")?;
+    let len_before_rows = buffer.as_ref().len();
+    let mut written = 0;
+    for table in batch {
+      let len_before_row = buffer.as_ref().len();
+      buffer_try_push_str(buffer, "(")?;
+      let len_before_values = buffer.as_ref().len();
+      if let &Some(ref elem) = table.id_field().value() {
+        for idx in 0..TD::PRIMARY_KEY_NAMES.len() {
+          elem.write_value(buffer, dialect, mode, idx)?;
+          buffer_try_push_str(buffer, ",")?;
+        }
+      }
+      table.fields().write_insert_values(buffer, dialect, mode)?;
+      if buffer.as_ref().len() == len_before_values {
+        buffer.truncate(len_before_row);
+        rows.push(None);
+        continue;
+      }
+      rows.push(Some(written));
+      written = written.wrapping_add(1);
+      for (_, value) in foreign_keys {
+        value.write(buffer, dialect, mode)?;
+        buffer_try_push_str(buffer, ",")?;
+      }
+      truncate_if_ends_with_char(buffer, ',');
+      buffer_try_push_str(buffer, "),")?;
     }
-    table.fields().write_insert_values(buffer)?;
 
-    if buffer.as_ref().len() == len_before_values {
+    if buffer.as_ref().len() == len_before_rows {
       buffer.truncate(len_before_insert);
-    } else {
-      foreign_key_value_cb(&mut *buffer)?;
-      truncate_if_ends_with_char(buffer, ',');
-      buffer_try_push_str(buffer, ");")?;
+      return Ok(None);
     }
-    Ok(())
+    truncate_if_ends_with_char(buffer, ',');
+    if let Some(elem) = options.on_conflict() {
+      let target = elem.target(TD::TABLE_NAME).unwrap_or(TD::PRIMARY_KEY_NAMES);
+      dialect.write_on_conflict(buffer, elem.action(), target, first.fields().field_names())?;
+    }
+    // Keys generated by the database are returned so that they can be used by associations.
+    // Composite keys are never generated.
+    let returning_statement = match (first.id_field().value(), mode.bind_arguments()) {
+      (None, Some(args)) if D::SUPPORTS_RETURNING && TD::PRIMARY_KEY_NAMES.len() == 1 => {
+        buffer_write_fmt(buffer, format_args!(" RETURNING {q}{}{q}", first.id_field().name()))?;
+        Some(args.next_statement())
+      }
+      _ => None,
+    };
+    write_statement_terminator(
+      buffer,
+      dialect,
+      mode,
+      returning_statement.and_then(|_| Some(TD::PrimaryKeyValue::column_type(0)?.0)),
+      TD::TABLE_NAME,
+    )?;
+    Ok(returning_statement)
   }
 }
@@ -1,6 +1,7 @@
 use crate::{
   buffer_try_push_str, buffer_write_fmt, sql_writer::SqlWriterLogic, truncate_if_ends_with_char,
-  truncate_if_ends_with_str, SelectLimit, SelectOrderBy, SqlWriter, Table, TableDefs,
+  truncate_if_ends_with_str, write_select_field, write_select_order_by, EagerLoading, OrderBy,
+  SelectLimit, SqlDialect, SqlWriter, Table, TableDefs,
 };
 
 impl<'entity, B, TD> SqlWriterLogic<'entity, B, TD>
@@ -11,39 +12,110 @@ where
   TD::Error: From<crate::Error>,
 {
   #[inline]
-  pub(crate) fn write_select(
+  pub(crate) fn write_select<D>(
     buffer: &mut B,
-    order_by: SelectOrderBy,
+    dialect: D,
+    eager_loading: EagerLoading<'_>,
+    orders_by: &[OrderBy],
     select_limit: SelectLimit,
     table: &Table<'entity, TD>,
     where_cb: &mut impl FnMut(&mut B) -> Result<(), TD::Error>,
-  ) -> Result<(), TD::Error> {
+  ) -> Result<(), TD::Error>
+  where
+    D: SqlDialect,
+  {
+    let q = D::IDENTIFIER_QUOTE;
+    let suffix = table.suffix();
+    let name = TD::TABLE_NAME;
+    let alias = TD::TABLE_NAME_ALIAS.unwrap_or(TD::TABLE_NAME);
     buffer_try_push_str(buffer, "SELECT ")?;
-    table.write_select_fields(buffer)?;
+    table.write_select_fields(buffer, dialect, eager_loading)?;
     truncate_if_ends_with_char(buffer, ',');
-    buffer_write_fmt(
-      buffer,
-      format_args!(
-        " FROM \"{table}\" AS \"{table}{suffix}\" ",
-        suffix = table.suffix(),
-        table = TD::TABLE_NAME
-      ),
-    )?;
-    table.write_select_associations(buffer)?;
-    buffer_try_push_str(buffer, " WHERE ")?;
-    where_cb(buffer)?;
-    truncate_if_ends_with_str(buffer, " WHERE ");
+    if let SelectLimit::All = select_limit {
+      buffer_write_fmt(buffer, format_args!(" FROM {q}{name}{q} AS {q}{alias}{suffix}{q} "))?;
+    } else {
+      // Root entities are selected in a subquery so that the limit doesn't count joined rows.
+      // Duplicates are removed by grouping on the primary key because `DISTINCT` would compare
+      // every column and some types, like `json`, don't support equality.
+      buffer_write_fmt(
+        buffer,
+        format_args!(
+          " FROM (SELECT {q}{alias}{suffix}{q}.* FROM {q}{name}{q} AS {q}{alias}{suffix}{q} "
+        ),
+      )?;
+      let len = buffer.as_ref().len();
+      table.write_select_associations(buffer, dialect, eager_loading)?;
+      buffer_try_push_str(buffer, " WHERE ")?;
+      where_cb(buffer)?;
+      if buffer.as_ref().ends_with(" WHERE ") {
+        buffer.truncate(len);
+      }
+      truncate_if_ends_with_char(buffer, ' ');
+      buffer_try_push_str(buffer, " GROUP BY ")?;
+      for key_name in TD::PRIMARY_KEY_NAMES {
+        write_select_field(buffer, dialect, name, TD::TABLE_NAME_ALIAS, suffix, key_name)?;
+        buffer_try_push_str(buffer, ",")?;
+      }
+      truncate_if_ends_with_char(buffer, ',');
+      buffer_try_push_str(buffer, " ORDER BY ")?;
+      Self::write_table_orders_by(buffer, dialect, orders_by, table)?;
+      truncate_if_ends_with_char(buffer, ',');
+      dialect.write_limit(buffer, select_limit)?;
+      buffer_write_fmt(buffer, format_args!(") AS {q}{alias}{suffix}{q} "))?;
+    }
+    table.write_select_associations(buffer, dialect, eager_loading)?;
+    // Limited root entities were already filtered by the subquery and `where_cb` must only be
+    // called once, otherwise the same bind values would be pushed twice
+    if let SelectLimit::All = select_limit {
+      buffer_try_push_str(buffer, " WHERE ")?;
+      where_cb(buffer)?;
+      truncate_if_ends_with_str(buffer, " WHERE ");
+    }
     buffer_try_push_str(buffer, " ORDER BY ")?;
-    table.write_select_orders_by(buffer)?;
+    table.write_select_orders_by(buffer, dialect, eager_loading, orders_by)?;
     truncate_if_ends_with_char(buffer, ',');
-    match order_by {
-      SelectOrderBy::Ascending => buffer_try_push_str(buffer, " ASC")?,
-      SelectOrderBy::Descending => buffer_try_push_str(buffer, " DESC")?,
+    if let SelectLimit::All = select_limit {
+      dialect.write_limit(buffer, select_limit)?;
+    }
+    Ok(())
+  }
+
+  /// Sort keys of `table` followed by its primary key columns, if not already present, without
+  /// the keys of the associations.
+  #[inline]
+  pub(crate) fn write_table_orders_by<D>(
+    buffer: &mut B,
+    dialect: D,
+    orders_by: &[OrderBy],
+    table: &Table<'entity, TD>,
+  ) -> Result<(), TD::Error>
+  where
+    D: SqlDialect,
+  {
+    let name = TD::TABLE_NAME_ALIAS.unwrap_or(TD::TABLE_NAME);
+    let table_orders_by = || {
+      orders_by.iter().filter(|elem| {
+        let column = elem.column();
+        column.table() == name && column.suffix() == table.suffix()
+      })
+    };
+    for order_by in table_orders_by() {
+      order_by.write(buffer, dialect)?;
+      buffer_try_push_str(buffer, ",")?;
     }
-    buffer_try_push_str(buffer, " LIMIT ")?;
-    match select_limit {
-      SelectLimit::All => buffer_try_push_str(buffer, "ALL")?,
-      SelectLimit::Count(n) => buffer_write_fmt(buffer, format_args!("{}", n))?,
+    for key_name in TD::PRIMARY_KEY_NAMES {
+      if table_orders_by().any(|elem| elem.column().field() == *key_name) {
+        continue;
+      }
+      write_select_order_by(
+        buffer,
+        dialect,
+        TD::TABLE_NAME,
+        TD::TABLE_NAME_ALIAS,
+        table.suffix(),
+        key_name,
+      )?;
+      buffer_try_push_str(buffer, ",")?;
     }
     Ok(())
   }
@@ -1,6 +1,7 @@
 use crate::{
   buffer_try_push_str, buffer_write_fmt, node_was_already_visited, sql_writer::SqlWriterLogic,
-  truncate_if_ends_with_char, AuxNodes, SqlValue, SqlWriter, Table, TableDefs, TableFields,
+  truncate_if_ends_with_char, write_statement_terminator, AuxNodes, SqlDialect, SqlWriter,
+  SqlWriterMode, Table, TableDefs, TableFields,
 };
 
 impl<'entity, B, TD> SqlWriterLogic<'entity, B, TD>
@@ -11,34 +12,48 @@ where
   TD::Error: From<crate::Error>,
 {
   #[inline]
-  pub(crate) fn write_update(
+  pub(crate) fn write_update<D>(
     aux: &mut AuxNodes,
     buffer: &mut B,
+    dialect: D,
+    mode: &mut SqlWriterMode,
     table: &Table<'entity, TD>,
-  ) -> Result<(), TD::Error> {
+  ) -> Result<(), TD::Error>
+  where
+    D: SqlDialect,
+  {
     if node_was_already_visited(aux, table)? {
       return Ok(());
     }
-    Self::write_update_manager(buffer, table)?;
-    table.associations().write_update(aux, buffer)?;
+    Self::write_update_manager(buffer, dialect, mode, table)?;
+    table.associations().write_update(aux, buffer, dialect, mode)?;
     Ok(())
   }
 
-  fn write_update_manager(buffer: &mut B, table: &Table<'entity, TD>) -> Result<(), TD::Error> {
+  fn write_update_manager<D>(
+    buffer: &mut B,
+    dialect: D,
+    mode: &mut SqlWriterMode,
+    table: &Table<'entity, TD>,
+  ) -> Result<(), TD::Error>
+  where
+    D: SqlDialect,
+  {
     let id_value = if let &Some(ref el) = table.id_field().value() { el } else { return Ok(()) };
 
-    buffer_write_fmt(buffer, format_args!("UPDATE {} SET ", TD::TABLE_NAME))?;
+    buffer_write_fmt(
+      buffer,
+      format_args!("UPDATE {q}{}{q} SET ", TD::TABLE_NAME, q = D::IDENTIFIER_QUOTE),
+    )?;
 
-    buffer_write_fmt(buffer, format_args!("{}=", table.id_field().name()))?;
-    id_value.write(buffer)?;
+    Self::write_primary_key_pairs(buffer, dialect, id_value, mode, ",")?;
     buffer_try_push_str(buffer, ",")?;
-    table.fields().write_update_values(buffer)?;
+    table.fields().write_update_values(buffer, dialect, mode)?;
     truncate_if_ends_with_char(buffer, ',');
 
     buffer_try_push_str(buffer, " WHERE ")?;
-    buffer_write_fmt(buffer, format_args!("{}=", TD::PRIMARY_KEY_NAME))?;
-    id_value.write(buffer)?;
-    buffer_try_push_str(buffer, ";")?;
+    Self::write_primary_key_pairs(buffer, dialect, id_value, mode, " AND ")?;
+    write_statement_terminator(buffer, dialect, mode, None, TD::TABLE_NAME)?;
 
     Ok(())
   }
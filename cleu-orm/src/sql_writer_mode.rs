@@ -0,0 +1,29 @@
+use crate::BindArguments;
+
+/// Defines how values are written by [crate::SqlWriter]
+#[derive(Debug, Default, PartialEq)]
+pub enum SqlWriterMode {
+  /// Values are escaped and written directly into the buffer
+  #[default]
+  Inline,
+  /// Values are replaced by `$1, $2, ...` placeholders and collected into [BindArguments]
+  Parameterized(BindArguments),
+}
+
+impl SqlWriterMode {
+  /// Shortcut for `SqlWriterMode::Parameterized(BindArguments::new())`
+  #[inline]
+  pub const fn parameterized() -> Self {
+    Self::Parameterized(BindArguments::new())
+  }
+
+  /// Collected arguments, if any
+  #[inline]
+  pub fn bind_arguments(&self) -> Option<&BindArguments> {
+    if let Self::Parameterized(ref elem) = *self {
+      Some(elem)
+    } else {
+      None
+    }
+  }
+}
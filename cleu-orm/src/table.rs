@@ -1,6 +1,7 @@
-use crate::{FxHasher, Suffix, TableDefs, TableField};
+use crate::{Column, Filter, FxHasher, PrimaryKey, SqlValue, Suffix, TableDefs, TableField};
 use core::{
   hash::{Hash, Hasher},
+  iter,
   marker::PhantomData,
 };
 
@@ -28,7 +29,7 @@ where
     Self {
       associations,
       fields,
-      id_field: TableField::new(TD::PRIMARY_KEY_NAME),
+      id_field: TableField::new(TD::PRIMARY_KEY_NAMES.first().copied().unwrap_or_default()),
       phantom: PhantomData,
       suffix,
     }
@@ -46,6 +47,16 @@ where
     &mut self.associations
   }
 
+  /// Column of one of the [Self::fields] of this instance, which can be used to build
+  /// [crate::Filter]s.
+  #[inline]
+  pub fn column<E, T>(&self, field: &TableField<E, T>) -> Column<T>
+  where
+    T: SqlValue,
+  {
+    Column::new(TD::TABLE_NAME_ALIAS.unwrap_or(TD::TABLE_NAME), self.suffix, field.name())
+  }
+
   /// Table instance fields
   #[inline]
   pub fn fields(&self) -> &TD::Fields {
@@ -58,7 +69,8 @@ where
     &mut self.fields
   }
 
-  /// Field information related to the entity ID
+  /// Field information related to the entity ID. Named after the first primary key column, which
+  /// means that the value of composite keys holds all columns.
   #[inline]
   pub fn id_field(&self) -> &TableField<TD::Error, TD::PrimaryKeyValue> {
     &self.id_field
@@ -70,6 +82,58 @@ where
     &mut self.id_field
   }
 
+  /// Column of the primary key of this instance. See [Self::column].
+  #[inline]
+  pub fn id_column(&self) -> Column<TD::PrimaryKeyValue>
+  where
+    TD::PrimaryKeyValue: SqlValue,
+  {
+    self.column(&self.id_field)
+  }
+
+  /// `pk_column_0 = id.0 AND pk_column_1 = id.1 ...`, which also works with composite keys.
+  #[inline]
+  pub fn id_filter(&self, id: TD::PrimaryKeyValue) -> Option<Filter> {
+    let (table, suffix) = self.table_ref();
+    TD::PRIMARY_KEY_NAMES
+      .iter()
+      .enumerate()
+      .map(|(idx, name)| {
+        Filter::compare(Column::<()>::new(table, suffix, name), " = ", id.to_bind_value(idx))
+      })
+      .reduce(Filter::and)
+  }
+
+  /// `pk_column_0 > last.0 OR (pk_column_0 = last.0 AND pk_column_1 > last.1) ...`, which selects
+  /// the rows that come after `last` when sorted by the primary key. See [crate::Keyset].
+  #[inline]
+  pub fn keyset_filter(&self, last: TD::PrimaryKeyValue) -> Option<Filter> {
+    let (table, suffix) = self.table_ref();
+    let compare = |idx: usize, name: &'static str, operator: &'static str| {
+      Filter::compare(Column::<()>::new(table, suffix, name), operator, last.to_bind_value(idx))
+    };
+    TD::PRIMARY_KEY_NAMES
+      .iter()
+      .enumerate()
+      .filter_map(|(idx, name)| {
+        TD::PRIMARY_KEY_NAMES
+          .iter()
+          .take(idx)
+          .enumerate()
+          .map(|(prev_idx, prev_name)| compare(prev_idx, prev_name, " = "))
+          .chain(iter::once(compare(idx, name, " > ")))
+          .reduce(Filter::and)
+      })
+      .reduce(Filter::or)
+  }
+
+  /// Alias, or name, and suffix that identify this instance in SELECT operations. See
+  /// [crate::EagerLoading].
+  #[inline]
+  pub fn table_ref(&self) -> (&'static str, Suffix) {
+    (TD::TABLE_NAME_ALIAS.unwrap_or(TD::TABLE_NAME), self.suffix)
+  }
+
   /// Used to write internal SQL operations
   #[inline]
   pub fn suffix(&self) -> Suffix {
@@ -85,7 +149,7 @@ where
   #[inline]
   pub(crate) fn instance_hash(&self) -> u64 {
     let mut fx_hasher = FxHasher::default();
-    TD::PRIMARY_KEY_NAME.hash(&mut fx_hasher);
+    TD::PRIMARY_KEY_NAMES.hash(&mut fx_hasher);
     TD::TABLE_NAME.hash(&mut fx_hasher);
     TD::TABLE_NAME_ALIAS.hash(&mut fx_hasher);
     self.id_field().value().hash(&mut fx_hasher);
@@ -1,15 +1,43 @@
+use crate::{JoinKind, JoinTable};
+use core::iter;
+
 /// Table association and its associated Rust type
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct TableAssociation {
+  belongs_to: bool,
+  composite_ids: &'static [(&'static str, &'static str)],
   from_id: &'static str,
+  join_kind: JoinKind,
+  join_table: Option<JoinTable>,
   to_id: &'static str,
 }
 
 impl TableAssociation {
-  /// Creates a new instance from all parameters
+  /// Creates a new instance from all parameters using [JoinKind::Left].
   #[inline]
   pub const fn new(from_id: &'static str, to_id: &'static str) -> Self {
-    Self { from_id, to_id }
+    Self {
+      belongs_to: false,
+      composite_ids: &[],
+      from_id,
+      join_kind: JoinKind::Left,
+      join_table: None,
+      to_id,
+    }
+  }
+
+  /// If the caller holds the foreign key that references the callee. See
+  /// [Self::with_belongs_to].
+  #[inline]
+  pub const fn belongs_to(&self) -> bool {
+    self.belongs_to
+  }
+
+  /// Additional pairs of caller and callee id field names of associations between tables with
+  /// composite keys. See [Self::with_composite_ids].
+  #[inline]
+  pub const fn composite_ids(&self) -> &'static [(&'static str, &'static str)] {
+    self.composite_ids
   }
 
   /// Caller id filed name
@@ -18,9 +46,71 @@ impl TableAssociation {
     self.from_id
   }
 
+  /// See [JoinKind].
+  #[inline]
+  pub const fn join_kind(&self) -> JoinKind {
+    self.join_kind
+  }
+
+  /// See [JoinTable].
+  #[inline]
+  pub const fn join_table(&self) -> Option<JoinTable> {
+    self.join_table
+  }
+
+  /// All pairs of caller and callee id field names, starting with [Self::from_id] and
+  /// [Self::to_id].
+  #[inline]
+  pub fn ids(&self) -> impl Iterator<Item = (&'static str, &'static str)> {
+    iter::once((self.from_id, self.to_id)).chain(self.composite_ids.iter().copied())
+  }
+
   /// Callee id filed name
   #[inline]
   pub const fn to_id(&self) -> &'static str {
     self.to_id
   }
+
+  /// Changes the direction of this association to many-to-one, i.e., [Self::from_id] becomes a
+  /// foreign key column of the caller that references the [Self::to_id] key of the callee, e.g.,
+  /// `TableAssociation::new("customer_id", "id").with_belongs_to(true)`.
+  ///
+  /// Referenced entities are written before the caller, which receives their keys, and are never
+  /// deleted along with it.
+  #[inline]
+  pub const fn with_belongs_to(mut self, belongs_to: bool) -> Self {
+    self.belongs_to = belongs_to;
+    self
+  }
+
+  /// Adds pairs of caller and callee id field names that are compared along with [Self::from_id]
+  /// and [Self::to_id], e.g., `&[("tenant_id", "tenant_id")]`.
+  #[inline]
+  pub const fn with_composite_ids(
+    mut self,
+    composite_ids: &'static [(&'static str, &'static str)],
+  ) -> Self {
+    self.composite_ids = composite_ids;
+    self
+  }
+
+  /// Replaces the current [JoinKind].
+  #[inline]
+  pub const fn with_join_kind(mut self, join_kind: JoinKind) -> Self {
+    self.join_kind = join_kind;
+    self
+  }
+
+  /// Turns this association into a many-to-many association whose pairs of ids are stored in
+  /// `join_table`. [Self::from_id] and [Self::to_id] become the columns referenced by the join
+  /// table, which aren't required to be part of the caller or the callee. Composite ids aren't
+  /// supported.
+  ///
+  /// Related entities are independent of each other so INSERT and DELETE operations only
+  /// create and remove rows of the join table.
+  #[inline]
+  pub const fn with_join_table(mut self, join_table: JoinTable) -> Self {
+    self.join_table = Some(join_table);
+    self
+  }
 }
@@ -16,6 +16,7 @@ where
   pub association: TableAssociation,
   /// Used to construct SELECT operations
   pub guide: Table<'entity, TD>,
-  /// A storage of zero, one or many tables used for INSERT and UPDATE operations
+  /// A storage of zero, one or many tables used for INSERT and UPDATE operations. See
+  /// [crate::OptionalTable] for optional associations.
   pub tables: TS,
 }
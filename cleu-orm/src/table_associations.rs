@@ -7,4 +7,8 @@ pub trait TableAssociations {
 
   /// Yields all table associations
   fn full_associations(&self) -> Self::FullTableAssociations;
+
+  /// If associations that aren't belongs-to have tables, which can only be written with the
+  /// primary key of the current table.
+  fn has_dependent_tables(&self) -> bool;
 }
@@ -1,10 +1,13 @@
-use crate::{FromSuffixRslt, SqlValue, Suffix, Table, TableAssociations, TableFields};
-use core::{fmt::Display, hash::Hash};
+use crate::{FromSuffixRslt, PrimaryKey, Suffix, Table, TableAssociations, TableFields};
 
 /// All SQL definitions of an entity table.
 pub trait TableDefs<'entity> {
-  /// Table primary key name
-  const PRIMARY_KEY_NAME: &'static str;
+  /// Names of all columns that compose the table primary key. Composite keys have more than one
+  /// name.
+  const PRIMARY_KEY_NAMES: &'static [&'static str];
+  /// If the values of a single-column primary key are generated by the database when omitted, i.e.,
+  /// identity, `SERIAL` or `AUTO_INCREMENT` columns. Only affects [crate::Schema].
+  const PRIMARY_KEY_IS_GENERATED: bool = false;
   /// Table name specified in the database
   const TABLE_NAME: &'static str;
   /// Optional table alias specified in the database
@@ -18,8 +21,8 @@ pub trait TableDefs<'entity> {
   type Error: From<crate::Error>;
   /// All table fields minus the primary key. For more information, see [TableFields]
   type Fields: TableFields<Error = Self::Error>;
-  /// Table primary key value type
-  type PrimaryKeyValue: Copy + Display + Hash + SqlValue;
+  /// Table primary key value type. See [PrimaryKey].
+  type PrimaryKeyValue: PrimaryKey;
 
   /// Implementation should provide all related fields and associations
   fn type_instances(suffix: Suffix) -> FromSuffixRslt<'entity, Self>;
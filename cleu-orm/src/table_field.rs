@@ -1,4 +1,3 @@
-use crate::SqlValue;
 use core::marker::PhantomData;
 
 /// Table field name and its associated Rust type
@@ -9,10 +8,7 @@ pub struct TableField<E, T> {
   value: Option<T>,
 }
 
-impl<E, T> TableField<E, T>
-where
-  T: SqlValue,
-{
+impl<E, T> TableField<E, T> {
   /// Creates a new instance from the table field name
   #[inline]
   pub const fn new(name: &'static str) -> Self {
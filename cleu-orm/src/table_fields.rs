@@ -1,3 +1,5 @@
+use crate::{SqlDialect, SqlWriterMode};
+
 /// Groups tuples that form all fields of a table
 pub trait TableFields {
   /// See [crate::Error]
@@ -9,12 +11,24 @@ pub trait TableFields {
   fn field_names(&self) -> Self::FieldNames;
 
   /// Writes the table instance values for INSERT statements
-  fn write_insert_values<B>(&self, buffer: &mut B) -> Result<(), Self::Error>
+  fn write_insert_values<B, D>(
+    &self,
+    buffer: &mut B,
+    dialect: D,
+    mode: &mut SqlWriterMode,
+  ) -> Result<(), Self::Error>
   where
-    B: cl_traits::String;
+    B: cl_traits::String,
+    D: SqlDialect;
 
   /// Writes the table instance values for UPDATE statements
-  fn write_update_values<B>(&self, buffer: &mut B) -> Result<(), Self::Error>
+  fn write_update_values<B, D>(
+    &self,
+    buffer: &mut B,
+    dialect: D,
+    mode: &mut SqlWriterMode,
+  ) -> Result<(), Self::Error>
   where
-    B: cl_traits::String;
+    B: cl_traits::String,
+    D: SqlDialect;
 }
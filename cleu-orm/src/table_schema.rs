@@ -0,0 +1,103 @@
+use crate::{
+  truncate_if_ends_with_char, write_names, write_statement_terminator, ColumnSchema,
+  ForeignKeySchema, SqlDialect, SqlWriterMode,
+};
+
+/// Columns and constraints of a table. See [crate::Schema].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableSchema {
+  columns: Vec<ColumnSchema>,
+  foreign_keys: Vec<ForeignKeySchema>,
+  name: &'static str,
+  primary_key: Vec<&'static str>,
+}
+
+impl TableSchema {
+  /// Creates a new instance from all parameters
+  #[inline]
+  pub const fn new(
+    columns: Vec<ColumnSchema>,
+    foreign_keys: Vec<ForeignKeySchema>,
+    name: &'static str,
+    primary_key: Vec<&'static str>,
+  ) -> Self {
+    Self { columns, foreign_keys, name, primary_key }
+  }
+
+  /// Column named `name`, if any
+  #[inline]
+  pub fn column(&self, name: &str) -> Option<&ColumnSchema> {
+    self.columns.iter().find(|elem| elem.name() == name)
+  }
+
+  /// All columns in declaration order, starting with the primary key
+  #[inline]
+  pub fn columns(&self) -> &[ColumnSchema] {
+    &self.columns
+  }
+
+  /// Mutable version of [Self::columns]
+  #[inline]
+  pub fn columns_mut(&mut self) -> &mut Vec<ColumnSchema> {
+    &mut self.columns
+  }
+
+  /// See [ForeignKeySchema].
+  #[inline]
+  pub fn foreign_keys(&self) -> &[ForeignKeySchema] {
+    &self.foreign_keys
+  }
+
+  /// Mutable version of [Self::foreign_keys]
+  #[inline]
+  pub fn foreign_keys_mut(&mut self) -> &mut Vec<ForeignKeySchema> {
+    &mut self.foreign_keys
+  }
+
+  /// Table name
+  #[inline]
+  pub const fn name(&self) -> &'static str {
+    self.name
+  }
+
+  /// Names of all columns that compose the primary key
+  #[inline]
+  pub fn primary_key(&self) -> &[&'static str] {
+    &self.primary_key
+  }
+
+  /// Writes a `CREATE TABLE` statement with all columns and constraints
+  #[inline]
+  pub fn write_create_table<B, D>(
+    &self,
+    buffer: &mut B,
+    dialect: D,
+    mode: &mut SqlWriterMode,
+  ) -> crate::Result<()>
+  where
+    B: cl_traits::String,
+    D: SqlDialect,
+  {
+    let q = D::IDENTIFIER_QUOTE;
+    buffer.write_fmt(format_args!("CREATE TABLE {q}{}{q} (", self.name))?;
+    for column in &self.columns {
+      column.write(buffer, dialect)?;
+      buffer.push(",")?;
+    }
+    if !self.primary_key.is_empty() {
+      buffer.push("PRIMARY KEY (")?;
+      write_names(buffer, dialect, &self.primary_key)?;
+      buffer.push("),")?;
+    }
+    for foreign_key in &self.foreign_keys {
+      buffer.push("FOREIGN KEY (")?;
+      write_names(buffer, dialect, foreign_key.columns())?;
+      buffer.write_fmt(format_args!(") REFERENCES {q}{}{q} (", foreign_key.referenced_table()))?;
+      write_names(buffer, dialect, foreign_key.referenced_columns())?;
+      buffer.push("),")?;
+    }
+    truncate_if_ends_with_char(buffer, ',');
+    buffer.push(")")?;
+    write_statement_terminator(buffer, dialect, mode, None, self.name)
+  }
+}
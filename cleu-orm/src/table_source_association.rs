@@ -1,28 +1,77 @@
+use crate::TableAssociation;
+
 /// Used by nodes that need source (backward) information
 #[derive(Clone, Copy, Debug)]
 pub struct TableSourceAssociation<'value, V = &'static str> {
-  source_field: &'static str,
-  source_value: &'value V,
+  source_association: TableAssociation,
+  source_key_names: &'static [&'static str],
+  source_row: usize,
+  source_statement: usize,
+  source_value: Option<&'value V>,
 }
 
 impl<'value, V> TableSourceAssociation<'value, V> {
   #[inline]
-  pub(crate) const fn new(source_value: &'value V) -> Self {
-    Self { source_field: "", source_value }
+  pub(crate) const fn new(
+    source_key_names: &'static [&'static str],
+    source_value: &'value V,
+  ) -> Self {
+    Self {
+      source_association: TableAssociation::new("", ""),
+      source_key_names,
+      source_row: 0,
+      source_statement: 0,
+      source_value: Some(source_value),
+    }
+  }
+
+  /// The source value is the primary key generated by the row at index `source_row` of the
+  /// statement at index `source_statement`
+  #[inline]
+  pub(crate) const fn with_generated_key(
+    source_key_names: &'static [&'static str],
+    source_statement: usize,
+    source_row: usize,
+  ) -> Self {
+    Self {
+      source_association: TableAssociation::new("", ""),
+      source_key_names,
+      source_row,
+      source_statement,
+      source_value: None,
+    }
+  }
+
+  /// Association between the source and the current node
+  #[inline]
+  pub(crate) const fn source_association(&self) -> &TableAssociation {
+    &self.source_association
+  }
+
+  #[inline]
+  pub(crate) fn source_association_mut(&mut self) -> &mut TableAssociation {
+    &mut self.source_association
+  }
+
+  /// Primary key column names of the source, in the same order of the values of
+  /// [Self::source_value].
+  #[inline]
+  pub(crate) const fn source_key_names(&self) -> &'static [&'static str] {
+    self.source_key_names
   }
 
   #[inline]
-  pub(crate) const fn source_field(&self) -> &'static str {
-    self.source_field
+  pub(crate) const fn source_row(&self) -> usize {
+    self.source_row
   }
 
   #[inline]
-  pub(crate) fn source_field_mut(&mut self) -> &mut &'static str {
-    &mut self.source_field
+  pub(crate) const fn source_statement(&self) -> usize {
+    self.source_statement
   }
 
   #[inline]
-  pub(crate) const fn source_value(&self) -> &'value V {
+  pub(crate) const fn source_value(&self) -> Option<&'value V> {
     self.source_value
   }
 }
@@ -0,0 +1,23 @@
+use crate::{Table, TableDefs};
+
+/// Storage of the tables of a [crate::TableAssociationWrapper], used by [crate::Schema] to
+/// derive the nullability of foreign key columns
+pub trait TableStorage {
+  /// If the associated entity can be absent, which makes the foreign key columns of
+  /// belongs-to associations nullable
+  const IS_OPTIONAL: bool;
+}
+
+impl<'entity, TD> TableStorage for Vec<Table<'entity, TD>>
+where
+  TD: TableDefs<'entity>,
+{
+  const IS_OPTIONAL: bool = false;
+}
+
+impl<'entity, TD, const N: usize> TableStorage for [Table<'entity, TD>; N]
+where
+  TD: TableDefs<'entity>,
+{
+  const IS_OPTIONAL: bool = false;
+}
@@ -1,2 +1,13 @@
+mod belongs_to;
 mod collection;
+mod composite_key;
+mod db_row;
 mod diamond;
+mod generated_key;
+mod key_types;
+mod many_to_many;
+mod migrations;
+mod optional;
+mod postgres;
+mod self_reference;
+mod sqlite;
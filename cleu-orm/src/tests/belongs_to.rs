@@ -0,0 +1,342 @@
+// CREATE TABLE customers (
+//   id INTEGER PRIMARY KEY NOT NULL,
+//   name TEXT NOT NULL
+// );
+//
+// CREATE TABLE orders (
+//   id INTEGER PRIMARY KEY NOT NULL,
+//   customer_id INTEGER NOT NULL REFERENCES customers(id),
+//   name TEXT NOT NULL
+// );
+//
+// CREATE TABLE invoices (
+//   id INTEGER PRIMARY KEY NOT NULL,
+//   customer_id INTEGER REFERENCES customers(id),
+//   number TEXT NOT NULL
+// );
+//
+// Invoice --> Option<Customer>
+// Order --> Customer
+
+use crate::{
+  seek_related_entities, write_column_alias, ColumnType, DbRow, DecodeColumn, EagerLoading,
+  FromRowsSuffix, FromSuffixRslt, InitialInsertValue, InsertOptions, JoinKind, MySqlDialect,
+  NoTableAssociation, OptionalTable, PostgresDialect, Schema, SelectLimit, SqlWriter,
+  SqlWriterMode, Suffix, Table, TableAssociation, TableAssociationWrapper, TableDefs, TableField,
+};
+
+#[derive(Debug, PartialEq)]
+struct Customer {
+  id: i64,
+  name: String,
+}
+
+impl<B, ROW> FromRowsSuffix<B, ROW> for Customer
+where
+  B: cl_traits::String,
+  ROW: DbRow + DecodeColumn<i64> + DecodeColumn<String>,
+{
+  type Error = crate::Error;
+
+  fn from_rows_suffix(
+    _: &[ROW],
+    buffer: &mut B,
+    suffix: Suffix,
+    target_row: &ROW,
+  ) -> Result<(usize, Self), Self::Error> {
+    write_column_alias(buffer, "customers", suffix, "id")?;
+    let id = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    write_column_alias(buffer, "customers", suffix, "name")?;
+    let name = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    Ok((1, Self { id, name }))
+  }
+}
+
+struct CustomerTableDefs;
+
+impl<'entity> TableDefs<'entity> for CustomerTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "customers";
+
+  type Associations = NoTableAssociation<crate::Error>;
+  type Entity = Customer;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity i64;
+
+  fn type_instances(_: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (NoTableAssociation::new(), (TableField::new("name"),))
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.name);
+  }
+}
+
+#[derive(Debug, PartialEq)]
+struct Order {
+  customer: Customer,
+  id: i64,
+  name: String,
+}
+
+impl<B, ROW> FromRowsSuffix<B, ROW> for Order
+where
+  B: cl_traits::String,
+  ROW: DbRow + DecodeColumn<i64> + DecodeColumn<String>,
+{
+  type Error = crate::Error;
+
+  fn from_rows_suffix(
+    all_rows: &[ROW],
+    buffer: &mut B,
+    suffix: Suffix,
+    target_row: &ROW,
+  ) -> Result<(usize, Self), Self::Error> {
+    write_column_alias(buffer, "orders", suffix, "id")?;
+    let id = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    write_column_alias(buffer, "orders", suffix, "name")?;
+    let name = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    let mut customer = None;
+    let skip = seek_related_entities::<_, _, Customer, _, CustomerTableDefs, OrderTableDefs>(
+      buffer,
+      all_rows,
+      suffix,
+      suffix + 1,
+      OrderTableDefs::type_instances(suffix).0 .0.association.join_kind(),
+      |elem| {
+        customer = Some(elem);
+        Ok(())
+      },
+    )?;
+    let customer = customer.ok_or(crate::Error::NoDatabaseRowResult)?;
+    Ok((skip, Self { customer, id, name }))
+  }
+}
+
+struct OrderTableDefs;
+
+impl<'entity> TableDefs<'entity> for OrderTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "orders";
+
+  type Associations =
+    (TableAssociationWrapper<'entity, CustomerTableDefs, [Table<'entity, CustomerTableDefs>; 1]>,);
+  type Entity = Order;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity i64;
+
+  fn type_instances(suffix: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (
+      (TableAssociationWrapper {
+        association: TableAssociation::new("customer_id", "id")
+          .with_belongs_to(true)
+          .with_join_kind(JoinKind::Inner),
+        guide: Table::new(suffix + 1),
+        tables: [Table::new(suffix + 1)],
+      },),
+      (TableField::new("name"),),
+    )
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.name);
+
+    table.associations_mut().0.tables[0].update_all_table_fields(&entity.customer);
+  }
+}
+
+struct Invoice {
+  customer: Option<Customer>,
+  id: i64,
+  number: String,
+}
+
+struct InvoiceTableDefs;
+
+impl<'entity> TableDefs<'entity> for InvoiceTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "invoices";
+
+  type Associations = (
+    TableAssociationWrapper<'entity, CustomerTableDefs, OptionalTable<'entity, CustomerTableDefs>>,
+  );
+  type Entity = Invoice;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity i64;
+
+  fn type_instances(suffix: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (
+      (TableAssociationWrapper {
+        association: TableAssociation::new("customer_id", "id").with_belongs_to(true),
+        guide: Table::new(suffix + 1),
+        tables: OptionalTable::default(),
+      },),
+      (TableField::new("number"),),
+    )
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.number);
+
+    table.associations_mut().0.tables =
+      OptionalTable::from_entity(entity.customer.as_ref(), table.suffix() + 1);
+  }
+}
+
+fn orders() -> [Order; 2] {
+  [
+    Order { customer: Customer { id: 1, name: "foo".into() }, id: 1, name: "bar".into() },
+    Order { customer: Customer { id: 1, name: "foo".into() }, id: 2, name: "baz".into() },
+  ]
+}
+
+#[test]
+fn foreign_keys_of_the_caller_are_used_by_select_joins() {
+  let mut buffer = String::new();
+  let table = Table::<OrderTableDefs>::default();
+  table
+    .write_select(
+      &mut buffer,
+      PostgresDialect,
+      EagerLoading::all(),
+      &[],
+      SelectLimit::All,
+      &mut |_| Ok(()),
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"SELECT "orders0"."id" AS "orders0__id","orders0"."name" AS "orders0__name","customers1"."id" AS "customers1__id","customers1"."name" AS "customers1__name" FROM "orders" AS "orders0" INNER JOIN "customers" AS "customers1" ON "orders0"."customer_id" = "customers1"."id"  ORDER BY "orders0"."id","customers1"."id" LIMIT ALL"#
+  );
+}
+
+#[test]
+fn referenced_tables_are_written_before_the_caller() {
+  let orders = orders();
+  let mut aux = <_>::default();
+  let mut buffer = String::new();
+  for order in &orders {
+    let mut table = Table::<OrderTableDefs>::default();
+    table.update_all_table_fields(order);
+    table
+      .write_insert::<InitialInsertValue>(
+        &mut aux,
+        &mut buffer,
+        PostgresDialect,
+        &mut SqlWriterMode::Inline,
+        InsertOptions::new(),
+        &mut None,
+      )
+      .unwrap();
+  }
+  assert_eq!(
+    &buffer,
+    r#"INSERT INTO "customers" ("id","name") VALUES ('1','foo');INSERT INTO "orders" ("id","name","customer_id") VALUES ('1','bar','1');INSERT INTO "orders" ("id","name","customer_id") VALUES ('2','baz','1');"#
+  );
+
+  buffer.clear();
+  let mut table = Table::<OrderTableDefs>::default();
+  table.update_all_table_fields(&orders[0]);
+  table
+    .write_delete(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
+  assert_eq!(&buffer, r#"DELETE FROM "orders" WHERE "id"='1';"#);
+}
+
+#[test]
+fn generated_keys_of_referenced_tables_must_be_returned() {
+  let orders = orders();
+  let mut buffer = String::new();
+  let mut table = Table::<OrderTableDefs>::default();
+  table.update_all_table_fields(&orders[0]);
+  *table.associations_mut().0.tables[0].id_field_mut().value_mut() = None;
+  let rslt = table.write_insert::<InitialInsertValue>(
+    &mut <_>::default(),
+    &mut buffer,
+    MySqlDialect,
+    &mut SqlWriterMode::parameterized(),
+    InsertOptions::new(),
+    &mut None,
+  );
+  assert!(matches!(rslt, Err(crate::Error::UnsupportedGeneratedKey(ColumnType::I64))));
+
+  buffer.clear();
+  let mut mode = SqlWriterMode::parameterized();
+  table
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut mode,
+      InsertOptions::new(),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"INSERT INTO "customers" ("name") VALUES ($1) RETURNING "id";INSERT INTO "orders" ("id","name","customer_id") VALUES ($1,$2,$3);"#
+  );
+}
+
+#[test]
+fn referenced_tables_are_created_before_the_caller() {
+  let mut buffer = String::new();
+  let mut schema = Schema::new();
+  schema.push_table(&Table::<OrderTableDefs>::default()).unwrap();
+  schema.write_create_tables(&mut buffer, PostgresDialect, &mut SqlWriterMode::Inline).unwrap();
+  assert_eq!(
+    &buffer,
+    r#"CREATE TABLE "customers" ("id" BIGINT NOT NULL,"name" TEXT NOT NULL,PRIMARY KEY ("id"));CREATE TABLE "orders" ("id" BIGINT NOT NULL,"name" TEXT NOT NULL,"customer_id" BIGINT NOT NULL,PRIMARY KEY ("id"),FOREIGN KEY ("customer_id") REFERENCES "customers" ("id"));"#
+  );
+}
+
+#[test]
+fn optional_referenced_tables_have_nullable_foreign_keys() {
+  let invoice = Invoice { customer: None, id: 1, number: "foo".into() };
+  let mut buffer = String::new();
+  let mut table = Table::<InvoiceTableDefs>::default();
+  table.update_all_table_fields(&invoice);
+  let mut schema = Schema::new();
+  schema.push_table(&table).unwrap();
+  schema.write_create_tables(&mut buffer, PostgresDialect, &mut SqlWriterMode::Inline).unwrap();
+  assert_eq!(
+    &buffer,
+    r#"CREATE TABLE "customers" ("id" BIGINT NOT NULL,"name" TEXT NOT NULL,PRIMARY KEY ("id"));CREATE TABLE "invoices" ("id" BIGINT NOT NULL,"number" TEXT NOT NULL,"customer_id" BIGINT,PRIMARY KEY ("id"),FOREIGN KEY ("customer_id") REFERENCES "customers" ("id"));"#
+  );
+}
+
+#[cfg(all(feature = "sqlx-sqlite", feature = "sqlx-runtime-tokio-rustls"))]
+#[tokio::test]
+async fn sqlite_backend_reads_and_writes_belongs_to_associations() {
+  use sqlx_core::{query::query, sqlite::SqlitePoolOptions};
+
+  let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+  let mut buffer = String::new();
+  let _ = Table::<OrderTableDefs>::default().create_schema(&mut buffer, &pool).await.unwrap();
+  let [first, second] = orders();
+
+  let mut table = Table::<OrderTableDefs>::default();
+  table.create(&mut buffer, &pool, &first).await.unwrap();
+  let _ = query("INSERT INTO orders (id, customer_id, name) VALUES (2, 1, 'baz')")
+    .execute(&pool)
+    .await
+    .unwrap();
+
+  let table = Table::<OrderTableDefs>::default();
+  let mut orders = table.read_all(&mut buffer, &pool).await.unwrap();
+  orders.sort_by_key(|elem| elem.id);
+  assert_eq!(orders, [first, second]);
+}
@@ -1,8 +1,9 @@
 // C --> A/B
 
 use crate::{
-  FromSuffixRslt, InitialInsertValue, NoTableAssociation, SelectLimit, SelectOrderBy, SqlWriter,
-  Suffix, Table, TableAssociation, TableAssociationWrapper, TableDefs, TableField,
+  BindValue, EagerLoading, FromSuffixRslt, InitialInsertValue, InsertOptions, MySqlDialect,
+  NoTableAssociation, PostgresDialect, SelectLimit, SqlValue, SqlWriter, SqlWriterMode,
+  SqliteDialect, Suffix, Table, TableAssociation, TableAssociationWrapper, TableDefs, TableField,
 };
 use core::mem;
 
@@ -16,7 +17,7 @@ struct A {
 struct ATableDefs;
 
 impl<'entity> TableDefs<'entity> for ATableDefs {
-  const PRIMARY_KEY_NAME: &'static str = "id";
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
   const TABLE_NAME: &'static str = "a";
 
   type Associations = NoTableAssociation<()>;
@@ -45,7 +46,7 @@ struct B {
 struct BTableDefs;
 
 impl<'entity> TableDefs<'entity> for BTableDefs {
-  const PRIMARY_KEY_NAME: &'static str = "id";
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
   const TABLE_NAME: &'static str = "b";
 
   type Associations = NoTableAssociation<()>;
@@ -76,7 +77,7 @@ struct C {
 struct CTableDefs;
 
 impl<'entity> TableDefs<'entity> for CTableDefs {
-  const PRIMARY_KEY_NAME: &'static str = "id";
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
   const TABLE_NAME: &'static str = "c";
 
   type Associations = (
@@ -132,7 +133,7 @@ impl<'entity> TableDefs<'entity> for CTableDefs {
 fn assert_sizes() {
   assert_eq!(mem::size_of::<Table<'_, ATableDefs>>(), 64);
   assert_eq!(mem::size_of::<Table<'_, BTableDefs>>(), 64);
-  assert_eq!(mem::size_of::<Table<'_, CTableDefs>>(), 304);
+  assert_eq!(mem::size_of::<Table<'_, CTableDefs>>(), 448);
 }
 
 #[test]
@@ -150,8 +151,13 @@ fn update_some_values_has_correct_behavior() {
   *elem.id_field_mut().value_mut() = Some(&c3.r#as[0].id);
   c_table_defs.associations_mut().0.tables.push(elem);
 
-  c_table_defs.write_update(&mut <_>::default(), &mut buffer).unwrap();
-  assert_eq!(&buffer, r#"UPDATE c SET id='3' WHERE id='3';UPDATE a SET id='1' WHERE id='1';"#);
+  c_table_defs
+    .write_update(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"UPDATE "c" SET "id"='3' WHERE "id"='3';UPDATE "a" SET "id"='1' WHERE "id"='1';"#
+  );
 }
 
 #[test]
@@ -163,58 +169,226 @@ fn write_collection_has_correct_params() {
   let mut buffer = String::new();
   let mut c_table_defs = Table::<CTableDefs>::default();
 
-  c_table_defs.write_delete(&mut <_>::default(), &mut buffer).unwrap();
+  c_table_defs
+    .write_delete(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
   assert_eq!(&buffer, r#""#);
 
   c_table_defs
-    .write_insert::<InitialInsertValue>(&mut <_>::default(), &mut buffer, &mut None)
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut SqlWriterMode::Inline,
+      InsertOptions::new(),
+      &mut None,
+    )
     .unwrap();
   assert_eq!(&buffer, r#""#);
 
   buffer.clear();
   c_table_defs
-    .write_select(&mut buffer, SelectOrderBy::Ascending, SelectLimit::All, &mut |_| Ok(()))
+    .write_select(
+      &mut buffer,
+      PostgresDialect,
+      EagerLoading::all(),
+      &[],
+      SelectLimit::All,
+      &mut |_| Ok(()),
+    )
     .unwrap();
   assert_eq!(
     &buffer,
-    r#"SELECT "c0".id AS c0__id,"c0".name AS c0__name,"a1".id AS a1__id,"a1".name AS a1__name,"b2".id AS b2__id,"b2".name AS b2__name FROM "c" AS "c0" LEFT JOIN "a" AS "a1" ON "c0".id = "a1".id_a LEFT JOIN "b" AS "b2" ON "c0".id = "b2".id_b  ORDER BY "c0".id,"a1".id,"b2".id ASC LIMIT ALL"#
+    r#"SELECT "c0"."id" AS "c0__id","c0"."name" AS "c0__name","a1"."id" AS "a1__id","a1"."name" AS "a1__name","b2"."id" AS "b2__id","b2"."name" AS "b2__name" FROM "c" AS "c0" LEFT JOIN "a" AS "a1" ON "c0"."id" = "a1"."id_a" LEFT JOIN "b" AS "b2" ON "c0"."id" = "b2"."id_b"  ORDER BY "c0"."id","a1"."id","b2"."id" LIMIT ALL"#
   );
 
   buffer.clear();
-  c_table_defs.write_update(&mut <_>::default(), &mut buffer).unwrap();
+  c_table_defs
+    .write_update(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
   assert_eq!(&buffer, r#""#);
 
   c_table_defs.update_all_table_fields(&c3);
 
   buffer.clear();
-  c_table_defs.write_delete(&mut <_>::default(), &mut buffer).unwrap();
+  c_table_defs
+    .write_delete(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"DELETE FROM "a" WHERE "id"='1';DELETE FROM "a" WHERE "id"='2';DELETE FROM "c" WHERE "id"='3';"#
+  );
+
+  buffer.clear();
+  c_table_defs
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut SqlWriterMode::Inline,
+      InsertOptions::new(),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"INSERT INTO "c" ("id","name") VALUES ('3','foo3');INSERT INTO "a" ("id","name","id_a") VALUES ('1','foo1','3');INSERT INTO "a" ("id","name","id_a") VALUES ('2','foo2','3');"#
+  );
+
+  buffer.clear();
+  c_table_defs
+    .write_select(
+      &mut buffer,
+      PostgresDialect,
+      EagerLoading::all(),
+      &[],
+      SelectLimit::All,
+      &mut |_| Ok(()),
+    )
+    .unwrap();
   assert_eq!(
     &buffer,
-    r#"DELETE FROM a WHERE id='1';DELETE FROM a WHERE id='2';DELETE FROM c WHERE id='3';"#
+    r#"SELECT "c0"."id" AS "c0__id","c0"."name" AS "c0__name","a1"."id" AS "a1__id","a1"."name" AS "a1__name","b2"."id" AS "b2__id","b2"."name" AS "b2__name" FROM "c" AS "c0" LEFT JOIN "a" AS "a1" ON "c0"."id" = "a1"."id_a" LEFT JOIN "b" AS "b2" ON "c0"."id" = "b2"."id_b"  ORDER BY "c0"."id","a1"."id","b2"."id" LIMIT ALL"#
   );
 
   buffer.clear();
   c_table_defs
-    .write_insert::<InitialInsertValue>(&mut <_>::default(), &mut buffer, &mut None)
+    .write_update(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"UPDATE "c" SET "id"='3',"name"='foo3' WHERE "id"='3';UPDATE "a" SET "id"='1',"name"='foo1' WHERE "id"='1';UPDATE "a" SET "id"='2',"name"='foo2' WHERE "id"='2';"#
+  );
+}
+
+#[test]
+fn hostile_values_are_escaped() {
+  let a1 = A { id: 1, name: r"foo\'); DROP TABLE a; --" };
+  let a2 = A { id: 2, name: "foo'2" };
+  let c3 = C { r#as: vec![a1, a2], bs: vec![], id: 3, name: "'; DELETE FROM c; --" };
+
+  let mut buffer = String::new();
+  let mut c_table_defs = Table::<CTableDefs>::default();
+  c_table_defs.update_all_table_fields(&c3);
+
+  c_table_defs
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut SqlWriterMode::Inline,
+      InsertOptions::new(),
+      &mut None,
+    )
     .unwrap();
   assert_eq!(
     &buffer,
-    r#"INSERT INTO "c" (id,name) VALUES ('3','foo3');INSERT INTO "a" (id,name,id_a) VALUES ('1','foo1','3');INSERT INTO "a" (id,name,id_a) VALUES ('2','foo2','3');"#
+    r#"INSERT INTO "c" ("id","name") VALUES ('3','''; DELETE FROM c; --');INSERT INTO "a" ("id","name","id_a") VALUES ('1',E'foo\\''); DROP TABLE a; --','3');INSERT INTO "a" ("id","name","id_a") VALUES ('2','foo''2','3');"#
   );
 
   buffer.clear();
   c_table_defs
-    .write_select(&mut buffer, SelectOrderBy::Ascending, SelectLimit::All, &mut |_| Ok(()))
+    .write_update(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
     .unwrap();
   assert_eq!(
     &buffer,
-    r#"SELECT "c0".id AS c0__id,"c0".name AS c0__name,"a1".id AS a1__id,"a1".name AS a1__name,"b2".id AS b2__id,"b2".name AS b2__name FROM "c" AS "c0" LEFT JOIN "a" AS "a1" ON "c0".id = "a1".id_a LEFT JOIN "b" AS "b2" ON "c0".id = "b2".id_b  ORDER BY "c0".id,"a1".id,"b2".id ASC LIMIT ALL"#
+    r#"UPDATE "c" SET "id"='3',"name"='''; DELETE FROM c; --' WHERE "id"='3';UPDATE "a" SET "id"='1',"name"=E'foo\\''); DROP TABLE a; --' WHERE "id"='1';UPDATE "a" SET "id"='2',"name"='foo''2' WHERE "id"='2';"#
   );
 
+  let c4 = C { r#as: vec![], bs: vec![], id: 4, name: "foo\0" };
+  c_table_defs.update_all_table_fields(&c4);
+
   buffer.clear();
-  c_table_defs.write_update(&mut <_>::default(), &mut buffer).unwrap();
+  assert!(c_table_defs
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut SqlWriterMode::Inline,
+      InsertOptions::new(),
+      &mut None
+    )
+    .is_err());
+  assert!(c_table_defs
+    .write_update(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .is_err());
+}
+
+#[test]
+fn bytes_are_written_by_each_dialect() {
+  let mut buffer = String::new();
+  let bytes: &[u8] = &[0, 171, 255];
+  bytes.write(&mut buffer, PostgresDialect).unwrap();
+  buffer.push(',');
+  bytes.write(&mut buffer, SqliteDialect).unwrap();
+  buffer.push(',');
+  bytes.write(&mut buffer, MySqlDialect).unwrap();
+  assert_eq!(&buffer, r"E'\\x00abff',X'00abff',X'00abff'");
+}
+
+#[test]
+fn sibling_rows_are_batched() {
+  let r#as = vec![A { id: 1, name: "foo1" }, A { id: 2, name: "foo2" }, A { id: 3, name: "foo3" }];
+  let c4 = C { r#as, bs: vec![], id: 4, name: "foo4" };
+
+  let mut buffer = String::new();
+  let mut c_table_defs = Table::<CTableDefs>::default();
+  c_table_defs.update_all_table_fields(&c4);
+
+  let mut mode = SqlWriterMode::parameterized();
+  c_table_defs
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut mode,
+      InsertOptions::new().with_batch_size(2),
+      &mut None,
+    )
+    .unwrap();
   assert_eq!(
     &buffer,
-    r#"UPDATE c SET id='3',name='foo3' WHERE id='3';UPDATE a SET id='1',name='foo1' WHERE id='1';UPDATE a SET id='2',name='foo2' WHERE id='2';"#
+    r#"INSERT INTO "c" ("id","name") VALUES ($1,$2);INSERT INTO "a" ("id","name","id_a") VALUES ($1,$2,$3),($4,$5,$6);INSERT INTO "a" ("id","name","id_a") VALUES ($1,$2,$3);"#
+  );
+  assert_eq!(mode.bind_arguments().unwrap().tables().collect::<Vec<_>>(), ["c", "a", "a"]);
+}
+
+#[test]
+fn sibling_rows_with_generated_keys_are_batched() {
+  let r#as = vec![A { id: 0, name: "foo1" }, A { id: 0, name: "foo2" }];
+  let c3 = C { r#as, bs: vec![], id: 0, name: "foo3" };
+
+  let mut buffer = String::new();
+  let mut c_table_defs = Table::<CTableDefs>::default();
+  c_table_defs.update_all_table_fields(&c3);
+  *c_table_defs.id_field_mut().value_mut() = None;
+  for elem in &mut c_table_defs.associations_mut().0.tables {
+    *elem.id_field_mut().value_mut() = None;
+  }
+
+  let mut mode = SqlWriterMode::parameterized();
+  c_table_defs
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      SqliteDialect,
+      &mut mode,
+      InsertOptions::new().with_batch_size(2),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"INSERT INTO "c" ("name") VALUES (?1) RETURNING "id";INSERT INTO "a" ("name","id_a") VALUES (?1,?2),(?3,?4) RETURNING "id";"#
+  );
+  let args = mode.bind_arguments().unwrap();
+  assert_eq!(
+    args.values_with_generated_keys(1, &[vec![BindValue::I32(3)]]).unwrap(),
+    [
+      BindValue::String("foo1".into()),
+      BindValue::I32(3),
+      BindValue::String("foo2".into()),
+      BindValue::I32(3)
+    ]
   );
 }
@@ -0,0 +1,333 @@
+// CREATE TABLE orders (
+//   tenant_id INTEGER NOT NULL,
+//   id INTEGER NOT NULL,
+//   name TEXT NOT NULL,
+//   PRIMARY KEY (tenant_id, id)
+// );
+//
+// CREATE TABLE order_lines (
+//   order_id INTEGER NOT NULL,
+//   line_no INTEGER NOT NULL,
+//   tenant_id INTEGER NOT NULL,
+//   product TEXT NOT NULL,
+//   PRIMARY KEY (order_id, line_no),
+//   FOREIGN KEY (tenant_id, order_id) REFERENCES orders(tenant_id, id)
+// );
+//
+// Order --> OrderLine
+
+use crate::{
+  seek_related_entities,
+  tests::db_row::{TestRow, TestValue},
+  write_column_alias, DbRow, DecodeColumn, EagerLoading, FromRowsSuffix, FromSuffixRslt,
+  InitialInsertValue, InsertOptions, JoinKind, MySqlDialect, NoTableAssociation, PostgresDialect,
+  Schema, SelectLimit, SqlWriter, SqlWriterMode, Suffix, Table, TableAssociation,
+  TableAssociationWrapper, TableDefs, TableField,
+};
+
+#[derive(Debug, PartialEq)]
+struct OrderLine {
+  line_no: i64,
+  order_id: i64,
+  product: String,
+}
+
+impl<B, ROW> FromRowsSuffix<B, ROW> for OrderLine
+where
+  B: cl_traits::String,
+  ROW: DbRow + DecodeColumn<i64> + DecodeColumn<String>,
+{
+  type Error = crate::Error;
+
+  fn from_rows_suffix(
+    _: &[ROW],
+    buffer: &mut B,
+    suffix: Suffix,
+    target_row: &ROW,
+  ) -> Result<(usize, Self), Self::Error> {
+    write_column_alias(buffer, "order_lines", suffix, "order_id")?;
+    let order_id = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    write_column_alias(buffer, "order_lines", suffix, "line_no")?;
+    let line_no = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    write_column_alias(buffer, "order_lines", suffix, "product")?;
+    let product = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    Ok((1, Self { line_no, order_id, product }))
+  }
+}
+
+struct OrderLineTableDefs;
+
+impl<'entity> TableDefs<'entity> for OrderLineTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["order_id", "line_no"];
+  const TABLE_NAME: &'static str = "order_lines";
+
+  type Associations = NoTableAssociation<crate::Error>;
+  type Entity = OrderLine;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = (&'entity i64, &'entity i64);
+
+  fn type_instances(_: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (NoTableAssociation::new(), (TableField::new("product"),))
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some((&entity.order_id, &entity.line_no));
+
+    *table.fields_mut().0.value_mut() = Some(&entity.product);
+  }
+}
+
+#[derive(Debug, PartialEq)]
+struct Order {
+  id: i64,
+  lines: Vec<OrderLine>,
+  name: String,
+  tenant_id: i64,
+}
+
+impl<B, ROW> FromRowsSuffix<B, ROW> for Order
+where
+  B: cl_traits::String,
+  ROW: DbRow + DecodeColumn<i64> + DecodeColumn<String>,
+{
+  type Error = crate::Error;
+
+  fn from_rows_suffix(
+    all_rows: &[ROW],
+    buffer: &mut B,
+    suffix: Suffix,
+    target_row: &ROW,
+  ) -> Result<(usize, Self), Self::Error> {
+    write_column_alias(buffer, "orders", suffix, "tenant_id")?;
+    let tenant_id = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    write_column_alias(buffer, "orders", suffix, "id")?;
+    let id = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    write_column_alias(buffer, "orders", suffix, "name")?;
+    let name = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    let mut lines = Vec::new();
+    let skip = seek_related_entities::<_, _, OrderLine, _, OrderLineTableDefs, OrderTableDefs>(
+      buffer,
+      all_rows,
+      suffix,
+      suffix + 1,
+      OrderTableDefs::type_instances(suffix).0 .0.association.join_kind(),
+      |line| {
+        lines.push(line);
+        Ok(())
+      },
+    )?;
+    Ok((skip, Self { id, lines, name, tenant_id }))
+  }
+}
+
+struct OrderTableDefs;
+
+impl<'entity> TableDefs<'entity> for OrderTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["tenant_id", "id"];
+  const TABLE_NAME: &'static str = "orders";
+
+  type Associations = (
+    TableAssociationWrapper<'entity, OrderLineTableDefs, Vec<Table<'entity, OrderLineTableDefs>>>,
+  );
+  type Entity = Order;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = (&'entity i64, &'entity i64);
+
+  fn type_instances(suffix: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (
+      (TableAssociationWrapper {
+        association: TableAssociation::new("id", "order_id")
+          .with_composite_ids(&[("tenant_id", "tenant_id")]),
+        guide: Table::new(suffix + 1),
+        tables: vec![],
+      },),
+      (TableField::new("name"),),
+    )
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some((&entity.tenant_id, &entity.id));
+
+    *table.fields_mut().0.value_mut() = Some(&entity.name);
+
+    table.associations_mut().0.tables.clear();
+    for line in entity.lines.iter() {
+      let mut elem = Table::new(table.suffix() + 1);
+      elem.update_all_table_fields(line);
+      table.associations_mut().0.tables.push(elem);
+    }
+  }
+}
+
+fn order() -> Order {
+  Order {
+    id: 2,
+    lines: vec![
+      OrderLine { line_no: 1, order_id: 2, product: "foo".into() },
+      OrderLine { line_no: 2, order_id: 2, product: "bar".into() },
+    ],
+    name: "baz".into(),
+    tenant_id: 1,
+  }
+}
+
+#[test]
+fn composite_keys_are_used_by_select_joins_and_orders() {
+  let mut buffer = String::new();
+  let table = Table::<OrderTableDefs>::default();
+  let id_desc = table.column(&TableField::<(), i64>::new("id")).desc();
+  table
+    .write_select(
+      &mut buffer,
+      PostgresDialect,
+      EagerLoading::all(),
+      &[id_desc],
+      SelectLimit::All,
+      &mut |_| Ok(()),
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"SELECT "orders0"."tenant_id" AS "orders0__tenant_id","orders0"."id" AS "orders0__id","orders0"."name" AS "orders0__name","order_lines1"."order_id" AS "order_lines1__order_id","order_lines1"."line_no" AS "order_lines1__line_no","order_lines1"."product" AS "order_lines1__product" FROM "orders" AS "orders0" LEFT JOIN "order_lines" AS "order_lines1" ON "orders0"."id" = "order_lines1"."order_id" AND "orders0"."tenant_id" = "order_lines1"."tenant_id"  ORDER BY "orders0"."id" DESC,"orders0"."tenant_id","order_lines1"."order_id","order_lines1"."line_no" LIMIT ALL"#
+  );
+}
+
+#[test]
+fn composite_keys_are_used_by_write_operations() {
+  let order = order();
+  let mut buffer = String::new();
+  let mut table = Table::<OrderTableDefs>::default();
+  table.update_all_table_fields(&order);
+
+  table
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut SqlWriterMode::Inline,
+      InsertOptions::new(),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"INSERT INTO "orders" ("tenant_id","id","name") VALUES ('1','2','baz');INSERT INTO "order_lines" ("order_id","line_no","product","tenant_id") VALUES ('2','1','foo','1');INSERT INTO "order_lines" ("order_id","line_no","product","tenant_id") VALUES ('2','2','bar','1');"#
+  );
+
+  buffer.clear();
+  table
+    .write_update(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"UPDATE "orders" SET "tenant_id"='1',"id"='2',"name"='baz' WHERE "tenant_id"='1' AND "id"='2';UPDATE "order_lines" SET "order_id"='2',"line_no"='1',"product"='foo' WHERE "order_id"='2' AND "line_no"='1';UPDATE "order_lines" SET "order_id"='2',"line_no"='2',"product"='bar' WHERE "order_id"='2' AND "line_no"='2';"#
+  );
+
+  buffer.clear();
+  table
+    .write_delete(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"DELETE FROM "order_lines" WHERE "order_id"='2' AND "line_no"='1';DELETE FROM "order_lines" WHERE "order_id"='2' AND "line_no"='2';DELETE FROM "orders" WHERE "tenant_id"='1' AND "id"='2';"#
+  );
+
+  buffer.clear();
+  table
+    .id_filter((&1, &2))
+    .unwrap()
+    .write(&mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
+  assert_eq!(&buffer, r#"("orders0"."tenant_id" = '1' AND "orders0"."id" = '2')"#);
+
+  buffer.clear();
+  table
+    .keyset_filter((&1, &2))
+    .unwrap()
+    .write(&mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"("orders0"."tenant_id" > '1' OR ("orders0"."tenant_id" = '1' AND "orders0"."id" > '2'))"#
+  );
+}
+
+#[test]
+fn composite_keys_are_used_by_foreign_keys() {
+  let mut buffer = String::new();
+  let mut schema = Schema::new();
+  schema.push_table(&Table::<OrderTableDefs>::default()).unwrap();
+  schema.write_create_tables(&mut buffer, MySqlDialect, &mut SqlWriterMode::Inline).unwrap();
+  assert_eq!(
+    &buffer,
+    "CREATE TABLE `orders` (`tenant_id` BIGINT NOT NULL,`id` BIGINT NOT NULL,`name` VARCHAR(255) \
+     NOT NULL,PRIMARY KEY (`tenant_id`,`id`));CREATE TABLE `order_lines` (`order_id` BIGINT NOT \
+     NULL,`line_no` BIGINT NOT NULL,`product` VARCHAR(255) NOT NULL,`tenant_id` BIGINT NOT \
+     NULL,PRIMARY KEY (`order_id`,`line_no`),FOREIGN KEY (`order_id`,`tenant_id`) REFERENCES \
+     `orders` (`id`,`tenant_id`));"
+  );
+}
+
+#[test]
+fn rows_are_grouped_by_all_primary_key_columns() {
+  let rows = [
+    TestRow(&[
+      ("orders0__tenant_id", Some(TestValue::I64(1))),
+      ("orders0__id", Some(TestValue::I64(2))),
+      ("orders0__name", Some(TestValue::Str("foo"))),
+      ("order_lines1__order_id", Some(TestValue::I64(2))),
+      ("order_lines1__line_no", Some(TestValue::I64(1))),
+      ("order_lines1__product", Some(TestValue::Str("bar"))),
+    ]),
+    TestRow(&[
+      ("orders0__tenant_id", Some(TestValue::I64(3))),
+      ("orders0__id", Some(TestValue::I64(2))),
+      ("orders0__name", Some(TestValue::Str("baz"))),
+      ("order_lines1__order_id", Some(TestValue::I64(2))),
+      ("order_lines1__line_no", Some(TestValue::I64(1))),
+      ("order_lines1__product", Some(TestValue::Str("qux"))),
+    ]),
+  ];
+  let mut buffer = String::new();
+  let mut entities = Vec::new();
+  let mut counter = 0;
+  while let Some(curr_rows) = rows.get(counter..).filter(|elem| !elem.is_empty()) {
+    counter += seek_related_entities::<_, _, Order, _, OrderTableDefs, OrderTableDefs>(
+      &mut buffer,
+      curr_rows,
+      0,
+      0,
+      JoinKind::Inner,
+      |order| {
+        entities.push(order);
+        Ok(())
+      },
+    )
+    .unwrap();
+  }
+  assert_eq!(
+    entities,
+    [
+      Order {
+        id: 2,
+        lines: vec![OrderLine { line_no: 1, order_id: 2, product: "bar".into() }],
+        name: "foo".into(),
+        tenant_id: 1
+      },
+      Order {
+        id: 2,
+        lines: vec![OrderLine { line_no: 1, order_id: 2, product: "qux".into() }],
+        name: "baz".into(),
+        tenant_id: 3
+      }
+    ]
+  );
+}
@@ -0,0 +1,362 @@
+// CREATE TABLE b (
+//   id INTEGER PRIMARY KEY NOT NULL,
+//   name TEXT NOT NULL
+// );
+//
+// CREATE TABLE a (
+//   id INTEGER PRIMARY KEY NOT NULL,
+//   id_b INTEGER NOT NULL REFERENCES b(id),
+//   name TEXT NOT NULL
+// );
+//
+// B --> A
+
+use crate::{
+  seek_related_entities, write_column_alias, ColumnType, DbRow, DecodeColumn, EagerLoading,
+  FromRowsSuffix, FromSuffixRslt, InitialInsertValue, InsertOptions, JoinKind, MySqlDialect,
+  NoTableAssociation, PostgresDialect, SelectLimit, SelectedRow, SqlWriter, SqlWriterMode, Suffix,
+  Table, TableAssociation, TableAssociationWrapper, TableDefs, TableField,
+};
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct A {
+  pub(crate) id: i64,
+  pub(crate) name: String,
+}
+
+impl<B, ROW> FromRowsSuffix<B, ROW> for A
+where
+  B: cl_traits::String,
+  ROW: DbRow + DecodeColumn<i64> + DecodeColumn<String>,
+{
+  type Error = crate::Error;
+
+  fn from_rows_suffix(
+    _: &[ROW],
+    buffer: &mut B,
+    suffix: Suffix,
+    target_row: &ROW,
+  ) -> Result<(usize, Self), Self::Error> {
+    write_column_alias(buffer, "a", suffix, "id")?;
+    let id = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    write_column_alias(buffer, "a", suffix, "name")?;
+    let name = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    Ok((1, Self { id, name }))
+  }
+}
+
+pub(crate) struct ATableDefs;
+
+impl<'entity> TableDefs<'entity> for ATableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "a";
+
+  type Associations = NoTableAssociation<crate::Error>;
+  type Entity = A;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity i64;
+
+  fn type_instances(_: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (NoTableAssociation::new(), (TableField::new("name"),))
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.name);
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct B {
+  pub(crate) r#as: Vec<A>,
+  pub(crate) id: i64,
+  pub(crate) name: String,
+}
+
+impl<BUFFER, ROW> FromRowsSuffix<BUFFER, ROW> for B
+where
+  BUFFER: cl_traits::String,
+  ROW: DbRow + DecodeColumn<i64> + DecodeColumn<String>,
+{
+  type Error = crate::Error;
+
+  fn from_rows_suffix(
+    all_rows: &[ROW],
+    buffer: &mut BUFFER,
+    suffix: Suffix,
+    target_row: &ROW,
+  ) -> Result<(usize, Self), Self::Error> {
+    write_column_alias(buffer, "b", suffix, "id")?;
+    let id = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    write_column_alias(buffer, "b", suffix, "name")?;
+    let name = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    let mut r#as = Vec::new();
+    let skip = seek_related_entities::<_, _, A, _, ATableDefs, BTableDefs>(
+      buffer,
+      all_rows,
+      suffix,
+      suffix + 1,
+      BTableDefs::type_instances(suffix).0 .0.association.join_kind(),
+      |a| {
+        r#as.push(a);
+        Ok(())
+      },
+    )?;
+    Ok((skip, Self { r#as, id, name }))
+  }
+}
+
+pub(crate) struct BTableDefs;
+
+impl<'entity> TableDefs<'entity> for BTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "b";
+
+  type Associations =
+    (TableAssociationWrapper<'entity, ATableDefs, Vec<Table<'entity, ATableDefs>>>,);
+  type Entity = B;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity i64;
+
+  fn type_instances(suffix: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (
+      (TableAssociationWrapper {
+        association: TableAssociation::new("id", "id_b"),
+        guide: Table::new(suffix + 1),
+        tables: vec![],
+      },),
+      (TableField::new("name"),),
+    )
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.name);
+
+    table.associations_mut().0.tables.clear();
+    for a in entity.r#as.iter() {
+      let mut elem = Table::new(table.suffix() + 1);
+      elem.update_all_table_fields(a);
+      table.associations_mut().0.tables.push(elem);
+    }
+  }
+}
+
+/// Hand-built row that mimics the result of `SELECT ... FROM b LEFT JOIN a ...`
+pub(crate) struct TestRow(pub(crate) &'static [(&'static str, Option<TestValue>)]);
+
+impl DbRow for TestRow {}
+
+impl DecodeColumn<i64> for TestRow {
+  fn decode_column(&self, column: &str) -> crate::Result<Option<i64>> {
+    Ok(
+      self.value(column).and_then(|elem| if let TestValue::I64(n) = elem { Some(n) } else { None }),
+    )
+  }
+}
+
+impl DecodeColumn<String> for TestRow {
+  fn decode_column(&self, column: &str) -> crate::Result<Option<String>> {
+    Ok(self.value(column).and_then(|elem| {
+      if let TestValue::Str(s) = elem {
+        Some(s.into())
+      } else {
+        None
+      }
+    }))
+  }
+}
+
+impl TestRow {
+  fn value(&self, column: &str) -> Option<TestValue> {
+    self.0.iter().find(|elem| elem.0 == column).and_then(|elem| elem.1)
+  }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum TestValue {
+  I64(i64),
+  Str(&'static str),
+}
+
+#[test]
+fn hand_built_rows_are_assembled_into_entities() {
+  let rows = [
+    TestRow(&[
+      ("b0__id", Some(TestValue::I64(1))),
+      ("b0__name", Some(TestValue::Str("bar1"))),
+      ("a1__id", Some(TestValue::I64(1))),
+      ("a1__name", Some(TestValue::Str("foo1"))),
+    ]),
+    TestRow(&[
+      ("b0__id", Some(TestValue::I64(1))),
+      ("b0__name", Some(TestValue::Str("bar1"))),
+      ("a1__id", Some(TestValue::I64(2))),
+      ("a1__name", Some(TestValue::Str("foo2"))),
+    ]),
+    TestRow(&[
+      ("b0__id", Some(TestValue::I64(2))),
+      ("b0__name", Some(TestValue::Str("bar2"))),
+      ("a1__id", None),
+      ("a1__name", None),
+    ]),
+  ];
+  let mut buffer = String::new();
+  let mut entities = Vec::new();
+  let mut counter = 0;
+  while let Some(curr_rows) = rows.get(counter..).filter(|elem| !elem.is_empty()) {
+    counter += seek_related_entities::<_, _, B, _, BTableDefs, BTableDefs>(
+      &mut buffer,
+      curr_rows,
+      0,
+      0,
+      JoinKind::Inner,
+      |b| {
+        entities.push(b);
+        Ok(())
+      },
+    )
+    .unwrap();
+  }
+  assert_eq!(
+    entities,
+    [
+      B {
+        r#as: vec![A { id: 1, name: "foo1".into() }, A { id: 2, name: "foo2".into() }],
+        id: 1,
+        name: "bar1".into()
+      },
+      B { r#as: vec![], id: 2, name: "bar2".into() }
+    ]
+  );
+}
+
+#[test]
+fn null_values_of_required_columns_are_errors() {
+  let row = TestRow(&[("a1__id", None)]);
+  assert!(matches!(row.decode::<i64>("a1__id"), Err(crate::Error::UnexpectedNullValue)));
+  assert_eq!(row.decode_opt::<i64>("a1__id").unwrap(), None);
+}
+
+#[test]
+fn inner_joins_require_related_entities() {
+  let mut buffer = String::new();
+  let mut table = Table::<BTableDefs>::default();
+  table.associations_mut().0.association =
+    TableAssociation::new("id", "id_b").with_join_kind(JoinKind::Inner);
+  table
+    .write_select(
+      &mut buffer,
+      PostgresDialect,
+      EagerLoading::all(),
+      &[],
+      SelectLimit::All,
+      &mut |_| Ok(()),
+    )
+    .unwrap();
+  assert!(buffer.contains(r#" INNER JOIN "a" AS "a1" ON "b0"."id" = "a1"."id_b" "#));
+
+  let rows = [TestRow(&[
+    ("b0__id", Some(TestValue::I64(2))),
+    ("b0__name", Some(TestValue::Str("bar2"))),
+    ("a1__id", None),
+    ("a1__name", None),
+  ])];
+  buffer.clear();
+  let rslt = seek_related_entities::<_, _, A, _, ATableDefs, BTableDefs>(
+    &mut buffer,
+    &rows,
+    0,
+    1,
+    table.associations().0.association.join_kind(),
+    |_| Ok(()),
+  );
+  assert!(matches!(rslt, Err(crate::Error::UnexpectedNullValue)));
+
+  let excluded: Arc<[(&'static str, Suffix)]> = Arc::new([("a", 1)]);
+  let rows = rows.map(|row| SelectedRow::new(Arc::clone(&excluded), row));
+  let rslt = seek_related_entities::<_, _, A, _, ATableDefs, BTableDefs>(
+    &mut buffer,
+    &rows,
+    0,
+    1,
+    table.associations().0.association.join_kind(),
+    |_| Ok(()),
+  );
+  assert_eq!(rslt.unwrap(), 1);
+}
+
+#[test]
+fn left_joins_only_treat_null_keys_as_absent_entities() {
+  let rows = [TestRow(&[
+    ("b0__id", Some(TestValue::I64(1))),
+    ("b0__name", Some(TestValue::Str("bar1"))),
+    ("a1__id", Some(TestValue::I64(1))),
+    ("a1__name", None),
+  ])];
+  let mut buffer = String::new();
+  let rslt = seek_related_entities::<_, _, A, _, ATableDefs, BTableDefs>(
+    &mut buffer,
+    &rows,
+    0,
+    1,
+    JoinKind::Left,
+    |_| Ok(()),
+  );
+  assert!(matches!(rslt, Err(crate::Error::UnexpectedNullValue)));
+}
+
+#[test]
+fn generated_keys_of_parents_must_be_returned() {
+  let entity = B { r#as: vec![A { id: 1, name: "foo1".into() }], id: 0, name: "bar1".into() };
+  let mut buffer = String::new();
+  let mut table = Table::<BTableDefs>::default();
+  table.update_all_table_fields(&entity);
+  *table.id_field_mut().value_mut() = None;
+
+  let rslt = table.write_insert::<InitialInsertValue>(
+    &mut <_>::default(),
+    &mut buffer,
+    MySqlDialect,
+    &mut SqlWriterMode::parameterized(),
+    InsertOptions::new(),
+    &mut None,
+  );
+  assert!(matches!(rslt, Err(crate::Error::UnsupportedGeneratedKey(ColumnType::I64))));
+
+  buffer.clear();
+  let rslt = table.write_insert::<InitialInsertValue>(
+    &mut <_>::default(),
+    &mut buffer,
+    PostgresDialect,
+    &mut SqlWriterMode::Inline,
+    InsertOptions::new(),
+    &mut None,
+  );
+  assert!(matches!(rslt, Err(crate::Error::UnsupportedGeneratedKey(ColumnType::I64))));
+
+  buffer.clear();
+  table
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut SqlWriterMode::parameterized(),
+      InsertOptions::new(),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"INSERT INTO "b" ("name") VALUES ($1) RETURNING "id";INSERT INTO "a" ("id","name","id_b") VALUES ($1,$2,$3);"#
+  );
+}
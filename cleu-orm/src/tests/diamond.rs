@@ -32,8 +32,10 @@
 //        |--> A
 
 use crate::{
-  FromSuffixRslt, InitialInsertValue, NoTableAssociation, SelectLimit, SelectOrderBy, SqlWriter,
-  Suffix, Table, TableAssociation, TableAssociationWrapper, TableDefs, TableField,
+  BindValue, ColumnType, EagerLoading, FromSuffixRslt, InitialInsertValue, InsertOptions,
+  MySqlDialect, NoTableAssociation, OnConflict, PostgresDialect, Schema, SelectLimit, SqlValue,
+  SqlWriter, SqlWriterMode, SqliteDialect, Suffix, Table, TableAssociation,
+  TableAssociationWrapper, TableDefs, TableField,
 };
 use core::mem;
 
@@ -51,7 +53,7 @@ struct A {
 struct ATableDefs;
 
 impl<'entity> TableDefs<'entity> for ATableDefs {
-  const PRIMARY_KEY_NAME: &'static str = "id";
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
   const TABLE_NAME: &'static str = "a";
 
   type Associations = NoTableAssociation<()>;
@@ -80,7 +82,7 @@ struct B {
 struct BTableDefs;
 
 impl<'entity> TableDefs<'entity> for BTableDefs {
-  const PRIMARY_KEY_NAME: &'static str = "id";
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
   const TABLE_NAME: &'static str = "b";
 
   type Associations =
@@ -119,7 +121,7 @@ struct C {
 struct CTableDefs;
 
 impl<'entity> TableDefs<'entity> for CTableDefs {
-  const PRIMARY_KEY_NAME: &'static str = "id";
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
   const TABLE_NAME: &'static str = "c";
 
   type Associations =
@@ -159,7 +161,7 @@ struct D {
 struct DTableDefs;
 
 impl<'entity> TableDefs<'entity> for DTableDefs {
-  const PRIMARY_KEY_NAME: &'static str = "id";
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
   const TABLE_NAME: &'static str = "d";
 
   type Associations = (
@@ -203,9 +205,9 @@ impl<'entity> TableDefs<'entity> for DTableDefs {
 #[test]
 fn assert_sizes() {
   assert_eq!(mem::size_of::<Table<'_, ATableDefs>>(), 64);
-  assert_eq!(mem::size_of::<Table<'_, BTableDefs>>(), 224);
-  assert_eq!(mem::size_of::<Table<'_, CTableDefs>>(), 224);
-  assert_eq!(mem::size_of::<Table<'_, DTableDefs>>(), 1024);
+  assert_eq!(mem::size_of::<Table<'_, BTableDefs>>(), 296);
+  assert_eq!(mem::size_of::<Table<'_, CTableDefs>>(), 296);
+  assert_eq!(mem::size_of::<Table<'_, DTableDefs>>(), 1456);
 }
 
 #[test]
@@ -214,38 +216,280 @@ fn multi_referred_table_has_correct_statements() {
   let mut d_table_defs = Table::<DTableDefs>::default();
 
   d_table_defs
-    .write_select(&mut buffer, SelectOrderBy::Ascending, SelectLimit::All, &mut |_| Ok(()))
+    .write_select(
+      &mut buffer,
+      PostgresDialect,
+      EagerLoading::all(),
+      &[],
+      SelectLimit::All,
+      &mut |_| Ok(()),
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"SELECT "d0"."id" AS "d0__id","d0"."name" AS "d0__name","b1"."id" AS "b1__id","b1"."name" AS "b1__name","a2"."id" AS "a2__id","a2"."name" AS "a2__name","c2"."id" AS "c2__id","c2"."name" AS "c2__name","a3"."id" AS "a3__id","a3"."name" AS "a3__name" FROM "d" AS "d0" LEFT JOIN "b" AS "b1" ON "d0"."id" = "b1"."id_d" LEFT JOIN "c" AS "c2" ON "d0"."id" = "c2"."id_d" LEFT JOIN "a" AS "a2" ON "b1"."id" = "a2"."id_b" LEFT JOIN "a" AS "a3" ON "c2"."id" = "a3"."id_c"  ORDER BY "d0"."id","b1"."id","a2"."id","c2"."id","a3"."id" LIMIT ALL"#
+  );
+
+  d_table_defs.update_all_table_fields(&D);
+
+  buffer.clear();
+  d_table_defs
+    .write_delete(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"DELETE FROM "a" WHERE "id"='1';DELETE FROM "b" WHERE "id"='2';DELETE FROM "c" WHERE "id"='3';DELETE FROM "d" WHERE "id"='4';"#
+  );
+
+  buffer.clear();
+  d_table_defs
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut SqlWriterMode::Inline,
+      InsertOptions::new(),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"INSERT INTO "d" ("id","name") VALUES ('4','foo4');INSERT INTO "b" ("id","name","id_d") VALUES ('2','foo2','4');INSERT INTO "c" ("id","name","id_d") VALUES ('3','foo3','4');INSERT INTO "a" ("id","name","id_b","id_c") VALUES ('1','foo1','2','3');"#
+  );
+
+  buffer.clear();
+  d_table_defs
+    .write_update(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"UPDATE "d" SET "id"='4',"name"='foo4' WHERE "id"='4';UPDATE "b" SET "id"='2',"name"='foo2' WHERE "id"='2';UPDATE "a" SET "id"='1',"name"='foo1' WHERE "id"='1';UPDATE "c" SET "id"='3',"name"='foo3' WHERE "id"='3';"#
+  );
+}
+
+#[test]
+fn multi_referred_table_has_correct_my_sql_statements() {
+  let mut buffer = String::new();
+  let mut d_table_defs = Table::<DTableDefs>::default();
+
+  d_table_defs
+    .write_select(
+      &mut buffer,
+      MySqlDialect,
+      EagerLoading::all(),
+      &[],
+      SelectLimit::All,
+      &mut |_| Ok(()),
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    "SELECT `d0`.`id` AS `d0__id`,`d0`.`name` AS `d0__name`,`b1`.`id` AS `b1__id`,`b1`.`name` AS \
+     `b1__name`,`a2`.`id` AS `a2__id`,`a2`.`name` AS `a2__name`,`c2`.`id` AS `c2__id`,`c2`.`name` \
+     AS `c2__name`,`a3`.`id` AS `a3__id`,`a3`.`name` AS `a3__name` FROM `d` AS `d0` LEFT JOIN `b` \
+     AS `b1` ON `d0`.`id` = `b1`.`id_d` LEFT JOIN `c` AS `c2` ON `d0`.`id` = `c2`.`id_d` LEFT \
+     JOIN `a` AS `a2` ON `b1`.`id` = `a2`.`id_b` LEFT JOIN `a` AS `a3` ON `c2`.`id` = `a3`.`id_c`  \
+     ORDER BY `d0`.`id`,`b1`.`id`,`a2`.`id`,`c2`.`id`,`a3`.`id`"
+  );
+
+  buffer.clear();
+  d_table_defs
+    .write_select(
+      &mut buffer,
+      MySqlDialect,
+      EagerLoading::all(),
+      &[
+        d_table_defs.column(&d_table_defs.fields().0).desc().nulls_last(),
+        d_table_defs.associations().1.guide.id_column().desc(),
+      ],
+      SelectLimit::Count(2),
+      &mut |_| Ok(()),
+    )
+    .unwrap();
+  assert!(buffer.contains(
+    " FROM (SELECT `d0`.* FROM `d` AS `d0` GROUP BY `d0`.`id` ORDER BY `d0`.`name` IS NULL \
+     ASC,`d0`.`name` DESC,`d0`.`id` LIMIT 2) AS `d0` LEFT JOIN "
+  ));
+  assert!(buffer.ends_with(
+    " ORDER BY `d0`.`name` IS NULL ASC,`d0`.`name` DESC,`d0`.`id`,`b1`.`id`,`a2`.`id`,`c2`.`id` \
+     DESC,`a3`.`id`"
+  ));
+
+  d_table_defs.update_all_table_fields(&D);
+
+  buffer.clear();
+  d_table_defs
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      MySqlDialect,
+      &mut SqlWriterMode::Inline,
+      InsertOptions::new(),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"INSERT INTO `d` (`id`,`name`) VALUES ('4','foo4');INSERT INTO `b` (`id`,`name`,`id_d`) VALUES ('2','foo2','4');INSERT INTO `c` (`id`,`name`,`id_d`) VALUES ('3','foo3','4');INSERT INTO `a` (`id`,`name`,`id_b`,`id_c`) VALUES ('1','foo1','2','3');"#
+  );
+
+  buffer.clear();
+  d_table_defs
+    .write_update(
+      &mut <_>::default(),
+      &mut buffer,
+      MySqlDialect,
+      &mut SqlWriterMode::parameterized(),
+    )
     .unwrap();
   assert_eq!(
     &buffer,
-    r#"SELECT "d0".id AS d0__id,"d0".name AS d0__name,"b1".id AS b1__id,"b1".name AS b1__name,"a2".id AS a2__id,"a2".name AS a2__name,"c2".id AS c2__id,"c2".name AS c2__name,"a3".id AS a3__id,"a3".name AS a3__name FROM "d" AS "d0" LEFT JOIN "b" AS "b1" ON "d0".id = "b1".id_d LEFT JOIN "c" AS "c2" ON "d0".id = "c2".id_d LEFT JOIN "a" AS "a2" ON "b1".id = "a2".id_b LEFT JOIN "a" AS "a3" ON "c2".id = "a3".id_c  ORDER BY "d0".id,"b1".id,"a2".id,"c2".id,"a3".id ASC LIMIT ALL"#
+    "UPDATE `d` SET `id`=?,`name`=? WHERE `id`=?;UPDATE `b` SET `id`=?,`name`=? WHERE \
+     `id`=?;UPDATE `a` SET `id`=?,`name`=? WHERE `id`=?;UPDATE `c` SET `id`=?,`name`=? WHERE \
+     `id`=?;"
+  );
+
+  buffer.clear();
+  r"foo\'bar".write(&mut buffer, MySqlDialect).unwrap();
+  true.write(&mut buffer, MySqlDialect).unwrap();
+  [1u8, 171].as_slice().write(&mut buffer, MySqlDialect).unwrap();
+  assert_eq!(&buffer, r"'foo\\''bar'TRUEX'01ab'");
+}
+
+#[test]
+fn multi_referred_table_has_correct_sqlite_statements() {
+  let mut buffer = String::new();
+  let mut d_table_defs = Table::<DTableDefs>::default();
+
+  d_table_defs
+    .write_select(
+      &mut buffer,
+      SqliteDialect,
+      EagerLoading::all(),
+      &[],
+      SelectLimit::All,
+      &mut |_| Ok(()),
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"SELECT "d0"."id" AS "d0__id","d0"."name" AS "d0__name","b1"."id" AS "b1__id","b1"."name" AS "b1__name","a2"."id" AS "a2__id","a2"."name" AS "a2__name","c2"."id" AS "c2__id","c2"."name" AS "c2__name","a3"."id" AS "a3__id","a3"."name" AS "a3__name" FROM "d" AS "d0" LEFT JOIN "b" AS "b1" ON "d0"."id" = "b1"."id_d" LEFT JOIN "c" AS "c2" ON "d0"."id" = "c2"."id_d" LEFT JOIN "a" AS "a2" ON "b1"."id" = "a2"."id_b" LEFT JOIN "a" AS "a3" ON "c2"."id" = "a3"."id_c"  ORDER BY "d0"."id","b1"."id","a2"."id","c2"."id","a3"."id""#
   );
 
   d_table_defs.update_all_table_fields(&D);
 
   buffer.clear();
-  d_table_defs.write_delete(&mut <_>::default(), &mut buffer).unwrap();
+  d_table_defs
+    .write_delete(&mut <_>::default(), &mut buffer, SqliteDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
   assert_eq!(
     &buffer,
-    r#"DELETE FROM a WHERE id='1';DELETE FROM b WHERE id='2';DELETE FROM c WHERE id='3';DELETE FROM d WHERE id='4';"#
+    r#"DELETE FROM "a" WHERE "id"='1';DELETE FROM "b" WHERE "id"='2';DELETE FROM "c" WHERE "id"='3';DELETE FROM "d" WHERE "id"='4';"#
   );
 
   buffer.clear();
   d_table_defs
-    .write_insert::<InitialInsertValue>(&mut <_>::default(), &mut buffer, &mut None)
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      SqliteDialect,
+      &mut SqlWriterMode::parameterized(),
+      InsertOptions::new(),
+      &mut None,
+    )
     .unwrap();
   assert_eq!(
     &buffer,
-    // FIXME
-    // INSERT INTO "d" (id,name) VALUES ('4','foo4');INSERT INTO "b" (id,name,id_d) VALUES ('2','foo2','4');INSERT INTO "c" (id,name,id_d) VALUES ('3','foo3','4');INSERT INTO "a" (id,name,id_b,id_c) VALUES ('1','foo1','2','3');
-    r#"INSERT INTO "d" (id,name) VALUES ('4','foo4');INSERT INTO "b" (id,name,id_d) VALUES ('2','foo2','4');INSERT INTO "a" (id,name,id_b) VALUES ('1','foo1','2');INSERT INTO "c" (id,name,id_d) VALUES ('3','foo3','4');"#
+    r#"INSERT INTO "d" ("id","name") VALUES (?1,?2);INSERT INTO "b" ("id","name","id_d") VALUES (?1,?2,?3);INSERT INTO "c" ("id","name","id_d") VALUES (?1,?2,?3);INSERT INTO "a" ("id","name","id_b","id_c") VALUES (?1,?2,?3,?4);"#
+  );
+
+  buffer.clear();
+  r"foo\'bar".write(&mut buffer, SqliteDialect).unwrap();
+  false.write(&mut buffer, SqliteDialect).unwrap();
+  [1u8, 171].as_slice().write(&mut buffer, SqliteDialect).unwrap();
+  assert_eq!(&buffer, r"'foo\''bar'0X'01ab'");
+}
+
+#[test]
+fn multi_referred_table_has_correct_parameterized_statements() {
+  let mut buffer = String::new();
+  let mut d_table_defs = Table::<DTableDefs>::default();
+  d_table_defs.update_all_table_fields(&D);
+
+  let mut mode = SqlWriterMode::parameterized();
+  d_table_defs.write_delete(&mut <_>::default(), &mut buffer, PostgresDialect, &mut mode).unwrap();
+  assert_eq!(
+    mode.bind_arguments().unwrap().statements(&buffer).collect::<Vec<_>>(),
+    [
+      (r#"DELETE FROM "a" WHERE "id"=$1;"#, &[BindValue::I32(1)][..]),
+      (r#"DELETE FROM "b" WHERE "id"=$1;"#, &[BindValue::I32(2)][..]),
+      (r#"DELETE FROM "c" WHERE "id"=$1;"#, &[BindValue::I32(3)][..]),
+      (r#"DELETE FROM "d" WHERE "id"=$1;"#, &[BindValue::I32(4)][..]),
+    ]
   );
 
   buffer.clear();
-  d_table_defs.write_update(&mut <_>::default(), &mut buffer).unwrap();
+  let mut mode = SqlWriterMode::parameterized();
+  d_table_defs
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut mode,
+      InsertOptions::new(),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(
+    mode.bind_arguments().unwrap().statements(&buffer).collect::<Vec<_>>(),
+    [
+      (
+        r#"INSERT INTO "d" ("id","name") VALUES ($1,$2);"#,
+        &[BindValue::I32(4), BindValue::String("foo4".into())][..]
+      ),
+      (
+        r#"INSERT INTO "b" ("id","name","id_d") VALUES ($1,$2,$3);"#,
+        &[BindValue::I32(2), BindValue::String("foo2".into()), BindValue::I32(4)][..]
+      ),
+      (
+        r#"INSERT INTO "c" ("id","name","id_d") VALUES ($1,$2,$3);"#,
+        &[BindValue::I32(3), BindValue::String("foo3".into()), BindValue::I32(4)][..]
+      ),
+      (
+        r#"INSERT INTO "a" ("id","name","id_b","id_c") VALUES ($1,$2,$3,$4);"#,
+        &[
+          BindValue::I32(1),
+          BindValue::String("foo1".into()),
+          BindValue::I32(2),
+          BindValue::I32(3)
+        ][..]
+      ),
+    ]
+  );
+
+  buffer.clear();
+  let mut mode = SqlWriterMode::parameterized();
+  d_table_defs.write_update(&mut <_>::default(), &mut buffer, PostgresDialect, &mut mode).unwrap();
   assert_eq!(
     &buffer,
-    r#"UPDATE d SET id='4',name='foo4' WHERE id='4';UPDATE b SET id='2',name='foo2' WHERE id='2';UPDATE a SET id='1',name='foo1' WHERE id='1';UPDATE c SET id='3',name='foo3' WHERE id='3';"#
+    r#"UPDATE "d" SET "id"=$1,"name"=$2 WHERE "id"=$3;UPDATE "b" SET "id"=$1,"name"=$2 WHERE "id"=$3;UPDATE "a" SET "id"=$1,"name"=$2 WHERE "id"=$3;UPDATE "c" SET "id"=$1,"name"=$2 WHERE "id"=$3;"#
+  );
+  assert_eq!(
+    mode.bind_arguments().unwrap().values(),
+    [
+      BindValue::I32(4),
+      BindValue::String("foo4".into()),
+      BindValue::I32(4),
+      BindValue::I32(2),
+      BindValue::String("foo2".into()),
+      BindValue::I32(2),
+      BindValue::I32(1),
+      BindValue::String("foo1".into()),
+      BindValue::I32(1),
+      BindValue::I32(3),
+      BindValue::String("foo3".into()),
+      BindValue::I32(3),
+    ]
   );
 }
 
@@ -254,33 +498,51 @@ fn referred_table_has_correct_statements() {
   let mut buffer = String::new();
   let mut b_table_defs = Table::<BTableDefs>::default();
   b_table_defs
-    .write_select(&mut buffer, SelectOrderBy::Ascending, SelectLimit::All, &mut |_| Ok(()))
+    .write_select(
+      &mut buffer,
+      PostgresDialect,
+      EagerLoading::all(),
+      &[],
+      SelectLimit::All,
+      &mut |_| Ok(()),
+    )
     .unwrap();
   assert_eq!(
     &buffer,
-    r#"SELECT "b0".id AS b0__id,"b0".name AS b0__name,"a1".id AS a1__id,"a1".name AS a1__name FROM "b" AS "b0" LEFT JOIN "a" AS "a1" ON "b0".id = "a1".id_b  ORDER BY "b0".id,"a1".id ASC LIMIT ALL"#
+    r#"SELECT "b0"."id" AS "b0__id","b0"."name" AS "b0__name","a1"."id" AS "a1__id","a1"."name" AS "a1__name" FROM "b" AS "b0" LEFT JOIN "a" AS "a1" ON "b0"."id" = "a1"."id_b"  ORDER BY "b0"."id","a1"."id" LIMIT ALL"#
   );
 
   b_table_defs.update_all_table_fields(&B);
 
   buffer.clear();
-  b_table_defs.write_delete(&mut <_>::default(), &mut buffer).unwrap();
-  assert_eq!(&buffer, r#"DELETE FROM a WHERE id='1';DELETE FROM b WHERE id='2';"#);
+  b_table_defs
+    .write_delete(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
+  assert_eq!(&buffer, r#"DELETE FROM "a" WHERE "id"='1';DELETE FROM "b" WHERE "id"='2';"#);
 
   buffer.clear();
   b_table_defs
-    .write_insert::<InitialInsertValue>(&mut <_>::default(), &mut buffer, &mut None)
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut SqlWriterMode::Inline,
+      InsertOptions::new(),
+      &mut None,
+    )
     .unwrap();
   assert_eq!(
     &buffer,
-    r#"INSERT INTO "b" (id,name) VALUES ('2','foo2');INSERT INTO "a" (id,name,id_b) VALUES ('1','foo1','2');"#
+    r#"INSERT INTO "b" ("id","name") VALUES ('2','foo2');INSERT INTO "a" ("id","name","id_b") VALUES ('1','foo1','2');"#
   );
 
   buffer.clear();
-  b_table_defs.write_update(&mut <_>::default(), &mut buffer).unwrap();
+  b_table_defs
+    .write_update(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
   assert_eq!(
     &buffer,
-    r#"UPDATE b SET id='2',name='foo2' WHERE id='2';UPDATE a SET id='1',name='foo1' WHERE id='1';"#
+    r#"UPDATE "b" SET "id"='2',"name"='foo2' WHERE "id"='2';UPDATE "a" SET "id"='1',"name"='foo1' WHERE "id"='1';"#
   );
 }
 
@@ -289,26 +551,279 @@ fn standalone_table_has_correct_statements() {
   let mut buffer = String::new();
   let mut a_table_defs = Table::<ATableDefs>::default();
   a_table_defs
-    .write_select(&mut buffer, SelectOrderBy::Ascending, SelectLimit::All, &mut |_| Ok(()))
+    .write_select(
+      &mut buffer,
+      PostgresDialect,
+      EagerLoading::all(),
+      &[],
+      SelectLimit::All,
+      &mut |_| Ok(()),
+    )
     .unwrap();
   assert_eq!(
     &buffer,
-    r#"SELECT "a0".id AS a0__id,"a0".name AS a0__name FROM "a" AS "a0"  ORDER BY "a0".id ASC LIMIT ALL"#
+    r#"SELECT "a0"."id" AS "a0__id","a0"."name" AS "a0__name" FROM "a" AS "a0"  ORDER BY "a0"."id" LIMIT ALL"#
   );
 
   a_table_defs.update_all_table_fields(&A);
 
   buffer.clear();
-  a_table_defs.write_delete(&mut <_>::default(), &mut buffer).unwrap();
-  assert_eq!(&buffer, r#"DELETE FROM a WHERE id='1';"#);
+  a_table_defs
+    .write_delete(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
+  assert_eq!(&buffer, r#"DELETE FROM "a" WHERE "id"='1';"#);
+
+  buffer.clear();
+  a_table_defs
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut SqlWriterMode::Inline,
+      InsertOptions::new(),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(&buffer, r#"INSERT INTO "a" ("id","name") VALUES ('1','foo1');"#);
 
   buffer.clear();
   a_table_defs
-    .write_insert::<InitialInsertValue>(&mut <_>::default(), &mut buffer, &mut None)
+    .write_update(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
+  assert_eq!(&buffer, r#"UPDATE "a" SET "id"='1',"name"='foo1' WHERE "id"='1';"#);
+}
+
+#[test]
+fn filters_resolve_suffixed_aliases() {
+  let mut buffer = String::new();
+  let d_table_defs = Table::<DTableDefs>::default();
+  let b_table_defs = &d_table_defs.associations().0.guide;
+  let a_table_defs = &b_table_defs.associations().0.guide;
+
+  let filter = d_table_defs
+    .column(&d_table_defs.fields().0)
+    .like("foo%")
+    .and(
+      b_table_defs
+        .id_column()
+        .is_in([&2, &3])
+        .or(!a_table_defs.column(&a_table_defs.fields().0).is_null()),
+    )
+    .and(a_table_defs.id_column().ne(&1));
+  filter.write(&mut buffer, PostgresDialect, &mut SqlWriterMode::Inline).unwrap();
+  assert_eq!(
+    &buffer,
+    r#"(("d0"."name" LIKE 'foo%' AND ("b1"."id" IN ('2','3') OR NOT ("a2"."name" IS NULL))) AND "a2"."id" <> '1')"#
+  );
+
+  buffer.clear();
+  let mut mode = SqlWriterMode::parameterized();
+  d_table_defs
+    .write_select(&mut buffer, MySqlDialect, EagerLoading::all(), &[], SelectLimit::All, &mut |b| {
+      filter.write(b, MySqlDialect, &mut mode)?;
+      Ok(())
+    })
+    .unwrap();
+  assert!(buffer.contains(
+    "WHERE ((`d0`.`name` LIKE ? AND (`b1`.`id` IN (?,?) OR NOT (`a2`.`name` IS NULL))) AND \
+     `a2`.`id` <> ?) ORDER BY"
+  ));
+  assert_eq!(
+    mode.bind_arguments().unwrap().values(),
+    [BindValue::String("foo%".into()), BindValue::I32(2), BindValue::I32(3), BindValue::I32(1)]
+  );
+
+  buffer.clear();
+  let empty: [&i32; 0] = [];
+  let filter = d_table_defs
+    .id_column()
+    .is_in(empty)
+    .or(d_table_defs.id_column().lt(&4).and(d_table_defs.id_column().gt(&0)));
+  filter.write(&mut buffer, SqliteDialect, &mut SqlWriterMode::Inline).unwrap();
+  assert_eq!(&buffer, r#"(1 = 0 OR ("d0"."id" < '4' AND "d0"."id" > '0'))"#);
+}
+
+#[test]
+fn orders_by_keep_primary_keys_grouped() {
+  let mut buffer = String::new();
+  let d_table_defs = Table::<DTableDefs>::default();
+  let b_table_defs = &d_table_defs.associations().0.guide;
+  let a_table_defs = &b_table_defs.associations().0.guide;
+
+  d_table_defs
+    .write_select(
+      &mut buffer,
+      PostgresDialect,
+      EagerLoading::all(),
+      &[
+        a_table_defs.column(&a_table_defs.fields().0).asc().nulls_first(),
+        d_table_defs.id_column().desc(),
+        d_table_defs.column(&d_table_defs.fields().0).asc(),
+      ],
+      SelectLimit::All,
+      &mut |_| Ok(()),
+    )
+    .unwrap();
+  assert!(buffer.ends_with(
+    r#" ORDER BY "d0"."id" DESC,"d0"."name" ASC,"b1"."id","a2"."name" ASC NULLS FIRST,"a2"."id","c2"."id","a3"."id" LIMIT ALL"#
+  ));
+}
+
+#[test]
+fn limits_count_root_entities() {
+  let mut buffer = String::new();
+  let d_table_defs = Table::<DTableDefs>::default();
+  let c_table_defs = &d_table_defs.associations().1.guide;
+  let filter =
+    c_table_defs.column(&c_table_defs.fields().0).eq("foo").and(d_table_defs.id_column().gt(&4));
+  let mut mode = SqlWriterMode::parameterized();
+
+  d_table_defs
+    .write_select(
+      &mut buffer,
+      PostgresDialect,
+      EagerLoading::all(),
+      &[],
+      SelectLimit::Offset { count: 10, offset: 20 },
+      &mut |b| {
+        filter.write(b, PostgresDialect, &mut mode)?;
+        Ok(())
+      },
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"SELECT "d0"."id" AS "d0__id","d0"."name" AS "d0__name","b1"."id" AS "b1__id","b1"."name" AS "b1__name","a2"."id" AS "a2__id","a2"."name" AS "a2__name","c2"."id" AS "c2__id","c2"."name" AS "c2__name","a3"."id" AS "a3__id","a3"."name" AS "a3__name" FROM (SELECT "d0".* FROM "d" AS "d0" LEFT JOIN "b" AS "b1" ON "d0"."id" = "b1"."id_d" LEFT JOIN "c" AS "c2" ON "d0"."id" = "c2"."id_d" LEFT JOIN "a" AS "a2" ON "b1"."id" = "a2"."id_b" LEFT JOIN "a" AS "a3" ON "c2"."id" = "a3"."id_c"  WHERE ("c2"."name" = $1 AND "d0"."id" > $2) GROUP BY "d0"."id" ORDER BY "d0"."id" LIMIT 10 OFFSET 20) AS "d0" LEFT JOIN "b" AS "b1" ON "d0"."id" = "b1"."id_d" LEFT JOIN "c" AS "c2" ON "d0"."id" = "c2"."id_d" LEFT JOIN "a" AS "a2" ON "b1"."id" = "a2"."id_b" LEFT JOIN "a" AS "a3" ON "c2"."id" = "a3"."id_c"  ORDER BY "d0"."id","b1"."id","a2"."id","c2"."id","a3"."id""#
+  );
+  assert_eq!(
+    mode.bind_arguments().unwrap().values(),
+    [BindValue::String("foo".into()), BindValue::I32(4)]
+  );
+}
+
+#[test]
+fn eager_loading_prunes_associations() {
+  let mut buffer = String::new();
+  let d_table_defs = Table::<DTableDefs>::default();
+  let excluded = [d_table_defs.associations().1.guide.table_ref()];
+
+  d_table_defs
+    .write_select(
+      &mut buffer,
+      PostgresDialect,
+      EagerLoading::all().with_excluded(&excluded).with_max_depth(1),
+      &[],
+      SelectLimit::All,
+      &mut |_| Ok(()),
+    )
     .unwrap();
-  assert_eq!(&buffer, r#"INSERT INTO "a" (id,name) VALUES ('1','foo1');"#);
+  assert_eq!(
+    &buffer,
+    r#"SELECT "d0"."id" AS "d0__id","d0"."name" AS "d0__name","b1"."id" AS "b1__id","b1"."name" AS "b1__name" FROM "d" AS "d0" LEFT JOIN "b" AS "b1" ON "d0"."id" = "b1"."id_d"  ORDER BY "d0"."id","b1"."id" LIMIT ALL"#
+  );
 
   buffer.clear();
-  a_table_defs.write_update(&mut <_>::default(), &mut buffer).unwrap();
-  assert_eq!(&buffer, r#"UPDATE a SET id='1',name='foo1' WHERE id='1';"#);
+  d_table_defs
+    .write_select(
+      &mut buffer,
+      PostgresDialect,
+      EagerLoading::none(),
+      &[],
+      SelectLimit::All,
+      &mut |_| Ok(()),
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"SELECT "d0"."id" AS "d0__id","d0"."name" AS "d0__name" FROM "d" AS "d0"  ORDER BY "d0"."id" LIMIT ALL"#
+  );
+}
+
+#[test]
+fn upserts_cover_all_tables() {
+  let mut buffer = String::new();
+  let mut d_table_defs = Table::<DTableDefs>::default();
+  d_table_defs.update_all_table_fields(&D);
+
+  d_table_defs
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut SqlWriterMode::Inline,
+      InsertOptions::new()
+        .with_on_conflict(OnConflict::do_update().with_targets(&[("a", &["name"])])),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"INSERT INTO "d" ("id","name") VALUES ('4','foo4') ON CONFLICT ("id") DO UPDATE SET "name"=EXCLUDED."name";INSERT INTO "b" ("id","name","id_d") VALUES ('2','foo2','4') ON CONFLICT ("id") DO UPDATE SET "name"=EXCLUDED."name";INSERT INTO "c" ("id","name","id_d") VALUES ('3','foo3','4') ON CONFLICT ("id") DO UPDATE SET "name"=EXCLUDED."name";INSERT INTO "a" ("id","name","id_b","id_c") VALUES ('1','foo1','2','3') ON CONFLICT ("name") DO UPDATE SET "name"=EXCLUDED."name";"#
+  );
+
+  buffer.clear();
+  d_table_defs
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      MySqlDialect,
+      &mut SqlWriterMode::Inline,
+      InsertOptions::new().with_on_conflict(OnConflict::do_nothing()),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    "INSERT INTO `d` (`id`,`name`) VALUES ('4','foo4') ON DUPLICATE KEY UPDATE `id`=`id`;INSERT \
+     INTO `b` (`id`,`name`,`id_d`) VALUES ('2','foo2','4') ON DUPLICATE KEY UPDATE \
+     `id`=`id`;INSERT INTO `c` (`id`,`name`,`id_d`) VALUES ('3','foo3','4') ON DUPLICATE KEY \
+     UPDATE `id`=`id`;INSERT INTO `a` (`id`,`name`,`id_b`,`id_c`) VALUES ('1','foo1','2','3') ON \
+     DUPLICATE KEY UPDATE `id`=`id`;"
+  );
+}
+
+#[test]
+fn multi_referred_tables_receive_generated_keys_of_all_parents() {
+  let mut buffer = String::new();
+  let mut d_table_defs = Table::<DTableDefs>::default();
+  d_table_defs.update_all_table_fields(&D);
+  *d_table_defs.associations_mut().0.tables[0].id_field_mut().value_mut() = None;
+
+  let mut mode = SqlWriterMode::parameterized();
+  d_table_defs
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut mode,
+      InsertOptions::new(),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"INSERT INTO "d" ("id","name") VALUES ($1,$2);INSERT INTO "b" ("name","id_d") VALUES ($1,$2) RETURNING "id";INSERT INTO "c" ("id","name","id_d") VALUES ($1,$2,$3);INSERT INTO "a" ("id","name","id_b","id_c") VALUES ($1,$2,$3,$4);"#
+  );
+  let args = mode.bind_arguments().unwrap();
+  assert_eq!(args.returned_key(1), Some(ColumnType::I32));
+  assert_eq!(
+    args.values_with_generated_keys(3, &[vec![], vec![BindValue::I32(2)], vec![]]).unwrap(),
+    [BindValue::I32(1), BindValue::String("foo1".into()), BindValue::I32(2), BindValue::I32(3)]
+  );
+  assert!(matches!(
+    args.values_with_generated_keys(3, &[vec![], vec![], vec![]]),
+    Err(crate::Error::MissingGeneratedKey("b"))
+  ));
+}
+
+#[test]
+fn multi_referred_table_is_created_after_all_parents() {
+  let mut buffer = String::new();
+  let mut schema = Schema::new();
+  schema.push_table(&Table::<DTableDefs>::default()).unwrap();
+  schema.write_create_tables(&mut buffer, PostgresDialect, &mut SqlWriterMode::Inline).unwrap();
+  assert_eq!(
+    &buffer,
+    r#"CREATE TABLE "d" ("id" INTEGER NOT NULL,"name" TEXT NOT NULL,PRIMARY KEY ("id"));CREATE TABLE "b" ("id" INTEGER NOT NULL,"name" TEXT NOT NULL,"id_d" INTEGER NOT NULL,PRIMARY KEY ("id"),FOREIGN KEY ("id_d") REFERENCES "d" ("id"));CREATE TABLE "c" ("id" INTEGER NOT NULL,"name" TEXT NOT NULL,"id_d" INTEGER NOT NULL,PRIMARY KEY ("id"),FOREIGN KEY ("id_d") REFERENCES "d" ("id"));CREATE TABLE "a" ("id" INTEGER NOT NULL,"name" TEXT NOT NULL,"id_b" INTEGER NOT NULL,"id_c" INTEGER NOT NULL,PRIMARY KEY ("id"),FOREIGN KEY ("id_b") REFERENCES "b" ("id"),FOREIGN KEY ("id_c") REFERENCES "c" ("id"));"#
+  );
 }
@@ -0,0 +1,118 @@
+// CREATE TABLE authors (
+//   id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY NOT NULL,
+//   name TEXT NOT NULL
+// );
+//
+// CREATE TABLE books (
+//   id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY NOT NULL,
+//   author_id INTEGER NOT NULL REFERENCES authors(id),
+//   title TEXT NOT NULL
+// );
+//
+// Author --> Book
+
+use crate::{
+  FromSuffixRslt, MySqlDialect, NoTableAssociation, PostgresDialect, Schema, SqlWriterMode,
+  SqliteDialect, Suffix, Table, TableAssociation, TableAssociationWrapper, TableDefs, TableField,
+};
+
+pub(crate) struct Book {
+  pub(crate) id: i32,
+  pub(crate) title: String,
+}
+
+pub(crate) struct BookTableDefs;
+
+impl<'entity> TableDefs<'entity> for BookTableDefs {
+  const PRIMARY_KEY_IS_GENERATED: bool = true;
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "books";
+
+  type Associations = NoTableAssociation<crate::Error>;
+  type Entity = Book;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity i32;
+
+  fn type_instances(_: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (NoTableAssociation::new(), (TableField::new("title"),))
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.title);
+  }
+}
+
+pub(crate) struct Author {
+  pub(crate) books: Vec<Book>,
+  pub(crate) id: i32,
+  pub(crate) name: String,
+}
+
+pub(crate) struct AuthorTableDefs;
+
+impl<'entity> TableDefs<'entity> for AuthorTableDefs {
+  const PRIMARY_KEY_IS_GENERATED: bool = true;
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "authors";
+
+  type Associations =
+    (TableAssociationWrapper<'entity, BookTableDefs, Vec<Table<'entity, BookTableDefs>>>,);
+  type Entity = Author;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity i32;
+
+  fn type_instances(suffix: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (
+      (TableAssociationWrapper {
+        association: TableAssociation::new("id", "author_id"),
+        guide: Table::new(suffix + 1),
+        tables: vec![],
+      },),
+      (TableField::new("name"),),
+    )
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.name);
+
+    table.associations_mut().0.tables.clear();
+    for book in entity.books.iter() {
+      let mut elem = Table::new(table.suffix() + 1);
+      elem.update_all_table_fields(book);
+      table.associations_mut().0.tables.push(elem);
+    }
+  }
+}
+
+#[test]
+fn generated_keys_are_rendered_by_each_dialect() {
+  let mut buffer = String::new();
+  let mut schema = Schema::new();
+  schema.push_table(&Table::<AuthorTableDefs>::default()).unwrap();
+  schema.write_create_tables(&mut buffer, PostgresDialect, &mut SqlWriterMode::Inline).unwrap();
+  assert_eq!(
+    &buffer,
+    r#"CREATE TABLE "authors" ("id" INTEGER NOT NULL GENERATED BY DEFAULT AS IDENTITY,"name" TEXT NOT NULL,PRIMARY KEY ("id"));CREATE TABLE "books" ("id" INTEGER NOT NULL GENERATED BY DEFAULT AS IDENTITY,"title" TEXT NOT NULL,"author_id" INTEGER NOT NULL,PRIMARY KEY ("id"),FOREIGN KEY ("author_id") REFERENCES "authors" ("id"));"#
+  );
+
+  buffer.clear();
+  schema.write_create_tables(&mut buffer, MySqlDialect, &mut SqlWriterMode::Inline).unwrap();
+  assert_eq!(
+    &buffer,
+    r#"CREATE TABLE `authors` (`id` INT NOT NULL AUTO_INCREMENT,`name` VARCHAR(255) NOT NULL,PRIMARY KEY (`id`));CREATE TABLE `books` (`id` INT NOT NULL AUTO_INCREMENT,`title` VARCHAR(255) NOT NULL,`author_id` INT NOT NULL,PRIMARY KEY (`id`),FOREIGN KEY (`author_id`) REFERENCES `authors` (`id`));"#
+  );
+
+  // Single-column `INTEGER` primary keys are already generated by SQLite
+  buffer.clear();
+  schema.write_create_tables(&mut buffer, SqliteDialect, &mut SqlWriterMode::Inline).unwrap();
+  assert_eq!(
+    &buffer,
+    r#"CREATE TABLE "authors" ("id" INTEGER NOT NULL,"name" TEXT NOT NULL,PRIMARY KEY ("id"));CREATE TABLE "books" ("id" INTEGER NOT NULL,"title" TEXT NOT NULL,"author_id" INTEGER NOT NULL,PRIMARY KEY ("id"),FOREIGN KEY ("author_id") REFERENCES "authors" ("id"));"#
+  );
+}
@@ -0,0 +1,236 @@
+// CREATE TABLE author (
+//   id {key type} PRIMARY KEY NOT NULL,
+//   name TEXT NOT NULL
+// );
+//
+// CREATE TABLE book (
+//   id {key type} PRIMARY KEY NOT NULL,
+//   id_author {key type} NOT NULL REFERENCES author(id),
+//   name TEXT NOT NULL
+// );
+//
+// Author --> Book
+
+#![cfg(all(feature = "sqlx-sqlite", feature = "sqlx-runtime-tokio-rustls"))]
+
+use crate::{
+  seek_related_entities, write_column_alias, DbRow, DecodeColumn, DecodePrimaryKey, FromRowsSuffix,
+  FromSuffixRslt, NoTableAssociation, SelectedRow, SqlType, SqlValue, Suffix, Table,
+  TableAssociation, TableAssociationWrapper, TableDefs, TableField,
+};
+use core::{fmt::Debug, hash::Hash, marker::PhantomData};
+use sqlx_core::{
+  query::query,
+  sqlite::{SqlitePool, SqlitePoolOptions, SqliteRow},
+};
+
+#[derive(Debug, PartialEq)]
+struct Book<K> {
+  id: K,
+  name: String,
+}
+
+impl<B, K, ROW> FromRowsSuffix<B, ROW> for Book<K>
+where
+  B: cl_traits::String,
+  ROW: DbRow + DecodeColumn<K> + DecodeColumn<String>,
+{
+  type Error = crate::Error;
+
+  fn from_rows_suffix(
+    _: &[ROW],
+    buffer: &mut B,
+    suffix: Suffix,
+    target_row: &ROW,
+  ) -> Result<(usize, Self), Self::Error> {
+    write_column_alias(buffer, "book", suffix, "id")?;
+    let id = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    write_column_alias(buffer, "book", suffix, "name")?;
+    let name = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    Ok((1, Self { id, name }))
+  }
+}
+
+struct BookTableDefs<K>(PhantomData<K>);
+
+impl<'entity, K> TableDefs<'entity> for BookTableDefs<K>
+where
+  K: Hash + SqlType + SqlValue + 'entity,
+{
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "book";
+
+  type Associations = NoTableAssociation<crate::Error>;
+  type Entity = Book<K>;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity K;
+
+  fn type_instances(_: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (NoTableAssociation::new(), (TableField::new("name"),))
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.name);
+  }
+}
+
+#[derive(Debug, PartialEq)]
+struct Author<K> {
+  books: Vec<Book<K>>,
+  id: K,
+  name: String,
+}
+
+impl<B, K, ROW> FromRowsSuffix<B, ROW> for Author<K>
+where
+  B: cl_traits::String,
+  K: DecodePrimaryKey<ROW> + Hash + SqlType + SqlValue,
+  ROW: DbRow + DecodeColumn<K> + DecodeColumn<String>,
+{
+  type Error = crate::Error;
+
+  fn from_rows_suffix(
+    all_rows: &[ROW],
+    buffer: &mut B,
+    suffix: Suffix,
+    target_row: &ROW,
+  ) -> Result<(usize, Self), Self::Error> {
+    write_column_alias(buffer, "author", suffix, "id")?;
+    let id = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    write_column_alias(buffer, "author", suffix, "name")?;
+    let name = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    let mut books = Vec::new();
+    let skip = seek_related_entities::<_, _, Book<K>, _, BookTableDefs<K>, AuthorTableDefs<K>>(
+      buffer,
+      all_rows,
+      suffix,
+      suffix + 1,
+      AuthorTableDefs::<K>::type_instances(suffix).0 .0.association.join_kind(),
+      |book| {
+        books.push(book);
+        Ok(())
+      },
+    )?;
+    Ok((skip, Self { books, id, name }))
+  }
+}
+
+struct AuthorTableDefs<K>(PhantomData<K>);
+
+impl<'entity, K> TableDefs<'entity> for AuthorTableDefs<K>
+where
+  K: Hash + SqlType + SqlValue + 'entity,
+{
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "author";
+
+  type Associations =
+    (TableAssociationWrapper<'entity, BookTableDefs<K>, Vec<Table<'entity, BookTableDefs<K>>>>,);
+  type Entity = Author<K>;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity K;
+
+  fn type_instances(suffix: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (
+      (TableAssociationWrapper {
+        association: TableAssociation::new("id", "id_author"),
+        guide: Table::new(suffix + 1),
+        tables: vec![],
+      },),
+      (TableField::new("name"),),
+    )
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.name);
+
+    table.associations_mut().0.tables.clear();
+    for book in entity.books.iter() {
+      let mut elem = Table::new(table.suffix() + 1);
+      elem.update_all_table_fields(book);
+      table.associations_mut().0.tables.push(elem);
+    }
+  }
+}
+
+async fn pool(key_type: &str) -> SqlitePool {
+  let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+  let _ =
+    query(&format!("CREATE TABLE author (id {key_type} PRIMARY KEY NOT NULL, name TEXT NOT NULL)"))
+      .execute(&pool)
+      .await
+      .unwrap();
+  let _ = query(&format!(
+    "CREATE TABLE book (id {key_type} PRIMARY KEY NOT NULL, id_author {key_type} NOT NULL \
+     REFERENCES author(id), name TEXT NOT NULL)"
+  ))
+  .execute(&pool)
+  .await
+  .unwrap();
+  pool
+}
+
+async fn entities_are_grouped_by_key<K>(key_type: &str, keys: [K; 5])
+where
+  K: Debug + DecodePrimaryKey<SelectedRow<SqliteRow>> + Hash + PartialEq + SqlType + SqlValue,
+  SqliteRow: DecodeColumn<K>,
+{
+  let pool = pool(key_type).await;
+  let mut buffer = String::new();
+  let [first_id, second_id, first_book_id, second_book_id, third_book_id] = keys;
+  let first = Author {
+    books: vec![
+      Book { id: first_book_id, name: "foo1".into() },
+      Book { id: second_book_id, name: "foo2".into() },
+    ],
+    id: first_id,
+    name: "bar1".into(),
+  };
+  let second = Author {
+    books: vec![Book { id: third_book_id, name: "foo3".into() }],
+    id: second_id,
+    name: "bar2".into(),
+  };
+
+  let mut table = Table::<AuthorTableDefs<K>>::default();
+  table.create(&mut buffer, &pool, &first).await.unwrap();
+  table.create(&mut buffer, &pool, &second).await.unwrap();
+
+  let table = Table::<AuthorTableDefs<K>>::default();
+  let mut authors = table.read_all(&mut buffer, &pool).await.unwrap();
+  authors.sort_by(|a, b| a.name.cmp(&b.name));
+  assert_eq!(authors, [first, second]);
+}
+
+#[tokio::test]
+async fn sqlite_backend_groups_entities_with_text_keys() {
+  entities_are_grouped_by_key::<String>(
+    "TEXT",
+    ["b".into(), "a".into(), "book-1".into(), "book-2".into(), "book-3".into()],
+  )
+  .await;
+}
+
+#[cfg(feature = "uuid")]
+#[tokio::test]
+async fn sqlite_backend_groups_entities_with_uuid_keys() {
+  let keys = [
+    "a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8",
+    "6ee1bde4-3b85-4c9e-9d47-6bd1d7a6aa59",
+    "00000000-0000-0000-0000-000000000001",
+    "00000000-0000-0000-0000-000000000002",
+    "00000000-0000-0000-0000-000000000003",
+  ]
+  .map(|elem| uuid::Uuid::parse_str(elem).unwrap());
+  entities_are_grouped_by_key("BLOB", keys).await;
+}
@@ -0,0 +1,270 @@
+// CREATE TABLE groups (
+//   id INTEGER PRIMARY KEY NOT NULL,
+//   name TEXT NOT NULL
+// );
+//
+// CREATE TABLE users (
+//   id INTEGER PRIMARY KEY NOT NULL,
+//   name TEXT NOT NULL
+// );
+//
+// CREATE TABLE user_groups (
+//   group_id INTEGER NOT NULL REFERENCES groups(id),
+//   user_id INTEGER NOT NULL REFERENCES users(id),
+//   PRIMARY KEY (group_id, user_id)
+// );
+//
+// User <--> Group
+
+use crate::{
+  seek_related_entities, write_column_alias, DbRow, DecodeColumn, EagerLoading, FromRowsSuffix,
+  FromSuffixRslt, InitialInsertValue, InsertOptions, JoinTable, NoTableAssociation,
+  PostgresDialect, Schema, SelectLimit, SqlWriter, SqlWriterMode, Suffix, Table, TableAssociation,
+  TableAssociationWrapper, TableDefs, TableField,
+};
+
+#[derive(Debug, PartialEq)]
+struct Group {
+  id: i64,
+  name: String,
+}
+
+impl<B, ROW> FromRowsSuffix<B, ROW> for Group
+where
+  B: cl_traits::String,
+  ROW: DbRow + DecodeColumn<i64> + DecodeColumn<String>,
+{
+  type Error = crate::Error;
+
+  fn from_rows_suffix(
+    _: &[ROW],
+    buffer: &mut B,
+    suffix: Suffix,
+    target_row: &ROW,
+  ) -> Result<(usize, Self), Self::Error> {
+    write_column_alias(buffer, "groups", suffix, "id")?;
+    let id = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    write_column_alias(buffer, "groups", suffix, "name")?;
+    let name = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    Ok((1, Self { id, name }))
+  }
+}
+
+struct GroupTableDefs;
+
+impl<'entity> TableDefs<'entity> for GroupTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "groups";
+
+  type Associations = NoTableAssociation<crate::Error>;
+  type Entity = Group;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity i64;
+
+  fn type_instances(_: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (NoTableAssociation::new(), (TableField::new("name"),))
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.name);
+  }
+}
+
+#[derive(Debug, PartialEq)]
+struct User {
+  groups: Vec<Group>,
+  id: i64,
+  name: String,
+}
+
+impl<B, ROW> FromRowsSuffix<B, ROW> for User
+where
+  B: cl_traits::String,
+  ROW: DbRow + DecodeColumn<i64> + DecodeColumn<String>,
+{
+  type Error = crate::Error;
+
+  fn from_rows_suffix(
+    all_rows: &[ROW],
+    buffer: &mut B,
+    suffix: Suffix,
+    target_row: &ROW,
+  ) -> Result<(usize, Self), Self::Error> {
+    write_column_alias(buffer, "users", suffix, "id")?;
+    let id = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    write_column_alias(buffer, "users", suffix, "name")?;
+    let name = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    let mut groups = Vec::new();
+    let skip = seek_related_entities::<_, _, Group, _, GroupTableDefs, UserTableDefs>(
+      buffer,
+      all_rows,
+      suffix,
+      suffix + 1,
+      UserTableDefs::type_instances(suffix).0 .0.association.join_kind(),
+      |group| {
+        groups.push(group);
+        Ok(())
+      },
+    )?;
+    Ok((skip, Self { groups, id, name }))
+  }
+}
+
+struct UserTableDefs;
+
+impl<'entity> TableDefs<'entity> for UserTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "users";
+
+  type Associations =
+    (TableAssociationWrapper<'entity, GroupTableDefs, Vec<Table<'entity, GroupTableDefs>>>,);
+  type Entity = User;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity i64;
+
+  fn type_instances(suffix: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (
+      (TableAssociationWrapper {
+        association: TableAssociation::new("id", "id").with_join_table(JoinTable::new(
+          "user_id",
+          "user_groups",
+          "group_id",
+        )),
+        guide: Table::new(suffix + 1),
+        tables: vec![],
+      },),
+      (TableField::new("name"),),
+    )
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.name);
+
+    table.associations_mut().0.tables.clear();
+    for group in entity.groups.iter() {
+      let mut elem = Table::new(table.suffix() + 1);
+      elem.update_all_table_fields(group);
+      table.associations_mut().0.tables.push(elem);
+    }
+  }
+}
+
+fn users() -> [User; 2] {
+  [
+    User {
+      groups: vec![Group { id: 1, name: "foo".into() }, Group { id: 2, name: "bar".into() }],
+      id: 1,
+      name: "baz".into(),
+    },
+    User { groups: vec![Group { id: 2, name: "bar".into() }], id: 2, name: "qux".into() },
+  ]
+}
+
+#[test]
+fn join_tables_are_used_by_select_joins() {
+  let mut buffer = String::new();
+  let table = Table::<UserTableDefs>::default();
+  table
+    .write_select(
+      &mut buffer,
+      PostgresDialect,
+      EagerLoading::all(),
+      &[],
+      SelectLimit::All,
+      &mut |_| Ok(()),
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"SELECT "users0"."id" AS "users0__id","users0"."name" AS "users0__name","groups1"."id" AS "groups1__id","groups1"."name" AS "groups1__name" FROM "users" AS "users0" LEFT JOIN "user_groups" AS "user_groups1" ON "users0"."id" = "user_groups1"."user_id" LEFT JOIN "groups" AS "groups1" ON "user_groups1"."group_id" = "groups1"."id"  ORDER BY "users0"."id","groups1"."id" LIMIT ALL"#
+  );
+}
+
+#[test]
+fn join_rows_are_maintained_by_write_operations() {
+  let users = users();
+  let mut aux = <_>::default();
+  let mut buffer = String::new();
+  for user in &users {
+    let mut table = Table::<UserTableDefs>::default();
+    table.update_all_table_fields(user);
+    table
+      .write_insert::<InitialInsertValue>(
+        &mut aux,
+        &mut buffer,
+        PostgresDialect,
+        &mut SqlWriterMode::Inline,
+        InsertOptions::new().with_batch_size(2),
+        &mut None,
+      )
+      .unwrap();
+  }
+  assert_eq!(
+    &buffer,
+    r#"INSERT INTO "users" ("id","name") VALUES ('1','baz');INSERT INTO "groups" ("id","name") VALUES ('1','foo'),('2','bar');INSERT INTO "user_groups" ("user_id","group_id") VALUES ('1','1'),('1','2');INSERT INTO "users" ("id","name") VALUES ('2','qux');INSERT INTO "user_groups" ("user_id","group_id") VALUES ('2','2');"#
+  );
+
+  buffer.clear();
+  let mut table = Table::<UserTableDefs>::default();
+  table.update_all_table_fields(&users[0]);
+  table
+    .write_delete(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"DELETE FROM "user_groups" WHERE "user_id"='1';DELETE FROM "users" WHERE "id"='1';"#
+  );
+}
+
+#[test]
+fn join_tables_are_created_after_both_sides() {
+  let mut buffer = String::new();
+  let mut schema = Schema::new();
+  schema.push_table(&Table::<UserTableDefs>::default()).unwrap();
+  schema.write_create_tables(&mut buffer, PostgresDialect, &mut SqlWriterMode::Inline).unwrap();
+  assert_eq!(
+    &buffer,
+    r#"CREATE TABLE "users" ("id" BIGINT NOT NULL,"name" TEXT NOT NULL,PRIMARY KEY ("id"));CREATE TABLE "groups" ("id" BIGINT NOT NULL,"name" TEXT NOT NULL,PRIMARY KEY ("id"));CREATE TABLE "user_groups" ("user_id" BIGINT NOT NULL,"group_id" BIGINT NOT NULL,PRIMARY KEY ("user_id","group_id"),FOREIGN KEY ("user_id") REFERENCES "users" ("id"),FOREIGN KEY ("group_id") REFERENCES "groups" ("id"));"#
+  );
+}
+
+#[cfg(all(feature = "sqlx-sqlite", feature = "sqlx-runtime-tokio-rustls"))]
+#[tokio::test]
+async fn sqlite_backend_reads_and_writes_many_to_many_associations() {
+  use crate::OnConflict;
+  use sqlx_core::sqlite::SqlitePoolOptions;
+
+  let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+  let mut buffer = String::new();
+  let _ = Table::<UserTableDefs>::default().create_schema(&mut buffer, &pool).await.unwrap();
+  let [first, second] = users();
+
+  let mut table = Table::<UserTableDefs>::default();
+  table.create(&mut buffer, &pool, &first).await.unwrap();
+  // The shared group already exists so only the missing pairs are written
+  table.upsert(&mut buffer, &pool, OnConflict::do_nothing(), &second).await.unwrap();
+
+  let table = Table::<UserTableDefs>::default();
+  let mut users = table.read_all(&mut buffer, &pool).await.unwrap();
+  users.sort_by_key(|elem| elem.id);
+  assert_eq!(users, [first, second]);
+
+  let mut table = Table::<UserTableDefs>::default();
+  table.delete(&mut buffer, &pool, &users[0]).await.unwrap();
+  let table = Table::<UserTableDefs>::default();
+  let users = table.read_all(&mut buffer, &pool).await.unwrap();
+  assert_eq!(
+    users,
+    [User { groups: vec![Group { id: 2, name: "bar".into() }], id: 2, name: "qux".into() }]
+  );
+}
@@ -0,0 +1,394 @@
+// CREATE TABLE authors (
+//   id BIGINT PRIMARY KEY NOT NULL,
+//   name TEXT NOT NULL
+// );
+//
+// CREATE TABLE posts (
+//   id BIGINT PRIMARY KEY NOT NULL,
+//   title TEXT NOT NULL,
+//   author_id BIGINT NOT NULL REFERENCES authors(id)
+// );
+//
+// CREATE TABLE comments (
+//   id BIGINT PRIMARY KEY NOT NULL,
+//   body TEXT NOT NULL,
+//   post_id BIGINT NOT NULL REFERENCES posts(id)
+// );
+//
+// Author --> Post --> Comment
+
+use crate::{
+  migrations::{schema_changes, DbColumn, DbForeignKey, DbTable, Migration, SchemaChange},
+  tests::generated_key,
+  ColumnSchema, ColumnType, FromSuffixRslt, MySqlDialect, NoTableAssociation, PostgresDialect,
+  Schema, SqlWriterMode, SqliteDialect, Suffix, Table, TableAssociation, TableAssociationWrapper,
+  TableDefs, TableField,
+};
+
+struct Comment {
+  body: String,
+  id: i64,
+}
+
+struct CommentTableDefs;
+
+impl<'entity> TableDefs<'entity> for CommentTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "comments";
+
+  type Associations = NoTableAssociation<crate::Error>;
+  type Entity = Comment;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity i64;
+
+  fn type_instances(_: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (NoTableAssociation::new(), (TableField::new("body"),))
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.body);
+  }
+}
+
+struct Post {
+  id: i64,
+  title: String,
+}
+
+struct PostTableDefs;
+
+impl<'entity> TableDefs<'entity> for PostTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "posts";
+
+  type Associations =
+    (TableAssociationWrapper<'entity, CommentTableDefs, Vec<Table<'entity, CommentTableDefs>>>,);
+  type Entity = Post;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity i64;
+
+  fn type_instances(suffix: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (
+      (TableAssociationWrapper {
+        association: TableAssociation::new("id", "post_id"),
+        guide: Table::new(suffix + 1),
+        tables: vec![],
+      },),
+      (TableField::new("title"),),
+    )
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.title);
+  }
+}
+
+struct Author {
+  id: i64,
+  name: String,
+}
+
+struct AuthorTableDefs;
+
+impl<'entity> TableDefs<'entity> for AuthorTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "authors";
+
+  type Associations =
+    (TableAssociationWrapper<'entity, PostTableDefs, Vec<Table<'entity, PostTableDefs>>>,);
+  type Entity = Author;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity i64;
+
+  fn type_instances(suffix: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (
+      (TableAssociationWrapper {
+        association: TableAssociation::new("id", "author_id"),
+        guide: Table::new(suffix + 1),
+        tables: vec![],
+      },),
+      (TableField::new("name"),),
+    )
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.name);
+  }
+}
+
+fn schema() -> Schema {
+  let mut schema = Schema::new();
+  schema.push_table(&Table::<AuthorTableDefs>::default()).unwrap();
+  schema
+}
+
+fn column(data_type: &str, is_nullable: bool, name: &str) -> DbColumn {
+  DbColumn::new(data_type.into(), false, is_nullable, name.into())
+}
+
+#[test]
+fn live_tables_are_altered_to_match_definitions() {
+  let current = [
+    DbTable::new(
+      vec![
+        column("bigint", false, "id"),
+        column("character varying(100)", true, "name"),
+        column("text", true, "nickname"),
+      ],
+      vec![],
+      "authors".into(),
+      vec!["id".into()],
+      Some("authors_pkey".into()),
+    ),
+    DbTable::new(
+      vec![column("bigint", false, "id"), column("TEXT", false, "title")],
+      vec![DbForeignKey::new(
+        vec!["id".into()],
+        "posts_id_fkey".into(),
+        vec!["id".into()],
+        "authors".into(),
+      )],
+      "posts".into(),
+      vec!["id".into(), "title".into()],
+      Some("posts_pkey".into()),
+    ),
+    DbTable::new(vec![column("bigint", false, "id")], vec![], "sessions".into(), vec![], None),
+  ];
+  let changes = schema_changes(&current, PostgresDialect, true, &schema()).unwrap();
+  let migration = Migration::from_changes(&changes, PostgresDialect, "0002_sync").unwrap();
+  assert_eq!(
+    migration.statements(),
+    [
+      r#"ALTER TABLE "posts" DROP CONSTRAINT "posts_id_fkey";"#,
+      r#"ALTER TABLE "posts" DROP CONSTRAINT "posts_pkey";"#,
+      r#"CREATE TABLE "comments" ("id" BIGINT NOT NULL,"body" TEXT NOT NULL,"post_id" BIGINT NOT NULL,PRIMARY KEY ("id"),FOREIGN KEY ("post_id") REFERENCES "posts" ("id"));"#,
+      r#"ALTER TABLE "authors" ALTER COLUMN "name" TYPE TEXT,ALTER COLUMN "name" SET NOT NULL;"#,
+      r#"ALTER TABLE "posts" ADD COLUMN "author_id" BIGINT NOT NULL;"#,
+      r#"ALTER TABLE "posts" ADD PRIMARY KEY ("id");"#,
+      r#"ALTER TABLE "posts" ADD FOREIGN KEY ("author_id") REFERENCES "authors" ("id");"#,
+      r#"ALTER TABLE "authors" DROP COLUMN "nickname";"#,
+    ]
+  );
+}
+
+#[test]
+fn up_to_date_tables_have_no_changes() {
+  let current = [
+    DbTable::new(
+      vec![column("bigint", false, "id"), column("text", false, "name")],
+      vec![],
+      "authors".into(),
+      vec!["id".into()],
+      Some("authors_pkey".into()),
+    ),
+    DbTable::new(
+      vec![
+        column("bigint", false, "id"),
+        column("text", false, "title"),
+        column("bigint", false, "author_id"),
+      ],
+      vec![DbForeignKey::new(
+        vec!["author_id".into()],
+        "posts_author_id_fkey".into(),
+        vec!["id".into()],
+        "authors".into(),
+      )],
+      "posts".into(),
+      vec!["id".into()],
+      Some("posts_pkey".into()),
+    ),
+    DbTable::new(
+      vec![
+        column("bigint", false, "id"),
+        column("text", false, "body"),
+        column("bigint", false, "post_id"),
+      ],
+      vec![DbForeignKey::new(
+        vec!["post_id".into()],
+        "comments_post_id_fkey".into(),
+        vec!["id".into()],
+        "posts".into(),
+      )],
+      "comments".into(),
+      vec!["id".into()],
+      Some("comments_pkey".into()),
+    ),
+  ];
+  assert_eq!(schema_changes(&current, PostgresDialect, true, &schema()).unwrap(), []);
+}
+
+#[test]
+fn replaced_primary_keys_recreate_dependent_foreign_keys() {
+  let current = [
+    DbTable::new(
+      vec![
+        column("bigint", false, "id"),
+        column("text", false, "name"),
+        column("text", true, "nickname"),
+      ],
+      vec![],
+      "authors".into(),
+      vec!["id".into()],
+      Some("authors_pkey".into()),
+    ),
+    DbTable::new(
+      vec![
+        column("bigint", false, "id"),
+        column("text", false, "title"),
+        column("bigint", false, "author_id"),
+      ],
+      vec![DbForeignKey::new(
+        vec!["author_id".into()],
+        "posts_author_id_fkey".into(),
+        vec!["id".into()],
+        "authors".into(),
+      )],
+      "posts".into(),
+      vec!["id".into(), "title".into()],
+      Some("posts_pkey".into()),
+    ),
+    DbTable::new(
+      vec![
+        column("bigint", false, "id"),
+        column("text", false, "body"),
+        column("bigint", false, "post_id"),
+      ],
+      vec![DbForeignKey::new(
+        vec!["post_id".into()],
+        "comments_post_id_fkey".into(),
+        vec!["id".into()],
+        "posts".into(),
+      )],
+      "comments".into(),
+      vec!["id".into()],
+      Some("comments_pkey".into()),
+    ),
+  ];
+  let changes = schema_changes(&current, PostgresDialect, false, &schema()).unwrap();
+  let migration = Migration::from_changes(&changes, PostgresDialect, "0003_keys").unwrap();
+  assert_eq!(
+    migration.statements(),
+    [
+      r#"ALTER TABLE "comments" DROP CONSTRAINT "comments_post_id_fkey";"#,
+      r#"ALTER TABLE "posts" DROP CONSTRAINT "posts_pkey";"#,
+      r#"ALTER TABLE "posts" ADD PRIMARY KEY ("id");"#,
+      r#"ALTER TABLE "comments" ADD FOREIGN KEY ("post_id") REFERENCES "posts" ("id");"#,
+    ]
+  );
+}
+
+#[test]
+fn altered_columns_are_written_by_each_dialect() {
+  let mut buffer = String::new();
+  let change = SchemaChange::AlterColumn(
+    "authors",
+    ColumnSchema::new(ColumnType::String, false, true, "name"),
+    false,
+  );
+  change.write(&mut buffer, MySqlDialect, &mut SqlWriterMode::Inline).unwrap();
+  assert_eq!(&buffer, "ALTER TABLE `authors` MODIFY COLUMN `name` VARCHAR(255);");
+  buffer.clear();
+  change.write(&mut buffer, PostgresDialect, &mut SqlWriterMode::Inline).unwrap();
+  assert_eq!(
+    &buffer,
+    r#"ALTER TABLE "authors" ALTER COLUMN "name" TYPE TEXT,ALTER COLUMN "name" DROP NOT NULL;"#
+  );
+  buffer.clear();
+  assert!(matches!(
+    change.write(&mut buffer, SqliteDialect, &mut SqlWriterMode::Inline),
+    Err(crate::Error::UnsupportedAlterColumn("authors", "name"))
+  ));
+}
+
+#[test]
+fn generated_columns_are_altered_to_match_definitions() {
+  let current = [
+    DbTable::new(
+      vec![column("integer", false, "id"), column("text", false, "name")],
+      vec![],
+      "authors".into(),
+      vec!["id".into()],
+      Some("authors_pkey".into()),
+    ),
+    DbTable::new(
+      vec![
+        DbColumn::new("integer".into(), true, false, "id".into()),
+        column("text", false, "title"),
+        column("integer", false, "author_id"),
+      ],
+      vec![DbForeignKey::new(
+        vec!["author_id".into()],
+        "books_author_id_fkey".into(),
+        vec!["id".into()],
+        "authors".into(),
+      )],
+      "books".into(),
+      vec!["id".into()],
+      Some("books_pkey".into()),
+    ),
+  ];
+  let mut schema = Schema::new();
+  schema.push_table(&Table::<generated_key::AuthorTableDefs>::default()).unwrap();
+  let changes = schema_changes(&current, PostgresDialect, false, &schema).unwrap();
+  let migration = Migration::from_changes(&changes, PostgresDialect, "0004_identities").unwrap();
+  assert_eq!(
+    migration.statements(),
+    [
+      r#"ALTER TABLE "authors" ALTER COLUMN "id" TYPE INTEGER,ALTER COLUMN "id" SET NOT NULL,ALTER COLUMN "id" ADD GENERATED BY DEFAULT AS IDENTITY;"#
+    ]
+  );
+
+  let mut buffer = String::new();
+  let change = SchemaChange::AlterColumn(
+    "books",
+    ColumnSchema::new(ColumnType::I32, false, false, "id"),
+    true,
+  );
+  change.write(&mut buffer, PostgresDialect, &mut SqlWriterMode::Inline).unwrap();
+  assert_eq!(
+    &buffer,
+    r#"ALTER TABLE "books" ALTER COLUMN "id" TYPE INTEGER,ALTER COLUMN "id" SET NOT NULL,ALTER COLUMN "id" DROP IDENTITY;"#
+  );
+}
+
+#[cfg(all(feature = "sqlx-sqlite", feature = "sqlx-runtime-tokio-rustls"))]
+#[tokio::test]
+async fn sqlite_backend_applies_pending_migrations_once() {
+  use crate::migrations::migrate;
+  use sqlx_core::{query::query, sqlite::SqlitePoolOptions};
+
+  let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+  let changes = schema_changes(&[], SqliteDialect, false, &schema()).unwrap();
+  let first = Migration::from_changes(&changes, SqliteDialect, "0001_init").unwrap();
+  let second = Migration::new("0002_tags", vec!["CREATE TABLE tags (name TEXT NOT NULL);".into()]);
+
+  assert_eq!(migrate(true, &pool, core::slice::from_ref(&first)).await.unwrap(), ["0001_init"]);
+  assert!(query("SELECT id FROM authors").execute(&pool).await.is_err());
+  assert_eq!(migrate(false, &pool, core::slice::from_ref(&first)).await.unwrap(), ["0001_init"]);
+  let _ = query("INSERT INTO authors (id,name) VALUES (1,'foo')").execute(&pool).await.unwrap();
+  let _ = query("INSERT INTO posts (id,title,author_id) VALUES (1,'bar',1)")
+    .execute(&pool)
+    .await
+    .unwrap();
+
+  let migrations = [first.clone(), second];
+  assert_eq!(migrate(true, &pool, &migrations).await.unwrap(), ["0002_tags"]);
+  assert!(query("SELECT name FROM tags").execute(&pool).await.is_err());
+  assert_eq!(migrate(false, &pool, &migrations).await.unwrap(), ["0002_tags"]);
+  assert!(migrate(false, &pool, &migrations).await.unwrap().is_empty());
+
+  let modified = Migration::new("0001_init", vec!["CREATE TABLE foo (id INTEGER);".into()]);
+  assert!(matches!(
+    migrate(false, &pool, &[modified]).await,
+    Err(crate::Error::MigrationChecksumMismatch("0001_init"))
+  ));
+}
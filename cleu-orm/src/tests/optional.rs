@@ -0,0 +1,239 @@
+// CREATE TABLE users (
+//   id INTEGER PRIMARY KEY NOT NULL,
+//   name TEXT NOT NULL
+// );
+//
+// CREATE TABLE profiles (
+//   id INTEGER PRIMARY KEY NOT NULL,
+//   user_id INTEGER NOT NULL UNIQUE REFERENCES users(id),
+//   bio TEXT NOT NULL
+// );
+//
+// User --> Option<Profile>
+
+use crate::{
+  seek_optional_entity,
+  tests::db_row::{TestRow, TestValue},
+  write_column_alias, DbRow, DecodeColumn, FromRowsSuffix, FromSuffixRslt, InitialInsertValue,
+  InsertOptions, NoTableAssociation, OptionalTable, PostgresDialect, SqlWriter, SqlWriterMode,
+  Suffix, Table, TableAssociation, TableAssociationWrapper, TableDefs, TableField,
+};
+
+#[derive(Debug, PartialEq)]
+struct Profile {
+  bio: String,
+  id: i64,
+}
+
+impl<B, ROW> FromRowsSuffix<B, ROW> for Profile
+where
+  B: cl_traits::String,
+  ROW: DbRow + DecodeColumn<i64> + DecodeColumn<String>,
+{
+  type Error = crate::Error;
+
+  fn from_rows_suffix(
+    _: &[ROW],
+    buffer: &mut B,
+    suffix: Suffix,
+    target_row: &ROW,
+  ) -> Result<(usize, Self), Self::Error> {
+    write_column_alias(buffer, "profiles", suffix, "id")?;
+    let id = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    write_column_alias(buffer, "profiles", suffix, "bio")?;
+    let bio = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    Ok((1, Self { bio, id }))
+  }
+}
+
+struct ProfileTableDefs;
+
+impl<'entity> TableDefs<'entity> for ProfileTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "profiles";
+
+  type Associations = NoTableAssociation<crate::Error>;
+  type Entity = Profile;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity i64;
+
+  fn type_instances(_: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (NoTableAssociation::new(), (TableField::new("bio"),))
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.bio);
+  }
+}
+
+#[derive(Debug, PartialEq)]
+struct User {
+  id: i64,
+  name: String,
+  profile: Option<Profile>,
+}
+
+impl<B, ROW> FromRowsSuffix<B, ROW> for User
+where
+  B: cl_traits::String,
+  ROW: DbRow + DecodeColumn<i64> + DecodeColumn<String>,
+{
+  type Error = crate::Error;
+
+  fn from_rows_suffix(
+    all_rows: &[ROW],
+    buffer: &mut B,
+    suffix: Suffix,
+    target_row: &ROW,
+  ) -> Result<(usize, Self), Self::Error> {
+    write_column_alias(buffer, "users", suffix, "id")?;
+    let id = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    write_column_alias(buffer, "users", suffix, "name")?;
+    let name = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    let (skip, profile) = seek_optional_entity::<_, _, _, ProfileTableDefs, UserTableDefs>(
+      buffer,
+      all_rows,
+      suffix,
+      suffix + 1,
+    )?;
+    Ok((skip, Self { id, name, profile }))
+  }
+}
+
+struct UserTableDefs;
+
+impl<'entity> TableDefs<'entity> for UserTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "users";
+
+  type Associations =
+    (TableAssociationWrapper<'entity, ProfileTableDefs, OptionalTable<'entity, ProfileTableDefs>>,);
+  type Entity = User;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity i64;
+
+  fn type_instances(suffix: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (
+      (TableAssociationWrapper {
+        association: TableAssociation::new("id", "user_id"),
+        guide: Table::new(suffix + 1),
+        tables: OptionalTable::default(),
+      },),
+      (TableField::new("name"),),
+    )
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.name);
+
+    table.associations_mut().0.tables =
+      OptionalTable::from_entity(entity.profile.as_ref(), table.suffix() + 1);
+  }
+}
+
+fn users() -> [User; 2] {
+  [
+    User { id: 1, name: "foo".into(), profile: Some(Profile { bio: "bar".into(), id: 1 }) },
+    User { id: 2, name: "baz".into(), profile: None },
+  ]
+}
+
+fn assemble(rows: &[TestRow]) -> crate::Result<Vec<User>> {
+  let mut buffer = String::new();
+  let mut users = Vec::new();
+  let mut counter = 0;
+  while let Some(row) = rows.get(counter) {
+    let (skip, user) = User::from_rows_suffix(&rows[counter..], &mut buffer, 0, row)?;
+    users.push(user);
+    counter += skip;
+  }
+  Ok(users)
+}
+
+#[test]
+fn absent_tables_are_skipped_by_write_operations() {
+  let users = users();
+  let mut buffer = String::new();
+  for user in &users {
+    let mut table = Table::<UserTableDefs>::default();
+    table.update_all_table_fields(user);
+    table
+      .write_insert::<InitialInsertValue>(
+        &mut <_>::default(),
+        &mut buffer,
+        PostgresDialect,
+        &mut SqlWriterMode::Inline,
+        InsertOptions::new(),
+        &mut None,
+      )
+      .unwrap();
+    table
+      .write_update(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+      .unwrap();
+    table
+      .write_delete(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+      .unwrap();
+  }
+  assert_eq!(
+    &buffer,
+    r#"INSERT INTO "users" ("id","name") VALUES ('1','foo');INSERT INTO "profiles" ("id","bio","user_id") VALUES ('1','bar','1');UPDATE "users" SET "id"='1',"name"='foo' WHERE "id"='1';UPDATE "profiles" SET "id"='1',"bio"='bar' WHERE "id"='1';DELETE FROM "profiles" WHERE "id"='1';DELETE FROM "users" WHERE "id"='1';INSERT INTO "users" ("id","name") VALUES ('2','baz');UPDATE "users" SET "id"='2',"name"='baz' WHERE "id"='2';DELETE FROM "users" WHERE "id"='2';"#
+  );
+}
+
+#[test]
+fn null_joined_columns_are_assembled_into_none() {
+  let rows = [
+    TestRow(&[
+      ("users0__id", Some(TestValue::I64(1))),
+      ("users0__name", Some(TestValue::Str("foo"))),
+      ("profiles1__id", Some(TestValue::I64(1))),
+      ("profiles1__bio", Some(TestValue::Str("bar"))),
+    ]),
+    TestRow(&[
+      ("users0__id", Some(TestValue::I64(2))),
+      ("users0__name", Some(TestValue::Str("baz"))),
+      ("profiles1__id", None),
+      ("profiles1__bio", None),
+    ]),
+  ];
+  assert_eq!(assemble(&rows).unwrap(), users());
+}
+
+#[test]
+fn present_entities_propagate_errors() {
+  let rows = [TestRow(&[
+    ("users0__id", Some(TestValue::I64(1))),
+    ("users0__name", Some(TestValue::Str("foo"))),
+    ("profiles1__id", Some(TestValue::I64(1))),
+    ("profiles1__bio", None),
+  ])];
+  assert!(matches!(assemble(&rows), Err(crate::Error::UnexpectedNullValue)));
+}
+
+#[cfg(all(feature = "sqlx-sqlite", feature = "sqlx-runtime-tokio-rustls"))]
+#[tokio::test]
+async fn only_null_keys_are_assembled_into_none() {
+  use crate::crud::Database;
+  use sqlx_core::sqlite::{Sqlite, SqlitePoolOptions};
+
+  let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+  let mut buffer = String::new();
+  for statement in [
+    "SELECT 1 AS users0__id,'foo' AS users0__name,'1' AS profiles1__id,'bar' AS profiles1__bio",
+    "SELECT 1 AS users0__id,'foo' AS users0__name",
+  ] {
+    let rows = Sqlite::fetch_all(statement, &pool, &[]).await.unwrap();
+    let rslt = User::from_rows_suffix(&rows, &mut buffer, 0, &rows[0]);
+    assert!(matches!(rslt, Err(crate::Error::Sqlx(_))));
+  }
+}
@@ -0,0 +1,248 @@
+// Requires a Postgres instance referenced by the `DATABASE_URL` environment variable, which is
+// why every test is ignored by default. Run them with
+// `DATABASE_URL=postgres://... cargo test --all-features -- --ignored`.
+
+#![cfg(all(feature = "sqlx-postgres", feature = "sqlx-runtime-tokio-rustls"))]
+
+use crate::{
+  crud::{AffectedRows, Database},
+  migrations::{read_postgres_tables, schema_changes},
+  tests::{
+    db_row::{BTableDefs, A, B},
+    generated_key::{Author, AuthorTableDefs, Book},
+  },
+  BindValue, ColumnType, EagerLoading, FromSuffixRslt, InitialInsertValue, InsertOptions,
+  NoTableAssociation, PostgresDialect, Schema, SelectLimit, SqlWriter, SqlWriterMode, Suffix,
+  Table, TableDefs, TableField,
+};
+use sqlx_core::{
+  postgres::{PgPool, PgPoolOptions, Postgres},
+  query::query,
+  query_scalar::query_scalar,
+};
+use std::env;
+
+struct Counter {
+  id: u64,
+  name: String,
+}
+
+struct CounterTableDefs;
+
+impl<'entity> TableDefs<'entity> for CounterTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "counters";
+
+  type Associations = NoTableAssociation<crate::Error>;
+  type Entity = Counter;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity u64;
+
+  fn type_instances(_: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (NoTableAssociation::new(), (TableField::new("name"),))
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.name);
+  }
+}
+
+struct Note {
+  id: i32,
+  summary: Option<String>,
+}
+
+struct NoteTableDefs;
+
+impl<'entity> TableDefs<'entity> for NoteTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "notes";
+
+  type Associations = NoTableAssociation<crate::Error>;
+  type Entity = Note;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity Option<String>>,);
+  type PrimaryKeyValue = &'entity i32;
+
+  fn type_instances(_: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (NoTableAssociation::new(), (TableField::new("summary"),))
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.summary);
+  }
+}
+
+// Each test gets its own schema because tests run concurrently
+async fn pool(schema: &str) -> PgPool {
+  let url = env::var("DATABASE_URL").expect("`DATABASE_URL` must be set to run Postgres tests");
+  let pool = PgPoolOptions::new().max_connections(1).connect(&url).await.unwrap();
+  for statement in [
+    format!("DROP SCHEMA IF EXISTS {schema} CASCADE"),
+    format!("CREATE SCHEMA {schema}"),
+    format!("SET search_path TO {schema}"),
+  ] {
+    let _ = query(&statement).execute(&pool).await.unwrap();
+  }
+  pool
+}
+
+#[ignore]
+#[tokio::test]
+async fn postgres_backend_feeds_int4_identity_keys_into_associations() {
+  let pool = pool("cleu_orm_identity_keys").await;
+  let mut buffer = String::new();
+  let author = Author {
+    books: vec![Book { id: 0, title: "foo".into() }, Book { id: 0, title: "bar".into() }],
+    id: 0,
+    name: "baz".into(),
+  };
+  let mut table = Table::<AuthorTableDefs>::default();
+  let _ = table.create_schema(&mut buffer, &pool).await.unwrap();
+  buffer.clear();
+  table.update_all_table_fields(&author);
+  *table.id_field_mut().value_mut() = None;
+  for elem in &mut table.associations_mut().0.tables {
+    *elem.id_field_mut().value_mut() = None;
+  }
+
+  let mut mode = SqlWriterMode::parameterized();
+  table
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut mode,
+      InsertOptions::new().with_batch_size(2),
+      &mut None,
+    )
+    .unwrap();
+  let rslts = Postgres::execute_parameterized(&buffer, &pool, &mode).await.unwrap();
+  assert_eq!(
+    AffectedRows::new(&mode, &rslts).generated_keys(),
+    [("authors", BindValue::I32(1)), ("books", BindValue::I32(1)), ("books", BindValue::I32(2))]
+  );
+  let author_ids: Vec<i32> =
+    query_scalar("SELECT author_id FROM books").fetch_all(&pool).await.unwrap();
+  assert_eq!(author_ids, [1, 1]);
+}
+
+#[ignore]
+#[tokio::test]
+async fn postgres_backend_migrates_identity_columns() {
+  let pool = pool("cleu_orm_identity_migrations").await;
+  for statement in [
+    "CREATE TABLE authors (id INTEGER PRIMARY KEY NOT NULL, name TEXT NOT NULL)",
+    "CREATE TABLE books (id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY NOT NULL, title \
+     TEXT NOT NULL, author_id INTEGER NOT NULL REFERENCES authors(id))",
+  ] {
+    let _ = query(statement).execute(&pool).await.unwrap();
+  }
+  let mut schema = Schema::new();
+  schema.push_table(&Table::<AuthorTableDefs>::default()).unwrap();
+  let current = read_postgres_tables(&pool, &schema).await.unwrap();
+  let changes = schema_changes(&current, PostgresDialect, false, &schema).unwrap();
+  assert_eq!(changes.len(), 1);
+  let mut buffer = String::new();
+  changes[0].write(&mut buffer, PostgresDialect, &mut SqlWriterMode::Inline).unwrap();
+  let _ = query(&buffer).execute(&pool).await.unwrap();
+
+  let current = read_postgres_tables(&pool, &schema).await.unwrap();
+  assert_eq!(schema_changes(&current, PostgresDialect, false, &schema).unwrap(), []);
+}
+
+#[ignore]
+#[tokio::test]
+async fn postgres_backend_rejects_generated_u64_keys() {
+  let pool = pool("cleu_orm_u64_keys").await;
+  let mut buffer = String::new();
+  let _ =
+    query("CREATE TABLE counters (id NUMERIC(20,0) PRIMARY KEY DEFAULT 1, name TEXT NOT NULL)")
+      .execute(&pool)
+      .await
+      .unwrap();
+  let counter = Counter { id: 0, name: "foo".into() };
+  let mut table = Table::<CounterTableDefs>::default();
+  table.update_all_table_fields(&counter);
+  *table.id_field_mut().value_mut() = None;
+
+  let mut mode = SqlWriterMode::parameterized();
+  table
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut mode,
+      InsertOptions::new(),
+      &mut None,
+    )
+    .unwrap();
+  assert!(matches!(
+    Postgres::execute_parameterized(&buffer, &pool, &mode).await,
+    Err(crate::Error::UnsupportedGeneratedKey(ColumnType::U64))
+  ));
+}
+
+#[ignore]
+#[tokio::test]
+async fn postgres_backend_writes_null_into_text_columns() {
+  let pool = pool("cleu_orm_null_values").await;
+  let mut buffer = String::new();
+  let mut table = Table::<NoteTableDefs>::default();
+  let _ = table.create_schema(&mut buffer, &pool).await.unwrap();
+  buffer.clear();
+  let _ = table.create(&mut buffer, &pool, &Note { id: 1, summary: None }).await.unwrap();
+  let is_null: bool =
+    query_scalar("SELECT summary IS NULL FROM notes WHERE id = 1").fetch_one(&pool).await.unwrap();
+  assert!(is_null);
+  assert!(matches!(
+    Postgres::fetch_all("SELECT $1::TEXT", &pool, &[BindValue::Null]).await,
+    Err(crate::Error::UnsupportedBindValue)
+  ));
+}
+
+#[ignore]
+#[tokio::test]
+async fn postgres_backend_paginates_root_entities_with_json_columns() {
+  let pool = pool("cleu_orm_json_pagination").await;
+  let mut buffer = String::new();
+  let mut table = Table::<BTableDefs>::default();
+  let _ = table.create_schema(&mut buffer, &pool).await.unwrap();
+  let _ = query("ALTER TABLE b ADD COLUMN payload JSON NOT NULL DEFAULT '{}'")
+    .execute(&pool)
+    .await
+    .unwrap();
+  let first = B {
+    r#as: vec![A { id: 1, name: "foo1".into() }, A { id: 2, name: "foo2".into() }],
+    id: 1,
+    name: "bar1".into(),
+  };
+  let second = B { r#as: vec![A { id: 3, name: "foo3".into() }], id: 2, name: "bar2".into() };
+  buffer.clear();
+  let _ = table.create(&mut buffer, &pool, &first).await.unwrap();
+  buffer.clear();
+  let _ = table.create(&mut buffer, &pool, &second).await.unwrap();
+
+  let table = Table::<BTableDefs>::default();
+  let filter = table.id_column().gt(&0);
+  buffer.clear();
+  assert_eq!(
+    table
+      .read_all_with_params(
+        &mut buffer,
+        &pool,
+        EagerLoading::all(),
+        &[],
+        SelectLimit::Count(1),
+        Some(&filter)
+      )
+      .await
+      .unwrap(),
+    [first]
+  );
+}
@@ -0,0 +1,524 @@
+// CREATE TABLE categories (
+//   id INTEGER PRIMARY KEY NOT NULL,
+//   parent_id INTEGER REFERENCES categories(id),
+//   name TEXT NOT NULL
+// );
+//
+// CREATE TABLE departments (
+//   id INTEGER PRIMARY KEY NOT NULL,
+//   name TEXT NOT NULL
+// );
+//
+// CREATE TABLE employees (
+//   id INTEGER PRIMARY KEY NOT NULL,
+//   department_id INTEGER NOT NULL REFERENCES departments(id),
+//   manager_id INTEGER REFERENCES employees(id),
+//   name TEXT NOT NULL
+// );
+//
+// Category --> Category
+// Employee --> Department
+// Employee --> Employee
+
+use crate::{
+  seek_related_entities, write_column_alias, BindValue, DbRow, DecodeColumn, EagerLoading,
+  FromRowsSuffix, FromSuffixRslt, InitialInsertValue, InsertOptions, NoTableAssociation,
+  PostgresDialect, Schema, SelectLimit, SelfTableAssociation, SqlWriter, SqlWriterMode,
+  SqliteDialect, Suffix, Table, TableAssociation, TableAssociationWrapper, TableDefs, TableField,
+};
+
+#[derive(Debug, PartialEq)]
+struct Category {
+  children: Vec<Category>,
+  id: i64,
+  name: String,
+}
+
+impl<B, ROW> FromRowsSuffix<B, ROW> for Category
+where
+  B: cl_traits::String,
+  ROW: DbRow + DecodeColumn<i64> + DecodeColumn<String>,
+{
+  type Error = crate::Error;
+
+  fn from_rows_suffix(
+    all_rows: &[ROW],
+    buffer: &mut B,
+    suffix: Suffix,
+    target_row: &ROW,
+  ) -> Result<(usize, Self), Self::Error> {
+    write_column_alias(buffer, "categories", suffix, "id")?;
+    let id = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    write_column_alias(buffer, "categories", suffix, "name")?;
+    let name = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    let mut children = Vec::new();
+    let skip = seek_related_entities::<_, _, Category, _, CategoryTableDefs, CategoryTableDefs>(
+      buffer,
+      all_rows,
+      suffix,
+      suffix + 1,
+      CategoryTableDefs::type_instances(suffix).0.association().join_kind(),
+      |child| {
+        children.push(child);
+        Ok(())
+      },
+    )?;
+    Ok((skip, Self { children, id, name }))
+  }
+}
+
+struct CategoryTableDefs;
+
+impl<'entity> TableDefs<'entity> for CategoryTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "categories";
+
+  type Associations = SelfTableAssociation<'entity, Self>;
+  type Entity = Category;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity i64;
+
+  fn type_instances(suffix: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (
+      SelfTableAssociation::new(TableAssociation::new("id", "parent_id"), 2, suffix + 1),
+      (TableField::new("name"),),
+    )
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.name);
+
+    let suffix = table.suffix();
+    table.associations_mut().tables_mut().clear();
+    for child in entity.children.iter() {
+      let mut elem = Table::new(suffix + 1);
+      elem.update_all_table_fields(child);
+      table.associations_mut().tables_mut().push(elem);
+    }
+  }
+}
+
+#[derive(Debug, PartialEq)]
+struct Department {
+  id: i64,
+  name: String,
+}
+
+impl<B, ROW> FromRowsSuffix<B, ROW> for Department
+where
+  B: cl_traits::String,
+  ROW: DbRow + DecodeColumn<i64> + DecodeColumn<String>,
+{
+  type Error = crate::Error;
+
+  fn from_rows_suffix(
+    _: &[ROW],
+    buffer: &mut B,
+    suffix: Suffix,
+    target_row: &ROW,
+  ) -> Result<(usize, Self), Self::Error> {
+    write_column_alias(buffer, "departments", suffix, "id")?;
+    let id = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    write_column_alias(buffer, "departments", suffix, "name")?;
+    let name = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    Ok((1, Self { id, name }))
+  }
+}
+
+struct DepartmentTableDefs;
+
+impl<'entity> TableDefs<'entity> for DepartmentTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "departments";
+
+  type Associations = NoTableAssociation<crate::Error>;
+  type Entity = Department;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity i64;
+
+  fn type_instances(_: Suffix) -> FromSuffixRslt<'entity, Self> {
+    (NoTableAssociation::new(), (TableField::new("name"),))
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.name);
+  }
+}
+
+#[derive(Debug, PartialEq)]
+struct Employee {
+  department: Department,
+  id: i64,
+  name: String,
+  reports: Vec<Employee>,
+}
+
+impl<B, ROW> FromRowsSuffix<B, ROW> for Employee
+where
+  B: cl_traits::String,
+  ROW: DbRow + DecodeColumn<i64> + DecodeColumn<String>,
+{
+  type Error = crate::Error;
+
+  fn from_rows_suffix(
+    all_rows: &[ROW],
+    buffer: &mut B,
+    suffix: Suffix,
+    target_row: &ROW,
+  ) -> Result<(usize, Self), Self::Error> {
+    write_column_alias(buffer, "employees", suffix, "id")?;
+    let id = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    write_column_alias(buffer, "employees", suffix, "name")?;
+    let name = target_row.decode(buffer.as_ref())?;
+    buffer.clear();
+    let mut department = None;
+    let _ = seek_related_entities::<_, _, Department, _, DepartmentTableDefs, EmployeeTableDefs>(
+      buffer,
+      all_rows,
+      suffix,
+      suffix + 1,
+      EmployeeTableDefs::type_instances(suffix).0.others().0.association.join_kind(),
+      |elem| {
+        department = Some(elem);
+        Ok(())
+      },
+    )?;
+    let department = department.ok_or(crate::Error::NoDatabaseRowResult)?;
+    let mut reports = Vec::new();
+    let skip = seek_related_entities::<_, _, Employee, _, EmployeeTableDefs, EmployeeTableDefs>(
+      buffer,
+      all_rows,
+      suffix,
+      suffix + 2,
+      EmployeeTableDefs::type_instances(suffix).0.association().join_kind(),
+      |report| {
+        reports.push(report);
+        Ok(())
+      },
+    )?;
+    Ok((skip, Self { department, id, name, reports }))
+  }
+}
+
+struct EmployeeTableDefs;
+
+impl<'entity> TableDefs<'entity> for EmployeeTableDefs {
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &["id"];
+  const TABLE_NAME: &'static str = "employees";
+
+  type Associations = SelfTableAssociation<
+    'entity,
+    Self,
+    (
+      TableAssociationWrapper<
+        'entity,
+        DepartmentTableDefs,
+        [Table<'entity, DepartmentTableDefs>; 1],
+      >,
+    ),
+  >;
+  type Entity = Employee;
+  type Error = crate::Error;
+  type Fields = (TableField<crate::Error, &'entity String>,);
+  type PrimaryKeyValue = &'entity i64;
+
+  fn type_instances(suffix: Suffix) -> FromSuffixRslt<'entity, Self> {
+    let department = TableAssociationWrapper {
+      association: TableAssociation::new("department_id", "id").with_belongs_to(true),
+      guide: Table::new(suffix + 1),
+      tables: [Table::new(suffix + 1)],
+    };
+    (
+      SelfTableAssociation::with_others(
+        TableAssociation::new("id", "manager_id"),
+        2,
+        (department,),
+        suffix + 2,
+      ),
+      (TableField::new("name"),),
+    )
+  }
+
+  fn update_all_table_fields(entity: &'entity Self::Entity, table: &mut Table<'entity, Self>) {
+    *table.id_field_mut().value_mut() = Some(&entity.id);
+
+    *table.fields_mut().0.value_mut() = Some(&entity.name);
+
+    let suffix = table.suffix();
+    table.associations_mut().others_mut().0.tables[0].update_all_table_fields(&entity.department);
+    table.associations_mut().tables_mut().clear();
+    for report in entity.reports.iter() {
+      let mut elem = Table::new(suffix + 2);
+      elem.update_all_table_fields(report);
+      table.associations_mut().tables_mut().push(elem);
+    }
+  }
+}
+
+fn category() -> Category {
+  Category {
+    children: vec![
+      Category {
+        children: vec![Category { children: vec![], id: 3, name: "baz".into() }],
+        id: 2,
+        name: "bar".into(),
+      },
+      Category { children: vec![], id: 4, name: "qux".into() },
+    ],
+    id: 1,
+    name: "foo".into(),
+  }
+}
+
+#[test]
+fn self_associations_are_joined_up_to_the_maximum_depth() {
+  let mut buffer = String::new();
+  let table = Table::<CategoryTableDefs>::default();
+  table
+    .write_select(
+      &mut buffer,
+      PostgresDialect,
+      EagerLoading::all(),
+      &[],
+      SelectLimit::All,
+      &mut |_| Ok(()),
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"SELECT "categories0"."id" AS "categories0__id","categories0"."name" AS "categories0__name","categories1"."id" AS "categories1__id","categories1"."name" AS "categories1__name","categories2"."id" AS "categories2__id","categories2"."name" AS "categories2__name" FROM "categories" AS "categories0" LEFT JOIN "categories" AS "categories1" ON "categories0"."id" = "categories1"."parent_id" LEFT JOIN "categories" AS "categories2" ON "categories1"."id" = "categories2"."parent_id"  ORDER BY "categories0"."id","categories1"."id","categories2"."id" LIMIT ALL"#
+  );
+
+  buffer.clear();
+  table
+    .write_select(
+      &mut buffer,
+      PostgresDialect,
+      EagerLoading::all().with_max_depth(1),
+      &[],
+      SelectLimit::All,
+      &mut |_| Ok(()),
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"SELECT "categories0"."id" AS "categories0__id","categories0"."name" AS "categories0__name","categories1"."id" AS "categories1__id","categories1"."name" AS "categories1__name" FROM "categories" AS "categories0" LEFT JOIN "categories" AS "categories1" ON "categories0"."id" = "categories1"."parent_id"  ORDER BY "categories0"."id","categories1"."id" LIMIT ALL"#
+  );
+}
+
+#[test]
+fn self_associations_are_written_regardless_of_the_maximum_depth() {
+  let category = category();
+  let mut buffer = String::new();
+  let mut table = Table::<CategoryTableDefs>::default();
+  table.update_all_table_fields(&category);
+
+  table
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut SqlWriterMode::Inline,
+      InsertOptions::new(),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"INSERT INTO "categories" ("id","name") VALUES ('1','foo');INSERT INTO "categories" ("id","name","parent_id") VALUES ('2','bar','1');INSERT INTO "categories" ("id","name","parent_id") VALUES ('3','baz','2');INSERT INTO "categories" ("id","name","parent_id") VALUES ('4','qux','1');"#
+  );
+
+  buffer.clear();
+  table
+    .write_delete(&mut <_>::default(), &mut buffer, PostgresDialect, &mut SqlWriterMode::Inline)
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"DELETE FROM "categories" WHERE "id"='3';DELETE FROM "categories" WHERE "id"='2';DELETE FROM "categories" WHERE "id"='4';DELETE FROM "categories" WHERE "id"='1';"#
+  );
+}
+
+#[test]
+fn generated_keys_of_batched_rows_are_mapped_by_position() {
+  let category = category();
+  let mut buffer = String::new();
+  let mut table = Table::<CategoryTableDefs>::default();
+  table.update_all_table_fields(&category);
+  *table.id_field_mut().value_mut() = None;
+  for child in table.associations_mut().tables_mut() {
+    *child.id_field_mut().value_mut() = None;
+    for grandchild in child.associations_mut().tables_mut() {
+      *grandchild.id_field_mut().value_mut() = None;
+    }
+  }
+
+  let mut mode = SqlWriterMode::parameterized();
+  table
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      PostgresDialect,
+      &mut mode,
+      InsertOptions::new().with_batch_size(2),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"INSERT INTO "categories" ("name") VALUES ($1) RETURNING "id";INSERT INTO "categories" ("name","parent_id") VALUES ($1,$2),($3,$4) RETURNING "id";INSERT INTO "categories" ("name","parent_id") VALUES ($1,$2) RETURNING "id";"#
+  );
+  let keys = [vec![BindValue::I64(1)], vec![BindValue::I64(2), BindValue::I64(4)]];
+  assert_eq!(
+    mode.bind_arguments().unwrap().values_with_generated_keys(2, &keys).unwrap(),
+    [BindValue::String("baz".into()), BindValue::I64(2)]
+  );
+
+  // The order of rows returned by SQLite isn't guaranteed so rows that have children are written
+  // alone
+  buffer.clear();
+  table
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      SqliteDialect,
+      &mut SqlWriterMode::parameterized(),
+      InsertOptions::new().with_batch_size(2),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"INSERT INTO "categories" ("name") VALUES (?1) RETURNING "id";INSERT INTO "categories" ("name","parent_id") VALUES (?1,?2) RETURNING "id";INSERT INTO "categories" ("name","parent_id") VALUES (?1,?2) RETURNING "id";INSERT INTO "categories" ("name","parent_id") VALUES (?1,?2) RETURNING "id";"#
+  );
+}
+
+#[test]
+fn self_referencing_foreign_keys_are_nullable() {
+  let mut buffer = String::new();
+  let mut schema = Schema::new();
+  schema.push_table(&Table::<CategoryTableDefs>::default()).unwrap();
+  schema.write_create_tables(&mut buffer, SqliteDialect, &mut SqlWriterMode::Inline).unwrap();
+  assert_eq!(
+    &buffer,
+    r#"CREATE TABLE "categories" ("id" INTEGER NOT NULL,"name" TEXT NOT NULL,"parent_id" INTEGER,PRIMARY KEY ("id"),FOREIGN KEY ("parent_id") REFERENCES "categories" ("id"));"#
+  );
+}
+
+#[cfg(all(feature = "sqlx-sqlite", feature = "sqlx-runtime-tokio-rustls"))]
+#[tokio::test]
+async fn sqlite_backend_reads_trees_up_to_the_maximum_depth() {
+  use sqlx_core::sqlite::SqlitePoolOptions;
+
+  let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+  let mut buffer = String::new();
+  let _ = Table::<CategoryTableDefs>::default().create_schema(&mut buffer, &pool).await.unwrap();
+  let mut category = category();
+  category.children[0].children[0].children.push(Category {
+    children: vec![],
+    id: 5,
+    name: "quux".into(),
+  });
+
+  let mut table = Table::<CategoryTableDefs>::default();
+  table.create(&mut buffer, &pool, &category).await.unwrap();
+
+  let table = Table::<CategoryTableDefs>::default();
+  let roots = table.column(&TableField::<(), i64>::new("parent_id")).is_null();
+  let categories = table
+    .read_all_with_params(
+      &mut buffer,
+      &pool,
+      EagerLoading::all(),
+      &[],
+      SelectLimit::All,
+      Some(&roots),
+    )
+    .await
+    .unwrap();
+  category.children[0].children[0].children.clear();
+  assert_eq!(categories, [category]);
+}
+
+#[test]
+fn self_associations_coexist_with_other_associations() {
+  let mut buffer = String::new();
+  let table = Table::<EmployeeTableDefs>::default();
+  table
+    .write_select(
+      &mut buffer,
+      PostgresDialect,
+      EagerLoading::all(),
+      &[],
+      SelectLimit::All,
+      &mut |_| Ok(()),
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"SELECT "employees0"."id" AS "employees0__id","employees0"."name" AS "employees0__name","employees2"."id" AS "employees2__id","employees2"."name" AS "employees2__name","employees4"."id" AS "employees4__id","employees4"."name" AS "employees4__name","departments5"."id" AS "departments5__id","departments5"."name" AS "departments5__name","departments3"."id" AS "departments3__id","departments3"."name" AS "departments3__name","departments1"."id" AS "departments1__id","departments1"."name" AS "departments1__name" FROM "employees" AS "employees0" LEFT JOIN "employees" AS "employees2" ON "employees0"."id" = "employees2"."manager_id" LEFT JOIN "departments" AS "departments1" ON "employees0"."department_id" = "departments1"."id" LEFT JOIN "employees" AS "employees4" ON "employees2"."id" = "employees4"."manager_id" LEFT JOIN "departments" AS "departments3" ON "employees2"."department_id" = "departments3"."id" LEFT JOIN "departments" AS "departments5" ON "employees4"."department_id" = "departments5"."id"  ORDER BY "employees0"."id","employees2"."id","employees4"."id","departments5"."id","departments3"."id","departments1"."id" LIMIT ALL"#
+  );
+
+  buffer.clear();
+  let mut schema = Schema::new();
+  schema.push_table(&table).unwrap();
+  schema.write_create_tables(&mut buffer, SqliteDialect, &mut SqlWriterMode::Inline).unwrap();
+  assert_eq!(
+    &buffer,
+    r#"CREATE TABLE "departments" ("id" INTEGER NOT NULL,"name" TEXT NOT NULL,PRIMARY KEY ("id"));CREATE TABLE "employees" ("id" INTEGER NOT NULL,"name" TEXT NOT NULL,"manager_id" INTEGER,"department_id" INTEGER NOT NULL,PRIMARY KEY ("id"),FOREIGN KEY ("manager_id") REFERENCES "employees" ("id"),FOREIGN KEY ("department_id") REFERENCES "departments" ("id"));"#
+  );
+}
+
+#[cfg(all(feature = "sqlx-sqlite", feature = "sqlx-runtime-tokio-rustls"))]
+#[tokio::test]
+async fn sqlite_backend_reads_trees_with_other_associations() {
+  use sqlx_core::sqlite::SqlitePoolOptions;
+
+  let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+  let mut buffer = String::new();
+  let _ = Table::<EmployeeTableDefs>::default().create_schema(&mut buffer, &pool).await.unwrap();
+  let employee = Employee {
+    department: Department { id: 1, name: "foo".into() },
+    id: 1,
+    name: "bar".into(),
+    reports: vec![Employee {
+      department: Department { id: 2, name: "baz".into() },
+      id: 2,
+      name: "qux".into(),
+      reports: vec![Employee {
+        department: Department { id: 2, name: "baz".into() },
+        id: 3,
+        name: "quux".into(),
+        reports: vec![],
+      }],
+    }],
+  };
+
+  let mut table = Table::<EmployeeTableDefs>::default();
+  table.create(&mut buffer, &pool, &employee).await.unwrap();
+
+  let table = Table::<EmployeeTableDefs>::default();
+  let roots = table.column(&TableField::<(), i64>::new("manager_id")).is_null();
+  let employees = table
+    .read_all_with_params(
+      &mut buffer,
+      &pool,
+      EagerLoading::all(),
+      &[],
+      SelectLimit::All,
+      Some(&roots),
+    )
+    .await
+    .unwrap();
+  assert_eq!(employees, [employee]);
+}
@@ -0,0 +1,364 @@
+#![cfg(all(feature = "sqlx-sqlite", feature = "sqlx-runtime-tokio-rustls"))]
+
+use crate::{
+  crud::{AffectedRows, Database},
+  tests::db_row::{BTableDefs, A, B},
+  BindValue, DbRow, EagerLoading, InitialInsertValue, InsertOptions, Keyset, OnConflict,
+  SelectLimit, SelectedRow, SqlWriter, SqlWriterMode, SqliteDialect, Table,
+};
+use futures_util::TryStreamExt;
+use sqlx_core::{
+  query::query,
+  sqlite::{Sqlite, SqlitePool, SqlitePoolOptions},
+};
+use std::sync::Arc;
+
+async fn pool() -> SqlitePool {
+  let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+  let _ = query("CREATE TABLE b (id INTEGER PRIMARY KEY NOT NULL, name TEXT NOT NULL)")
+    .execute(&pool)
+    .await
+    .unwrap();
+  let _ = query(
+    "CREATE TABLE a (id INTEGER PRIMARY KEY NOT NULL, id_b INTEGER NOT NULL REFERENCES b(id), \
+     name TEXT NOT NULL)",
+  )
+  .execute(&pool)
+  .await
+  .unwrap();
+  pool
+}
+
+#[tokio::test]
+async fn sqlite_backend_creates_and_reads_entities() {
+  let pool = pool().await;
+  let mut buffer = String::new();
+  let first = B {
+    r#as: vec![A { id: 1, name: "foo'1".into() }, A { id: 2, name: "foo2".into() }],
+    id: 1,
+    name: "bar1".into(),
+  };
+  let name = String::from("bar1");
+  let second = B { r#as: vec![], id: 2, name: "bar2".into() };
+
+  let mut table = Table::<BTableDefs>::default();
+  table.create(&mut buffer, &pool, &first).await.unwrap();
+  table.create(&mut buffer, &pool, &second).await.unwrap();
+
+  let table = Table::<BTableDefs>::default();
+  let filter = table.column(&table.fields().0).eq(&name);
+  assert_eq!(table.read_all(&mut buffer, &pool).await.unwrap(), [first, second]);
+  assert_eq!(table.read_by_id(&mut buffer, &pool, &&2).await.unwrap().name, "bar2");
+  assert_eq!(
+    table
+      .read_all_with_params(
+        &mut buffer,
+        &pool,
+        EagerLoading::all(),
+        &[table.id_column().desc()],
+        SelectLimit::Count(1),
+        Some(&filter)
+      )
+      .await
+      .unwrap()
+      .len(),
+    1
+  );
+}
+
+#[tokio::test]
+async fn sqlite_backend_paginates_root_entities() {
+  let pool = pool().await;
+  let mut buffer = String::new();
+  let first = B {
+    r#as: vec![A { id: 1, name: "foo1".into() }, A { id: 2, name: "foo2".into() }],
+    id: 1,
+    name: "bar1".into(),
+  };
+  let second = B { r#as: vec![A { id: 3, name: "foo3".into() }], id: 2, name: "bar2".into() };
+
+  let mut table = Table::<BTableDefs>::default();
+  table.create(&mut buffer, &pool, &first).await.unwrap();
+  table.create(&mut buffer, &pool, &second).await.unwrap();
+
+  let table = Table::<BTableDefs>::default();
+  let after_first = table.id_column().gt(&1);
+  assert_eq!(
+    table
+      .read_all_with_params(
+        &mut buffer,
+        &pool,
+        EagerLoading::all(),
+        &[],
+        SelectLimit::Count(1),
+        None
+      )
+      .await
+      .unwrap(),
+    [first]
+  );
+  assert_eq!(
+    table
+      .read_all_with_params(
+        &mut buffer,
+        &pool,
+        EagerLoading::all(),
+        &[],
+        SelectLimit::Offset { count: 1, offset: 1 },
+        None
+      )
+      .await
+      .unwrap(),
+    [B { r#as: vec![A { id: 3, name: "foo3".into() }], id: 2, name: "bar2".into() }]
+  );
+  assert_eq!(
+    table
+      .read_all_with_params(
+        &mut buffer,
+        &pool,
+        EagerLoading::all(),
+        &[],
+        SelectLimit::Count(1),
+        Some(&after_first)
+      )
+      .await
+      .unwrap(),
+    [second]
+  );
+  assert_eq!(
+    table
+      .read_all_with_params(&mut buffer, &pool, EagerLoading::none(), &[], SelectLimit::All, None)
+      .await
+      .unwrap(),
+    [
+      B { r#as: vec![], id: 1, name: "bar1".into() },
+      B { r#as: vec![], id: 2, name: "bar2".into() }
+    ]
+  );
+}
+
+#[tokio::test]
+async fn sqlite_backend_paginates_with_keysets() {
+  let pool = pool().await;
+  let mut buffer = String::new();
+  let first = B { r#as: vec![A { id: 1, name: "foo1".into() }], id: 1, name: "bar1".into() };
+  let second = B {
+    r#as: vec![A { id: 2, name: "foo2".into() }, A { id: 3, name: "foo3".into() }],
+    id: 2,
+    name: "bar2".into(),
+  };
+  let third = B { r#as: vec![], id: 3, name: "bar3".into() };
+
+  let mut table = Table::<BTableDefs>::default();
+  table.create(&mut buffer, &pool, &first).await.unwrap();
+  table.create(&mut buffer, &pool, &second).await.unwrap();
+  table.create(&mut buffer, &pool, &third).await.unwrap();
+
+  let table = Table::<BTableDefs>::default();
+  let name = String::from("bar3");
+  let filter = table.column(&table.fields().0).ne(&name);
+  let first_page = table
+    .read_keyset(&mut buffer, &pool, EagerLoading::all(), Keyset::first(2), None)
+    .await
+    .unwrap();
+  assert_eq!(first_page.iter().collect::<Vec<_>>(), [&first, &second]);
+  let second_page = table
+    .read_keyset(&mut buffer, &pool, EagerLoading::all(), Keyset::after(2, &1), None)
+    .await
+    .unwrap();
+  assert_eq!(second_page.iter().collect::<Vec<_>>(), [&second, &third]);
+  assert_eq!(
+    table
+      .read_keyset(&mut buffer, &pool, EagerLoading::all(), Keyset::after(2, &1), Some(&filter))
+      .await
+      .unwrap(),
+    [second]
+  );
+}
+
+#[tokio::test]
+async fn sqlite_backend_updates_and_deletes_entities() {
+  let pool = pool().await;
+  let mut buffer = String::new();
+  let first = B {
+    r#as: vec![A { id: 1, name: "foo1".into() }, A { id: 2, name: "foo2".into() }],
+    id: 1,
+    name: "bar1".into(),
+  };
+  let second = B { r#as: vec![], id: 2, name: "bar2".into() };
+  let mut table = Table::<BTableDefs>::default();
+  table.create(&mut buffer, &pool, &first).await.unwrap();
+  table.create(&mut buffer, &pool, &second).await.unwrap();
+
+  let updated = B {
+    r#as: vec![A { id: 1, name: "foo3".into() }, A { id: 2, name: "foo4".into() }],
+    id: 1,
+    name: "bar3".into(),
+  };
+  let affected_rows = table.update(&mut buffer, &pool, &updated).await.unwrap();
+  assert_eq!(affected_rows.tables(), [("b", 1), ("a", 2)]);
+  assert_eq!(table.read_by_id(&mut buffer, &pool, &&1).await.unwrap(), updated);
+
+  let affected_rows = table.delete_by_id(&mut buffer, &pool, &&2).await.unwrap();
+  assert_eq!(affected_rows.tables(), [("b", 1)]);
+  let affected_rows = table.delete(&mut buffer, &pool, &updated).await.unwrap();
+  assert_eq!(affected_rows.tables(), [("a", 2), ("b", 1)]);
+  assert_eq!(affected_rows.total(), 3);
+  assert_eq!(table.read_all(&mut buffer, &pool).await.unwrap(), []);
+}
+
+#[tokio::test]
+async fn sqlite_backend_upserts_entities() {
+  let pool = pool().await;
+  let mut buffer = String::new();
+  let first = B { r#as: vec![A { id: 1, name: "foo1".into() }], id: 1, name: "bar1".into() };
+  let mut table = Table::<BTableDefs>::default();
+  table.create(&mut buffer, &pool, &first).await.unwrap();
+
+  let resaved = B {
+    r#as: vec![A { id: 1, name: "foo2".into() }, A { id: 2, name: "foo3".into() }],
+    id: 1,
+    name: "bar2".into(),
+  };
+  let _ = table.upsert(&mut buffer, &pool, OnConflict::do_nothing(), &resaved).await.unwrap();
+  assert_eq!(
+    table.read_by_id(&mut buffer, &pool, &&1).await.unwrap(),
+    B {
+      r#as: vec![A { id: 1, name: "foo1".into() }, A { id: 2, name: "foo3".into() }],
+      id: 1,
+      name: "bar1".into()
+    }
+  );
+  let _ = table.upsert(&mut buffer, &pool, OnConflict::do_update(), &resaved).await.unwrap();
+  assert_eq!(table.read_by_id(&mut buffer, &pool, &&1).await.unwrap(), resaved);
+}
+
+#[tokio::test]
+async fn sqlite_backend_feeds_generated_keys_into_associations() {
+  let pool = pool().await;
+  let mut buffer = String::new();
+  let entity = B {
+    r#as: vec![A { id: 0, name: "foo1".into() }, A { id: 0, name: "foo2".into() }],
+    id: 0,
+    name: "bar1".into(),
+  };
+  let mut table = Table::<BTableDefs>::default();
+  table.update_all_table_fields(&entity);
+  *table.id_field_mut().value_mut() = None;
+  for elem in &mut table.associations_mut().0.tables {
+    *elem.id_field_mut().value_mut() = None;
+  }
+
+  let mut mode = SqlWriterMode::parameterized();
+  table
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      &mut buffer,
+      SqliteDialect,
+      &mut mode,
+      InsertOptions::new(),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(
+    buffer,
+    r#"INSERT INTO "b" ("name") VALUES (?1) RETURNING "id";INSERT INTO "a" ("name","id_b") VALUES (?1,?2) RETURNING "id";INSERT INTO "a" ("name","id_b") VALUES (?1,?2) RETURNING "id";"#
+  );
+  let rslts = Sqlite::execute_parameterized(&buffer, &pool, &mode).await.unwrap();
+  assert_eq!(
+    AffectedRows::new(&mode, &rslts).generated_keys(),
+    [("b", BindValue::I64(1)), ("a", BindValue::I64(1)), ("a", BindValue::I64(2))]
+  );
+
+  buffer.clear();
+  assert_eq!(
+    Table::<BTableDefs>::default().read_all(&mut buffer, &pool).await.unwrap(),
+    [B {
+      r#as: vec![A { id: 1, name: "foo1".into() }, A { id: 2, name: "foo2".into() }],
+      id: 1,
+      name: "bar1".into()
+    }]
+  );
+}
+
+#[tokio::test]
+async fn sqlite_backend_batches_sibling_rows() {
+  let pool = pool().await;
+  let mut buffer = String::new();
+  let first = B {
+    r#as: vec![A { id: 1, name: "foo1".into() }, A { id: 2, name: "foo2".into() }],
+    id: 1,
+    name: "bar1".into(),
+  };
+  let mut table = Table::<BTableDefs>::default();
+  let options = InsertOptions::new().with_batch_size(100);
+  let affected_rows = table.create_with_options(&mut buffer, &pool, options, &first).await.unwrap();
+  assert_eq!(affected_rows.tables(), [("b", 1), ("a", 2)]);
+  assert_eq!(table.read_all(&mut buffer, &pool).await.unwrap(), [first]);
+}
+
+#[tokio::test]
+async fn sqlite_backend_runs_operations_inside_transactions() {
+  let pool = pool().await;
+  let mut buffer = String::new();
+  let first = B { r#as: vec![A { id: 1, name: "foo1".into() }], id: 1, name: "bar1".into() };
+  let second = B { r#as: vec![], id: 2, name: "bar2".into() };
+
+  let mut table = Table::<BTableDefs>::default();
+  let read = table
+    .transaction(&pool, async |local_table, transaction| {
+      let _ = local_table.create(&mut buffer, &mut *transaction, &first).await?;
+      local_table.read_by_id(&mut buffer, &mut *transaction, &&1).await
+    })
+    .await
+    .unwrap();
+  assert_eq!(read, first);
+
+  let rslt = table
+    .transaction(&pool, async |local_table, transaction| {
+      let _ = local_table.create(&mut buffer, &mut *transaction, &second).await?;
+      local_table.read_by_id(&mut buffer, &mut *transaction, &&3).await
+    })
+    .await;
+  assert!(rslt.is_err());
+
+  let mut connection = pool.acquire().await.unwrap();
+  assert_eq!(table.read_all(&mut buffer, &mut *connection).await.unwrap(), [first]);
+}
+
+#[tokio::test]
+async fn sqlite_backend_streams_entities() {
+  let pool = pool().await;
+  let mut buffer = String::new();
+  let first = B {
+    r#as: vec![A { id: 1, name: "foo1".into() }, A { id: 2, name: "foo2".into() }],
+    id: 1,
+    name: "bar1".into(),
+  };
+  let second = B { r#as: vec![], id: 2, name: "bar2".into() };
+  let third = B { r#as: vec![A { id: 3, name: "foo3".into() }], id: 3, name: "bar3".into() };
+
+  let mut table = Table::<BTableDefs>::default();
+  let _ = table.create(&mut buffer, &pool, &first).await.unwrap();
+  let _ = table.create(&mut buffer, &pool, &second).await.unwrap();
+  let _ = table.create(&mut buffer, &pool, &third).await.unwrap();
+
+  let table = Table::<BTableDefs>::default();
+  let entities: Vec<_> = table
+    .read_stream(&mut buffer, &pool, EagerLoading::all(), &[], SelectLimit::All, None)
+    .try_collect()
+    .await
+    .unwrap();
+  assert_eq!(entities, [first, second, third]);
+}
+
+#[tokio::test]
+async fn sqlite_backend_only_skips_columns_of_excluded_associations() {
+  let pool = pool().await;
+  let rows = Sqlite::fetch_all("SELECT 1 AS b0__id", &pool, &[]).await.unwrap();
+  let row = SelectedRow::new(Arc::from([("a", 1)]), rows.into_iter().next().unwrap());
+  assert_eq!(row.decode::<i64>("b0__id").unwrap(), 1);
+  assert_eq!(row.decode_opt::<i64>("a1__id").unwrap(), None);
+  assert!(matches!(row.decode_opt::<i64>("b0__idd"), Err(crate::Error::Sqlx(_))));
+  assert!(matches!(row.decode_opt::<i64>("a2__id"), Err(crate::Error::Sqlx(_))));
+}
@@ -1,10 +1,12 @@
 use crate::{
-  buffer_try_push_str, buffer_write_fmt, AuxNodes, FullTableAssociation, SelectLimit,
-  SelectOrderBy, SqlValue, SqlWriter, Table, TableAssociationWrapper, TableAssociations, TableDefs,
-  TableField, TableFields, TableSourceAssociation,
+  buffer_try_push_str, buffer_write_fmt, write_sql_value, AssociationsSchema, AuxNodes, ColumnType,
+  ColumnTypes, EagerLoading, FullTableAssociation, InsertOptions, OrderBy, PrimaryKey, Schema,
+  SelectLimit, SqlDialect, SqlType, SqlValue, SqlWriter, SqlWriterMode, Suffix, Table,
+  TableAssociationWrapper, TableAssociations, TableDefs, TableField, TableFields,
+  TableSourceAssociation, TableStorage,
 };
 use cl_traits::SingleTypeStorage;
-use core::{array, fmt::Display};
+use core::array;
 
 macro_rules! double_tuple_impls {
   ($(
@@ -13,6 +15,30 @@ macro_rules! double_tuple_impls {
     }
   )+) => {
     $(
+      impl<'entity, $($T, $U,)+> AssociationsSchema for ($( TableAssociationWrapper<'entity, $U, $T>, )+)
+      where
+        $(
+          $T: AsRef<[Table<'entity, $U>]> + SingleTypeStorage<Item = Table<'entity, $U>> + TableStorage,
+          $U: TableDefs<'entity>,
+          $U::Associations: AssociationsSchema,
+          $U::Fields: ColumnTypes,
+          $U::PrimaryKeyValue: ColumnTypes,
+        )+
+      {
+        #[inline]
+        fn push_schema(&self, schema: &mut Schema, from_table: &'static str) -> crate::Result<()> {
+          $(
+            schema.push_associated(
+              &self.$idx.association,
+              from_table,
+              &self.$idx.guide,
+              $T::IS_OPTIONAL,
+            )?;
+          )+
+          Ok(())
+        }
+      }
+
       impl<'entity, $($T, $U,)+> TableAssociations for ($( TableAssociationWrapper<'entity, $U, $T>, )+)
       where
         $(
@@ -35,6 +61,16 @@ macro_rules! double_tuple_impls {
             )+
           ].into_iter()
         }
+
+        #[inline]
+        fn has_dependent_tables(&self) -> bool {
+          $(
+            if !self.$idx.association.belongs_to() && !self.$idx.tables.as_ref().is_empty() {
+              return true;
+            }
+          )+
+          false
+        }
       }
 
       impl<'entity, BUFFER, ERR, $($T, $U,)+> SqlWriter<BUFFER> for ($( TableAssociationWrapper<'entity, $U, $T>, )+)
@@ -49,15 +85,50 @@ macro_rules! double_tuple_impls {
       {
         type Error = ERR;
 
+        #[inline]
+        fn push_excluded_table_refs(
+          &self,
+          eager_loading: EagerLoading<'_>,
+          excluded: &mut Vec<(&'static str, Suffix)>,
+        ) {
+          $(
+            if eager_loading.includes(self.$idx.guide.table_ref()) {
+              self.$idx.guide.push_excluded_table_refs(eager_loading, excluded);
+            } else {
+              excluded.push(self.$idx.guide.table_ref());
+            }
+          )+
+        }
+
+        #[inline]
+        fn register_insert_parents(&self, aux: &mut AuxNodes) -> Result<(), Self::Error> {
+          $(
+            // Only owned entities have parents
+            let association = self.$idx.association;
+            if !association.belongs_to() && association.join_table().is_none() {
+              for elem in self.$idx.tables.as_ref() {
+                elem.register_insert_parents(aux)?;
+              }
+            }
+          )+
+          Ok(())
+        }
+
         #[inline]
         fn write_delete(
           &self,
           aux: &mut AuxNodes,
           buffer: &mut BUFFER,
+          dialect: impl SqlDialect,
+          mode: &mut SqlWriterMode,
         ) -> Result<(), Self::Error> {
           $(
-            for elem in self.$idx.tables.as_ref() {
-              elem.write_delete(aux, buffer)?;
+            // Entities of many-to-one and many-to-many associations are independent of the caller
+            let association = self.$idx.association;
+            if !association.belongs_to() && association.join_table().is_none() {
+              for elem in self.$idx.tables.as_ref() {
+                elem.write_delete(aux, buffer, dialect, mode)?;
+              }
             }
           )+
           Ok(())
@@ -68,17 +139,57 @@ macro_rules! double_tuple_impls {
           &self,
           aux: &mut AuxNodes,
           buffer: &mut BUFFER,
+          dialect: impl SqlDialect,
+          mode: &mut SqlWriterMode,
+          options: InsertOptions<'_>,
           table_source_association: &mut Option<TableSourceAssociation<'value, VALUE>>
         ) -> Result<(), Self::Error>
         where
-          VALUE: Display
+          VALUE: PrimaryKey
         {
           $(
-            if let Some(ref mut elem) = table_source_association.as_mut() {
-              *elem.source_field_mut() = self.$idx.association.to_id();
+            if !self.$idx.association.belongs_to() {
+              if let Some(ref mut elem) = table_source_association.as_mut() {
+                *elem.source_association_mut() = self.$idx.association;
+              }
+              Table::write_insert_siblings(
+                aux,
+                buffer,
+                dialect,
+                mode,
+                options,
+                self.$idx.tables.as_ref(),
+                table_source_association
+              )?;
             }
-            for elem in self.$idx.tables.as_ref() {
-              elem.write_insert(aux, buffer, table_source_association)?;
+          )+
+          Ok(())
+        }
+
+        #[inline]
+        fn write_insert_referenced(
+          &self,
+          aux: &mut AuxNodes,
+          buffer: &mut BUFFER,
+          dialect: impl SqlDialect,
+          hash: u64,
+          mode: &mut SqlWriterMode,
+          options: InsertOptions<'_>,
+        ) -> Result<(), Self::Error> {
+          $(
+            if self.$idx.association.belongs_to() {
+              for elem in self.$idx.tables.as_ref() {
+                Table::write_insert_referenced(
+                  aux,
+                  &self.$idx.association,
+                  buffer,
+                  dialect,
+                  hash,
+                  mode,
+                  options,
+                  elem
+                )?;
+              }
             }
           )+
           Ok(())
@@ -88,12 +199,14 @@ macro_rules! double_tuple_impls {
         fn write_select(
           &self,
           buffer: &mut BUFFER,
-          order_by: SelectOrderBy,
+          dialect: impl SqlDialect,
+          eager_loading: EagerLoading<'_>,
+          orders_by: &[OrderBy],
           limit: SelectLimit,
           where_cb: &mut impl FnMut(&mut BUFFER) -> Result<(), Self::Error>,
         ) -> Result<(), Self::Error> {
           $(
-            self.$idx.guide.write_select(buffer, order_by, limit, where_cb)?;
+            self.$idx.guide.write_select(buffer, dialect, eager_loading, orders_by, limit, where_cb)?;
           )+
           Ok(())
         }
@@ -101,10 +214,14 @@ macro_rules! double_tuple_impls {
         #[inline]
         fn write_select_associations(
           &self,
-            buffer: &mut BUFFER,
+          buffer: &mut BUFFER,
+          dialect: impl SqlDialect,
+          eager_loading: EagerLoading<'_>,
         ) -> Result<(), Self::Error> {
           $(
-            self.$idx.guide.write_select_associations(buffer)?;
+            if eager_loading.includes(self.$idx.guide.table_ref()) {
+              self.$idx.guide.write_select_associations(buffer, dialect, eager_loading)?;
+            }
           )+
           Ok(())
         }
@@ -112,18 +229,30 @@ macro_rules! double_tuple_impls {
         #[inline]
         fn write_select_fields(
           &self,
-            buffer: &mut BUFFER,
+          buffer: &mut BUFFER,
+          dialect: impl SqlDialect,
+          eager_loading: EagerLoading<'_>,
         ) -> Result<(), Self::Error> {
           $(
-            self.$idx.guide.write_select_fields(buffer)?;
+            if eager_loading.includes(self.$idx.guide.table_ref()) {
+              self.$idx.guide.write_select_fields(buffer, dialect, eager_loading)?;
+            }
           )+
           Ok(())
         }
 
         #[inline]
-        fn write_select_orders_by(&self, buffer: &mut BUFFER) -> Result<(), Self::Error> {
+        fn write_select_orders_by(
+          &self,
+          buffer: &mut BUFFER,
+          dialect: impl SqlDialect,
+          eager_loading: EagerLoading<'_>,
+          orders_by: &[OrderBy],
+        ) -> Result<(), Self::Error> {
           $(
-            self.$idx.guide.write_select_orders_by(buffer)?;
+            if eager_loading.includes(self.$idx.guide.table_ref()) {
+              self.$idx.guide.write_select_orders_by(buffer, dialect, eager_loading, orders_by)?;
+            }
           )+
           Ok(())
         }
@@ -133,10 +262,12 @@ macro_rules! double_tuple_impls {
           &self,
           aux: &mut AuxNodes,
           buffer: &mut BUFFER,
+          dialect: impl SqlDialect,
+          mode: &mut SqlWriterMode,
         ) -> Result<(), Self::Error> {
           $(
             for elem in self.$idx.tables.as_ref() {
-              elem.write_update(aux, buffer)?;
+              elem.write_update(aux, buffer, dialect, mode)?;
             }
           )+
           Ok(())
@@ -153,6 +284,16 @@ macro_rules! tuple_impls {
     }
   )+) => {
     $(
+      impl<ERR, $($T: SqlType),+> ColumnTypes for ($( TableField<ERR, $T>, )+) {
+        #[inline]
+        fn column_type(idx: usize) -> Option<(ColumnType, bool)> {
+          match idx {
+            $($idx => Some(($T::COLUMN_TYPE, $T::IS_NULLABLE)),)+
+            _ => None,
+          }
+        }
+      }
+
       impl<ERR, $($T: SqlValue),+> TableFields for ($( TableField<ERR, $T>, )+)
       where
         ERR: From<crate::Error>
@@ -166,13 +307,19 @@ macro_rules! tuple_impls {
         }
 
         #[inline]
-        fn write_insert_values<BUFFER>(&self, buffer: &mut BUFFER) -> Result<(), Self::Error>
+        fn write_insert_values<BUFFER, DIALECT>(
+          &self,
+          buffer: &mut BUFFER,
+          dialect: DIALECT,
+          mode: &mut SqlWriterMode,
+        ) -> Result<(), Self::Error>
         where
-          BUFFER: cl_traits::String
+          BUFFER: cl_traits::String,
+          DIALECT: SqlDialect
         {
           $(
             if let &Some(ref elem) = self.$idx.value() {
-              elem.write(buffer)?;
+              write_sql_value(buffer, dialect, mode, elem)?;
               buffer_try_push_str(buffer, ",")?;
             }
           )+
@@ -180,14 +327,23 @@ macro_rules! tuple_impls {
         }
 
         #[inline]
-        fn write_update_values<BUFFER>(&self, buffer: &mut BUFFER) -> Result<(), Self::Error>
+        fn write_update_values<BUFFER, DIALECT>(
+          &self,
+          buffer: &mut BUFFER,
+          dialect: DIALECT,
+          mode: &mut SqlWriterMode,
+        ) -> Result<(), Self::Error>
         where
-          BUFFER: cl_traits::String
+          BUFFER: cl_traits::String,
+          DIALECT: SqlDialect
         {
           $(
             if let &Some(ref elem) = self.$idx.value() {
-              buffer_write_fmt(buffer, format_args!("{}=", self.$idx.name()))?;
-              elem.write(buffer)?;
+              buffer_write_fmt(
+                buffer,
+                format_args!("{q}{}{q}=", self.$idx.name(), q = DIALECT::IDENTIFIER_QUOTE),
+              )?;
+              write_sql_value(buffer, dialect, mode, elem)?;
               buffer_try_push_str(buffer, ",")?;
             }
           )+
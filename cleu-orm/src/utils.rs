@@ -1,4 +1,7 @@
-use crate::{AuxNodes, FullTableAssociation, Suffix, Table, TableDefs};
+use crate::{
+  AuxNodes, BindValue, ColumnType, DbRow, DecodePrimaryKey, FromRowsSuffix, FullTableAssociation,
+  JoinKind, SqlDialect, SqlValue, SqlWriterMode, Suffix, Table, TableDefs,
+};
 use core::fmt::Arguments;
 
 /// Shortcut of `buffer.try_push(...)`
@@ -21,6 +24,119 @@ where
   buffer.write_fmt(args).map_err(|err| E::from(crate::Error::Fmt(err)))
 }
 
+/// Seeks all rows that equals `TD`'s primary key and suffix. Can be `TD` itself or any other
+/// associated/related entity `R` of the related table `RTD`. Keys are decoded and compared
+/// according to [DecodePrimaryKey].
+///
+/// `join_kind` must be the [JoinKind] of the association between `TD` and `RTD`, i.e.,
+/// [crate::TableAssociation::join_kind]. Rows whose primary key columns of `RTD` are NULL are
+/// treated as absent related entities when `join_kind` is [JoinKind::Left] or when `RTD` was
+/// excluded by [crate::EagerLoading]. All other errors are propagated.
+#[inline]
+pub fn seek_related_entities<'entity, B, F, R, ROW, RTD, TD>(
+  buffer: &mut B,
+  rows: &[ROW],
+  suffix: Suffix,
+  suffix_related: Suffix,
+  join_kind: JoinKind,
+  mut cb: F,
+) -> Result<usize, TD::Error>
+where
+  B: cl_traits::String,
+  F: FnMut(R) -> Result<(), TD::Error>,
+  R: FromRowsSuffix<B, ROW, Error = TD::Error>,
+  ROW: DbRow,
+  RTD: TableDefs<'entity>,
+  RTD::PrimaryKeyValue: DecodePrimaryKey<ROW>,
+  TD: TableDefs<'entity>,
+  TD::PrimaryKeyValue: DecodePrimaryKey<ROW>,
+{
+  let first_row = if let Some(elem) = rows.first() {
+    elem
+  } else {
+    return Ok(0);
+  };
+
+  match decode_table_key::<_, _, RTD>(buffer, first_row, suffix_related) {
+    Ok(_) => {}
+    Err(crate::Error::UnexpectedNullValue)
+      if join_kind == JoinKind::Left
+        || first_row
+          .is_excluded(RTD::TABLE_NAME_ALIAS.unwrap_or(RTD::TABLE_NAME), suffix_related) =>
+    {
+      return count_table_rows::<_, _, TD>(buffer, rows, suffix).map_err(Into::into);
+    }
+    Err(err) => return Err(err.into()),
+  }
+
+  let (mut counter, entity) = R::from_rows_suffix(rows, buffer, suffix_related, first_row)?;
+  let previous = decode_table_key::<_, _, TD>(buffer, first_row, suffix)?;
+  cb(entity)?;
+
+  loop {
+    if counter >= rows.len() {
+      break;
+    }
+
+    let row = if let Some(elem) = rows.get(counter) {
+      elem
+    } else {
+      break;
+    };
+
+    if !has_table_key::<_, _, TD>(buffer, &previous, row, suffix)? {
+      break;
+    }
+
+    let curr_rows = rows.get(counter..).unwrap_or_default();
+    let (skip, entity) = R::from_rows_suffix(curr_rows, buffer, suffix_related, row)?;
+    cb(entity)?;
+    counter = counter.wrapping_add(skip);
+  }
+
+  Ok(counter)
+}
+
+/// Seeks the optional entity `R` of the related table `RTD` that belongs to the rows of `TD`'s
+/// primary key and suffix.
+///
+/// The entity is [None] when the primary key columns of `RTD` are NULL, i.e., when a LEFT JOIN
+/// didn't find a related row or the association was excluded by [crate::EagerLoading]. All other
+/// errors are propagated.
+#[inline]
+pub fn seek_optional_entity<'entity, B, R, ROW, RTD, TD>(
+  buffer: &mut B,
+  rows: &[ROW],
+  suffix: Suffix,
+  suffix_related: Suffix,
+) -> Result<(usize, Option<R>), TD::Error>
+where
+  B: cl_traits::String,
+  R: FromRowsSuffix<B, ROW, Error = TD::Error>,
+  ROW: DbRow,
+  RTD: TableDefs<'entity>,
+  RTD::PrimaryKeyValue: DecodePrimaryKey<ROW>,
+  TD: TableDefs<'entity>,
+  TD::PrimaryKeyValue: DecodePrimaryKey<ROW>,
+{
+  let first_row = if let Some(elem) = rows.first() {
+    elem
+  } else {
+    return Ok((0, None));
+  };
+
+  match decode_table_key::<_, _, RTD>(buffer, first_row, suffix_related) {
+    Ok(_) => {
+      let (skip, entity) = R::from_rows_suffix(rows, buffer, suffix_related, first_row)?;
+      return Ok((skip, Some(entity)));
+    }
+    Err(crate::Error::UnexpectedNullValue) => {}
+    Err(err) => return Err(err.into()),
+  }
+
+  Ok((count_table_rows::<_, _, TD>(buffer, rows, suffix)?, None))
+}
+
 /// Writes {table}{suffix}__{field}` into a buffer.
 #[inline]
 pub fn write_column_alias<B>(
@@ -36,6 +152,125 @@ where
   Ok(())
 }
 
+/// Writes a placeholder whose value is the primary key generated by the row at index `row` of the
+/// statement at index `statement`. Generated keys are only known in
+/// [SqlWriterMode::Parameterized], otherwise `NULL` is written.
+#[inline]
+pub(crate) fn write_generated_key<B, D>(
+  buffer: &mut B,
+  dialect: D,
+  mode: &mut SqlWriterMode,
+  statement: usize,
+  row: usize,
+) -> crate::Result<()>
+where
+  B: cl_traits::String,
+  D: SqlDialect,
+{
+  if let SqlWriterMode::Parameterized(ref mut args) = *mode {
+    dialect.write_placeholder(buffer, args.push_generated_key(statement, row))
+  } else {
+    buffer.push("NULL")?;
+    Ok(())
+  }
+}
+
+/// Writes `value` as an escaped literal or as a placeholder, depending on `mode`.
+#[inline]
+pub fn write_sql_value<B, D, V>(
+  buffer: &mut B,
+  dialect: D,
+  mode: &mut SqlWriterMode,
+  value: &V,
+) -> crate::Result<()>
+where
+  B: cl_traits::String,
+  D: SqlDialect,
+  V: SqlValue,
+{
+  match *mode {
+    SqlWriterMode::Inline => value.write(buffer, dialect),
+    SqlWriterMode::Parameterized(ref mut args) => {
+      let bind_value = value.to_bind_value();
+      if let BindValue::Null = bind_value {
+        buffer.push("null")?;
+      } else {
+        dialect.write_placeholder(buffer, args.push(bind_value))?;
+      }
+      Ok(())
+    }
+  }
+}
+
+// Number of leading rows that share the primary key of the first row. Other associations of `TD`
+// can still yield more than one row for an absent related entity.
+#[inline]
+fn count_table_rows<'entity, B, ROW, TD>(
+  buffer: &mut B,
+  rows: &[ROW],
+  suffix: Suffix,
+) -> crate::Result<usize>
+where
+  B: cl_traits::String,
+  TD: TableDefs<'entity>,
+  TD::PrimaryKeyValue: DecodePrimaryKey<ROW>,
+{
+  let Some(first_row) = rows.first() else {
+    return Ok(0);
+  };
+  let previous = decode_table_key::<_, _, TD>(buffer, first_row, suffix)?;
+  let mut counter = 1;
+  for row in rows.get(1..).unwrap_or_default() {
+    if !has_table_key::<_, _, TD>(buffer, &previous, row, suffix)? {
+      break;
+    }
+    counter += 1;
+  }
+  Ok(counter)
+}
+
+// If the primary key of `TD` in `row` is `key`. Following rows can belong to other entities whose
+// `TD` table wasn't joined, in which case the key is NULL.
+#[inline]
+fn has_table_key<'entity, B, ROW, TD>(
+  buffer: &mut B,
+  key: &<TD::PrimaryKeyValue as DecodePrimaryKey<ROW>>::Decoded,
+  row: &ROW,
+  suffix: Suffix,
+) -> crate::Result<bool>
+where
+  B: cl_traits::String,
+  TD: TableDefs<'entity>,
+  TD::PrimaryKeyValue: DecodePrimaryKey<ROW>,
+{
+  match decode_table_key::<_, _, TD>(buffer, row, suffix) {
+    Ok(elem) => Ok(elem == *key),
+    Err(crate::Error::UnexpectedNullValue) => Ok(false),
+    Err(err) => Err(err),
+  }
+}
+
+/// Decodes all primary key columns of `TD` from `row`. See [DecodePrimaryKey].
+#[inline]
+pub(crate) fn decode_table_key<'entity, B, ROW, TD>(
+  buffer: &mut B,
+  row: &ROW,
+  suffix: Suffix,
+) -> crate::Result<<TD::PrimaryKeyValue as DecodePrimaryKey<ROW>>::Decoded>
+where
+  B: cl_traits::String,
+  TD: TableDefs<'entity>,
+  TD::PrimaryKeyValue: DecodePrimaryKey<ROW>,
+{
+  TD::PrimaryKeyValue::decode_primary_key(
+    buffer,
+    TD::PRIMARY_KEY_NAMES,
+    row,
+    suffix,
+    TD::TABLE_NAME,
+  )
+}
+
 pub(crate) fn node_was_already_visited<'entity, TD>(
   aux: &mut AuxNodes,
   table: &Table<'entity, TD>,
@@ -43,21 +278,12 @@ pub(crate) fn node_was_already_visited<'entity, TD>(
 where
   TD: TableDefs<'entity>,
 {
-  let hash = table.instance_hash();
-  match aux
-    .binary_search_by(|&(ref local_hash, _)| local_hash.cmp(&hash))
-    .and_then(|idx| aux.get(idx).map(|elem| elem.1).ok_or(idx))
-  {
-    Err(could_be_idx) => aux.insert(could_be_idx, (hash, TD::TABLE_NAME)),
-    Ok(existent_table_name) => {
-      if existent_table_name == TD::TABLE_NAME {
-        return Ok(true);
-      } else {
-        return Err(crate::Error::HashCollision(hash, existent_table_name, TD::TABLE_NAME));
-      }
-    }
+  // Instances without primary key values, like the ones with database-generated keys, can't be
+  // identified
+  if table.id_field().value().is_none() {
+    return Ok(false);
   }
-  Ok(false)
+  aux.visit(table.instance_hash(), TD::TABLE_NAME)
 }
 
 #[inline]
@@ -81,8 +307,85 @@ where
 }
 
 #[inline]
-pub(crate) fn write_full_select_field<B>(
+pub(crate) fn write_hex<B>(buffer: &mut B, bytes: &[u8]) -> crate::Result<()>
+where
+  B: cl_traits::String,
+{
+  for byte in bytes {
+    buffer.write_fmt(format_args!("{byte:02x}"))?;
+  }
+  Ok(())
+}
+
+/// Writes comma-separated quoted names, e.g., the columns of a key
+#[inline]
+pub(crate) fn write_names<B, D>(buffer: &mut B, _: D, names: &[&str]) -> crate::Result<()>
+where
+  B: cl_traits::String,
+  D: SqlDialect,
+{
+  let q = D::IDENTIFIER_QUOTE;
+  for (idx, name) in names.iter().enumerate() {
+    let comma = if idx == 0 { "" } else { "," };
+    buffer.write_fmt(format_args!("{comma}{q}{name}{q}"))?;
+  }
+  Ok(())
+}
+
+#[inline]
+pub(crate) fn write_statement_terminator<B, D>(
+  buffer: &mut B,
+  _: D,
+  mode: &mut SqlWriterMode,
+  returned_key: Option<ColumnType>,
+  table: &'static str,
+) -> crate::Result<()>
+where
+  B: cl_traits::String,
+  D: SqlDialect,
+{
+  buffer.write_fmt(format_args!("{}", D::STATEMENT_TERMINATOR))?;
+  if let SqlWriterMode::Parameterized(ref mut args) = *mode {
+    args.finish_statement(buffer.as_ref().len(), returned_key, table);
+  }
+  Ok(())
+}
+
+/// Writes `s` as a string literal that starts with `prefix` and ends with a single quote.
+///
+/// Single quotes, and backslashes if `escape_backslashes` is `true`, are doubled. NUL bytes are
+/// rejected because they can't be stored in textual columns.
+#[inline]
+pub(crate) fn write_str_literal<B>(
+  buffer: &mut B,
+  prefix: &str,
+  s: &str,
+  escape_backslashes: bool,
+) -> crate::Result<()>
+where
+  B: cl_traits::String,
+{
+  if s.contains('\0') {
+    return Err(crate::Error::NulByteInStringLiteral);
+  }
+  buffer.push(prefix)?;
+  let mut start: usize = 0;
+  let specials: &[char] = if escape_backslashes { &['\'', '\\'] } else { &['\''] };
+  for (idx, special) in s.match_indices(specials) {
+    buffer.push(s.get(start..idx).unwrap_or_default())?;
+    buffer.push(special)?;
+    buffer.push(special)?;
+    start = idx.wrapping_add(1);
+  }
+  buffer.push(s.get(start..).unwrap_or_default())?;
+  buffer.push("'")?;
+  Ok(())
+}
+
+#[inline]
+pub(crate) fn write_full_select_field<B, D>(
   buffer: &mut B,
+  dialect: D,
   table: &str,
   table_alias: Option<&str>,
   suffix: Suffix,
@@ -90,16 +393,19 @@ pub(crate) fn write_full_select_field<B>(
 ) -> crate::Result<()>
 where
   B: cl_traits::String,
+  D: SqlDialect,
 {
   let actual_table = table_alias.unwrap_or(table);
-  write_select_field(buffer, table, table_alias, suffix, field)?;
-  buffer.write_fmt(format_args!(" AS {actual_table}{suffix}__{field}"))?;
+  write_select_field(buffer, dialect, table, table_alias, suffix, field)?;
+  let q = D::IDENTIFIER_QUOTE;
+  buffer.write_fmt(format_args!(" AS {q}{actual_table}{suffix}__{field}{q}"))?;
   Ok(())
 }
 
 #[inline]
-pub(crate) fn write_select_field<B>(
+pub(crate) fn write_select_field<B, D>(
   buffer: &mut B,
+  _: D,
   table: &str,
   table_alias: Option<&str>,
   suffix: Suffix,
@@ -107,40 +413,70 @@ pub(crate) fn write_select_field<B>(
 ) -> crate::Result<()>
 where
   B: cl_traits::String,
+  D: SqlDialect,
 {
   let actual_table = table_alias.unwrap_or(table);
-  buffer.write_fmt(format_args!("\"{actual_table}{suffix}\".{field}"))?;
+  let q = D::IDENTIFIER_QUOTE;
+  buffer.write_fmt(format_args!("{q}{actual_table}{suffix}{q}.{q}{field}{q}"))?;
   Ok(())
 }
 
 #[inline]
-pub(crate) fn write_select_join<B>(
+pub(crate) fn write_select_join<B, D>(
   buffer: &mut B,
+  _: D,
   from_table: &str,
   from_table_suffix: Suffix,
   full_association: FullTableAssociation,
 ) -> crate::Result<()>
 where
   B: cl_traits::String,
+  D: SqlDialect,
 {
   let association = full_association.association();
+  let join = association.join_kind().keyword();
+  let q = D::IDENTIFIER_QUOTE;
+  let table_relationship_alias =
+    full_association.to_table_alias().unwrap_or_else(|| full_association.to_table());
+  let to_table_suffix = full_association.to_table_suffix();
+  // The join table shares the suffix of the callee, which makes its alias unique
+  if let Some(join_table) = association.join_table() {
+    buffer.write_fmt(format_args!(
+      "{join} {q}{jt}{q} AS {q}{jt}{to_table_suffix}{q} ON \
+       {q}{from_table}{from_table_suffix}{q}.{q}{table_id}{q} = \
+       {q}{jt}{to_table_suffix}{q}.{q}{jt_from}{q} {join} {q}{table_relationship}{q} AS \
+       {q}{table_relationship_alias}{to_table_suffix}{q} ON \
+       {q}{jt}{to_table_suffix}{q}.{q}{jt_to}{q} = \
+       {q}{table_relationship_alias}{to_table_suffix}{q}.{q}{table_relationship_id}{q}",
+      jt = join_table.name(),
+      jt_from = join_table.from_column(),
+      jt_to = join_table.to_column(),
+      table_id = association.from_id(),
+      table_relationship = full_association.to_table(),
+      table_relationship_id = association.to_id(),
+    ))?;
+    return Ok(());
+  }
   buffer.write_fmt(format_args!(
-    "LEFT JOIN \"{table_relationship}\" AS \"{table_relationship_alias}{to_table_suffix}\" ON \
-     \"{from_table}{from_table_suffix}\".{table_id} = \
-     \"{table_relationship_alias}{to_table_suffix}\".{table_relationship_id}",
-    table_id = association.from_id(),
+    "{join} {q}{table_relationship}{q} AS {q}{table_relationship_alias}{to_table_suffix}{q} ON ",
     table_relationship = full_association.to_table(),
-    table_relationship_alias =
-      full_association.to_table_alias().unwrap_or_else(|| full_association.to_table()),
-    table_relationship_id = association.to_id(),
-    to_table_suffix = full_association.to_table_suffix(),
   ))?;
+  for (idx, (table_id, table_relationship_id)) in association.ids().enumerate() {
+    if idx > 0 {
+      buffer.push(" AND ")?;
+    }
+    buffer.write_fmt(format_args!(
+      "{q}{from_table}{from_table_suffix}{q}.{q}{table_id}{q} = \
+       {q}{table_relationship_alias}{to_table_suffix}{q}.{q}{table_relationship_id}{q}",
+    ))?;
+  }
   Ok(())
 }
 
 #[inline]
-pub(crate) fn write_select_order_by<B>(
+pub(crate) fn write_select_order_by<B, D>(
   buffer: &mut B,
+  _: D,
   table: &str,
   table_alias: Option<&str>,
   suffix: Suffix,
@@ -148,8 +484,10 @@ pub(crate) fn write_select_order_by<B>(
 ) -> crate::Result<()>
 where
   B: cl_traits::String,
+  D: SqlDialect,
 {
   let actual_table = table_alias.unwrap_or(table);
-  buffer.write_fmt(format_args!("\"{actual_table}{suffix}\".{field}",))?;
+  let q = D::IDENTIFIER_QUOTE;
+  buffer.write_fmt(format_args!("{q}{actual_table}{suffix}{q}.{q}{field}{q}"))?;
   Ok(())
 }